@@ -0,0 +1,62 @@
+//! Times jamming and cueing a large noun.
+//!
+//! Run with `cargo bench --bench jam_cue`.
+
+use noun::{
+    atom::Atom,
+    cell::Cell,
+    serdes::{Cue, Jam},
+    Noun, Rc,
+};
+use std::time::{Duration, Instant};
+
+/// Number of times each operation is timed.
+const ITERS: u32 = 10;
+
+/// Returns the next value of a linear congruential generator.
+fn next(seed: &mut u64) -> u64 {
+    *seed = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+    *seed >> 32
+}
+
+/// Builds a balanced tree with `2^depth` leaves of varying widths, where roughly one in four
+/// subtrees repeats its sibling so that the encoding contains backreferences.
+fn tree(depth: u32, seed: &mut u64) -> Rc<Noun> {
+    if depth == 0 {
+        let bits = next(seed);
+        let width = (bits % 256) as usize;
+        let atom = (Atom::from(bits) << width) | Atom::from(width);
+        return Rc::<Noun>::from(atom);
+    }
+    let head = tree(depth - 1, seed);
+    let tail = if next(seed).is_multiple_of(4) {
+        head.clone()
+    } else {
+        tree(depth - 1, seed)
+    };
+    Rc::<Noun>::from(Cell::from([head, tail]))
+}
+
+fn time<T>(mut f: impl FnMut() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let mut out = f();
+    for _ in 1..ITERS {
+        out = f();
+    }
+    (out, start.elapsed() / ITERS)
+}
+
+fn main() {
+    let noun = tree(16, &mut 0x2545_f491_4f6c_dd1d);
+
+    let (jammed, jam_time) = time(|| (&*noun).jam());
+    let (cued, cue_time) = time(|| Noun::cue(jammed.clone()).expect("cue"));
+    assert_eq!(cued, *noun);
+
+    println!(
+        "jammed noun: {} bytes; jam: {:?}/iter; cue: {:?}/iter",
+        jammed.as_bytes().len(),
+        jam_time,
+        cue_time
+    );
+}
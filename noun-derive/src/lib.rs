@@ -0,0 +1,535 @@
+//! Derive macros for the `ToNoun` and `FromNoun` traits of the `noun` crate, which re-exports them
+//! from `noun::convert` when its `derive` feature is enabled.
+//!
+//! # Layout
+//!
+//! - A struct converts to a tuple of its fields that nests to the right: `[a b c]` for a struct
+//!   with the fields `a`, `b` and `c`. A struct with one field converts to that field, and a
+//!   struct with no fields converts to `~`.
+//! - An enum converts to a cell whose head is a tag naming the variant and whose tail is the
+//!   variant's fields laid out like a struct's: `[%tag a b c]`. A variant with no fields converts
+//!   to its bare tag, `%tag`.
+//!
+//! A variant's tag is its name in kebab case (`WorkDone` is `%work-done`) unless renamed with the
+//! `tag` attribute. A struct with a `tag` attribute is laid out like an enum variant.
+//!
+//! Both derives also implement `From<T>` for `Noun` and `TryFrom<&Noun>` for `T`, so derived types
+//! can be used with the `convert!` macro. A failure to convert a field is reported as a
+//! `convert::Error::Field` whose path names the field, such as `Work.job.0`.
+//!
+//! A field that holds an atom written in an aura, such as a ship name or a date, has the type
+//! `noun::convert::AuraAtom`, which checks that the atom is valid in the aura when it's created,
+//! so converting it to a noun can't fail. An `Atom` field can instead name its aura with the
+//! `aura` attribute, in which case the atom is only checked when converting from a noun.
+//!
+//! # Attributes
+//!
+//! - `#[noun(tag = "...")]` on a variant or struct sets its tag.
+//! - `#[noun(crate = "...")]` on a struct or enum sets the path to the `noun` crate, which is
+//!   `::noun` by default.
+//! - `#[noun(aura = "...")]` on an `Atom` field checks that the atom is valid in the aura, like an
+//!   `AuraAtom` would, when converting from a noun. The aura is one of `ud`, `ux`, `ub`, `uv`,
+//!   `uw`, `t`, `tas`, `p` and `da`.
+//! - `#[noun(nullable)]` on an `Option` field stores `None` as `~` and `Some(x)` as `x` itself
+//!   rather than as the unit `[~ x]`, which suits values that are never null.
+//! - `#[noun(skip)]` on a field leaves it out of the noun. It's set to its [`Default`] when
+//!   converting from a noun.
+//!
+//! # Examples
+//!
+//! ```
+//! use noun::{
+//!     convert::{aura, AuraAtom, FromNoun, ToNoun},
+//!     Noun,
+//! };
+//!
+//! #[derive(Debug, FromNoun, PartialEq, ToNoun)]
+//! enum Event {
+//!     Poke {
+//!         ship: AuraAtom<aura::P>,
+//!         mark: AuraAtom<aura::Tas>,
+//!         data: Noun,
+//!     },
+//!     #[noun(tag = "kick")]
+//!     Leave,
+//! }
+//!
+//! let event = Event::Poke {
+//!     ship: "~zod".parse().unwrap(),
+//!     mark: "json".parse().unwrap(),
+//!     data: Noun::null(),
+//! };
+//! assert_eq!(event.to_noun(), "[%poke ~zod %json 0]".parse::<Noun>().unwrap());
+//! assert_eq!(Event::from_noun(&event.to_noun()).unwrap(), event);
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Error, Fields, Generics, Ident,
+    LitStr, Path, Result,
+};
+
+/// The auras an `Atom` field can be checked in, and the `noun::convert::aura` markers that name
+/// them.
+const AURAS: [(&str, &str); 9] = [
+    ("ud", "Ud"),
+    ("ux", "Ux"),
+    ("ub", "Ub"),
+    ("uv", "Uv"),
+    ("uw", "Uw"),
+    ("t", "T"),
+    ("tas", "Tas"),
+    ("p", "P"),
+    ("da", "Da"),
+];
+
+/// Derives `ToNoun`, along with `From<T>` for `Noun`. See the [crate] docs for the layout and
+/// attributes.
+#[proc_macro_derive(ToNoun, attributes(noun))]
+pub fn derive_to_noun(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    to_noun(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derives `FromNoun`, along with `TryFrom<&Noun>` for `T`. See the [crate] docs for the layout
+/// and attributes.
+#[proc_macro_derive(FromNoun, attributes(noun))]
+pub fn derive_from_noun(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_noun(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// How a field is stored in a noun.
+enum Encoding {
+    /// With the field type's `ToNoun` and `FromNoun` implementations.
+    Plain,
+    /// As an atom that's checked to be valid in an aura, named by its `noun::convert::aura`
+    /// marker, when converting from a noun.
+    Aura(Ident),
+    /// As `~` or the value itself.
+    Nullable,
+    /// Not at all.
+    Skip,
+}
+
+/// A field of a struct or enum variant.
+struct Field {
+    /// The field's name or, for a tuple field, its index.
+    name: String,
+    /// The variable the field is bound to.
+    var: Ident,
+    encoding: Encoding,
+}
+
+/// A struct or enum variant.
+struct Shape {
+    /// The path to the `noun` crate.
+    krate: Path,
+    tag: Option<String>,
+    /// How the fields are named, to build a pattern or constructor.
+    fields: Fields,
+    parsed: Vec<Field>,
+}
+
+impl Shape {
+    /// Parses a struct, whose attributes may also include `crate`, or an enum variant.
+    fn new(
+        krate: &Path,
+        attrs: &[Attribute],
+        fields: &Fields,
+        default_tag: Option<String>,
+    ) -> Result<Self> {
+        let is_struct = default_tag.is_none();
+        let mut tag = default_tag;
+        parse_attrs(attrs, |name, meta| match name {
+            "tag" => {
+                tag = Some(meta.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            }
+            "crate" if is_struct => {
+                meta.value()?.parse::<LitStr>()?;
+                Ok(())
+            }
+            _ => Err(meta.error("expected `tag`")),
+        })?;
+        let parsed = fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let name = match &field.ident {
+                    Some(ident) => ident.to_string(),
+                    None => i.to_string(),
+                };
+                let mut encoding = Encoding::Plain;
+                parse_attrs(&field.attrs, |attr, meta| {
+                    if !matches!(encoding, Encoding::Plain) {
+                        return Err(meta.error("conflicting noun attributes"));
+                    }
+                    encoding = match attr {
+                        "aura" => {
+                            let lit = meta.value()?.parse::<LitStr>()?;
+                            match AURAS.iter().find(|(aura, _)| *aura == lit.value()) {
+                                Some((_, marker)) => Encoding::Aura(format_ident!("{}", marker)),
+                                None => return Err(Error::new(lit.span(), "unsupported aura")),
+                            }
+                        }
+                        "nullable" => Encoding::Nullable,
+                        "skip" => Encoding::Skip,
+                        _ => return Err(meta.error("expected `aura`, `nullable` or `skip`")),
+                    };
+                    Ok(())
+                })?;
+                Ok(Field {
+                    name,
+                    var: format_ident!("__field_{}", i),
+                    encoding,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            krate: krate.clone(),
+            tag,
+            fields: fields.clone(),
+            parsed,
+        })
+    }
+
+    /// Returns a pattern or constructor for `path` that binds each field to its variable.
+    fn bind(&self, path: TokenStream2) -> TokenStream2 {
+        let vars = self.parsed.iter().map(|field| &field.var);
+        match &self.fields {
+            Fields::Named(named) => {
+                let names = named.named.iter().map(|field| &field.ident);
+                quote!(#path { #(#names: #vars),* })
+            }
+            Fields::Unnamed(_) => quote!(#path(#(#vars),*)),
+            Fields::Unit => path,
+        }
+    }
+
+    /// Returns the fields that are stored in the noun.
+    fn stored(&self) -> impl Iterator<Item = &Field> {
+        self.parsed
+            .iter()
+            .filter(|field| !matches!(field.encoding, Encoding::Skip))
+    }
+
+    /// Returns an expression that converts the bound fields into a `Noun`.
+    fn encode(&self) -> TokenStream2 {
+        let krate = &self.krate;
+        let tag = self
+            .tag
+            .iter()
+            .map(|tag| quote!(#krate::Noun::from(#krate::atom::Atom::from(#tag))));
+        let fields = self.stored().map(|field| {
+            let var = &field.var;
+            match &field.encoding {
+                Encoding::Plain | Encoding::Aura(_) => {
+                    quote!(#krate::convert::ToNoun::to_noun(#var))
+                }
+                Encoding::Nullable => quote! {
+                    match #var {
+                        ::std::option::Option::Some(value) => #krate::convert::ToNoun::to_noun(value),
+                        ::std::option::Option::None => #krate::Noun::null(),
+                    }
+                },
+                Encoding::Skip => unreachable!("skipped fields aren't stored"),
+            }
+        });
+        let elems: Vec<_> = tag.chain(fields).collect();
+        match elems.len() {
+            0 => quote!(#krate::Noun::null()),
+            1 => elems.into_iter().next().expect("element"),
+            _ => quote! {
+                #krate::Noun::from(#krate::cell::Cell::from(::std::vec![
+                    #(#krate::Rc::new(#elems)),*
+                ]))
+            },
+        }
+    }
+
+    /// Returns statements that convert `noun`, the fields stored in a noun after any tag, into
+    /// the fields' variables, returning early on error. Paths in errors start with `prefix`.
+    fn decode(&self, noun: TokenStream2, prefix: &str) -> TokenStream2 {
+        let krate = &self.krate;
+        let stored: Vec<_> = self.stored().collect();
+        let mut stmts = vec![quote!(let __rest: &#krate::Noun = #noun;)];
+        if stored.is_empty() {
+            stmts.push(quote! {
+                if !__rest.is_null() {
+                    return ::std::result::Result::Err(#krate::convert::Error::ExpectedNull);
+                }
+            });
+        }
+        for (i, field) in stored.iter().enumerate() {
+            let var = &field.var;
+            let path = format!("{}{}", prefix, field.name);
+            let value = if i + 1 < stored.len() {
+                stmts.push(quote! {
+                    let (__value, __rest) = #krate::convert::split_cell(__rest)
+                        .map_err(|err| err.in_field(#path))?;
+                });
+                quote!(__value)
+            } else {
+                quote!(__rest)
+            };
+            let convert = match &field.encoding {
+                Encoding::Plain => quote!(#krate::convert::FromNoun::from_noun(#value)),
+                Encoding::Aura(marker) => quote! {
+                    <#krate::convert::AuraAtom<#krate::convert::aura::#marker>
+                        as #krate::convert::FromNoun>::from_noun(#value)
+                        .map(#krate::convert::AuraAtom::into_atom)
+                },
+                Encoding::Nullable => quote! {
+                    if #value.is_null() {
+                        ::std::result::Result::Ok(::std::option::Option::None)
+                    } else {
+                        #krate::convert::FromNoun::from_noun(#value).map(::std::option::Option::Some)
+                    }
+                },
+                Encoding::Skip => unreachable!("skipped fields aren't stored"),
+            };
+            stmts.push(quote!(let #var = #convert.map_err(|err| err.in_field(#path))?;));
+        }
+        for field in &self.parsed {
+            if let Encoding::Skip = field.encoding {
+                let var = &field.var;
+                stmts.push(quote!(let #var = ::std::default::Default::default();));
+            }
+        }
+        quote!(#(#stmts)*)
+    }
+}
+
+/// Calls `f` with the name and contents of each item of every `#[noun(...)]` attribute.
+fn parse_attrs(
+    attrs: &[Attribute],
+    mut f: impl FnMut(&str, &syn::meta::ParseNestedMeta) -> Result<()>,
+) -> Result<()> {
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("noun")) {
+        attr.parse_nested_meta(|meta| {
+            let name = meta
+                .path
+                .get_ident()
+                .map(Ident::to_string)
+                .unwrap_or_default();
+            f(&name, &meta)
+        })?;
+    }
+    Ok(())
+}
+
+/// Returns the path to the `noun` crate: `::noun` unless set with the `crate` attribute.
+fn crate_path(input: &DeriveInput) -> Result<Path> {
+    let mut krate = parse_quote!(::noun);
+    parse_attrs(&input.attrs, |name, meta| {
+        match name {
+            "crate" => krate = meta.value()?.parse::<LitStr>()?.parse()?,
+            "tag" if matches!(input.data, Data::Struct(_)) => {
+                meta.value()?.parse::<LitStr>()?;
+            }
+            _ => return Err(meta.error("expected `crate`")),
+        }
+        Ok(())
+    })?;
+    Ok(krate)
+}
+
+/// Converts a variant name like `WorkDone` into a tag like `work-done`.
+fn kebab_case(name: &str) -> String {
+    let mut tag = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            tag.push('-');
+        }
+        tag.extend(c.to_lowercase());
+    }
+    tag
+}
+
+/// Returns `generics` with `bound` added to each type parameter.
+fn with_bound(generics: &Generics, bound: TokenStream2) -> Generics {
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(#bound));
+    }
+    generics
+}
+
+fn to_noun(input: &DeriveInput) -> Result<TokenStream2> {
+    let krate = crate_path(input)?;
+    let ident = &input.ident;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let shape = Shape::new(&krate, &input.attrs, &data.fields, None)?;
+            let bind = shape.bind(quote!(Self));
+            let to_noun = shape.encode();
+            quote! {
+                #[allow(unused_variables)]
+                let #bind = self;
+                #to_noun
+            }
+        }
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let name = &variant.ident;
+                    let tag = kebab_case(&name.to_string());
+                    let shape = Shape::new(&krate, &variant.attrs, &variant.fields, Some(tag))?;
+                    let bind = shape.bind(quote!(Self::#name));
+                    let to_noun = shape.encode();
+                    Ok(quote!(#[allow(unused_variables)] #bind => #to_noun,))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                input,
+                "ToNoun can't be derived for unions",
+            ));
+        }
+    };
+
+    let generics = with_bound(&input.generics, quote!(#krate::convert::ToNoun));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #krate::convert::ToNoun for #ident #ty_generics #where_clause {
+            fn to_noun(&self) -> #krate::Noun {
+                #body
+            }
+        }
+
+        impl #impl_generics ::std::convert::From<#ident #ty_generics> for #krate::Noun #where_clause {
+            fn from(value: #ident #ty_generics) -> Self {
+                #krate::convert::ToNoun::to_noun(&value)
+            }
+        }
+    })
+}
+
+fn from_noun(input: &DeriveInput) -> Result<TokenStream2> {
+    let krate = crate_path(input)?;
+    let ident = &input.ident;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let shape = Shape::new(&krate, &input.attrs, &data.fields, None)?;
+            let construct = shape.bind(quote!(Self));
+            match &shape.tag {
+                Some(tag) => {
+                    let fields = shape.decode(quote!(__fields), "");
+                    let split = if shape.stored().next().is_some() {
+                        quote!(let (__tag, __fields) = #krate::convert::split_cell(noun)?;)
+                    } else {
+                        quote!(let (__tag, __fields) = (noun, &#krate::Noun::null());)
+                    };
+                    quote! {
+                        #split
+                        match __tag {
+                            #krate::Noun::Atom(__tag) if *__tag == #tag => {}
+                            #krate::Noun::Atom(_) => {
+                                return ::std::result::Result::Err(#krate::convert::Error::InvalidTag);
+                            }
+                            #krate::Noun::Cell(_) => {
+                                return ::std::result::Result::Err(#krate::convert::Error::UnexpectedCell);
+                            }
+                        }
+                        #fields
+                        ::std::result::Result::Ok(#construct)
+                    }
+                }
+                None => {
+                    let fields = shape.decode(quote!(noun), "");
+                    quote! {
+                        #fields
+                        ::std::result::Result::Ok(#construct)
+                    }
+                }
+            }
+        }
+        Data::Enum(data) => {
+            let mut bare = Vec::new();
+            let mut tagged = Vec::new();
+            for variant in &data.variants {
+                let name = &variant.ident;
+                let tag = kebab_case(&name.to_string());
+                let shape = Shape::new(&krate, &variant.attrs, &variant.fields, Some(tag))?;
+                let tag = shape.tag.as_deref().expect("tag");
+                let construct = shape.bind(quote!(Self::#name));
+                if shape.stored().next().is_none() {
+                    let fields = shape.decode(quote!(&#krate::Noun::null()), "");
+                    bare.push(quote! {
+                        if *__tag == #tag {
+                            #fields
+                            return ::std::result::Result::Ok(#construct);
+                        }
+                    });
+                } else {
+                    let fields = shape.decode(quote!(__fields), &format!("{}.", name));
+                    tagged.push(quote! {
+                        if *__tag == #tag {
+                            #fields
+                            return ::std::result::Result::Ok(#construct);
+                        }
+                    });
+                }
+            }
+            quote! {
+                match noun {
+                    #krate::Noun::Atom(__tag) => {
+                        #(#bare)*
+                    }
+                    #krate::Noun::Cell(__cell) => {
+                        let __fields = __cell.tail_ref();
+                        match __cell.head_ref() {
+                            #krate::Noun::Atom(__tag) => {
+                                #(#tagged)*
+                            }
+                            #krate::Noun::Cell(_) => {
+                                return ::std::result::Result::Err(#krate::convert::Error::UnexpectedCell);
+                            }
+                        }
+                    }
+                }
+                ::std::result::Result::Err(#krate::convert::Error::InvalidTag)
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                input,
+                "FromNoun can't be derived for unions",
+            ));
+        }
+    };
+
+    let generics = with_bound(&input.generics, quote!(#krate::convert::FromNoun));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #krate::convert::FromNoun for #ident #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn from_noun(noun: &#krate::Noun) -> ::std::result::Result<Self, #krate::convert::Error> {
+                #body
+            }
+        }
+
+        impl #impl_generics ::std::convert::TryFrom<&#krate::Noun> for #ident #ty_generics #where_clause {
+            type Error = #krate::convert::Error;
+
+            fn try_from(noun: &#krate::Noun) -> ::std::result::Result<Self, Self::Error> {
+                <Self as #krate::convert::FromNoun>::from_noun(noun)
+            }
+        }
+    })
+}
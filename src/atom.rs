@@ -1,9 +1,15 @@
+use crate::{mug, ob, print::Aura, time};
 use std::{
+    borrow::Cow,
+    cmp::Ordering,
     collections::hash_map::DefaultHasher,
     ffi::OsStr,
     fmt::{Display, Error, Formatter},
     hash::Hasher,
+    mem,
+    ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Rem, Shl, Shr, Sub},
     str::{self, Utf8Error},
+    time::{Duration, SystemTime},
 };
 
 /// Returns the length in bits of a sequence of bytes.
@@ -23,6 +29,7 @@ pub struct Builder {
     bit_idx: usize,
 }
 
+#[allow(clippy::new_without_default)]
 impl Builder {
     /// Creates an empty atom builder.
     pub fn new() -> Self {
@@ -54,11 +61,81 @@ impl Builder {
         self.bit_idx += 1;
     }
 
+    /// Pushes the low `n` bits of `bits` onto the end of this builder, from least significant to
+    /// most significant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than 64.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// let mut builder = Atom::builder();
+    /// builder.push_bit(true);
+    /// builder.push_bits(0b1_0110, 5);
+    /// assert_eq!(builder.pos(), 6);
+    /// assert_eq!(builder.into_atom(), 0b10_1101u8);
+    /// ```
+    pub fn push_bits(&mut self, bits: u64, n: usize) {
+        assert!(n <= 64, "cannot push more than 64 bits at a time");
+        let mut bits = if n == 64 { bits } else { bits & ((1 << n) - 1) };
+        let mut remaining = n;
+        let end = (self.bit_idx + n).div_ceil(8);
+        if self.bytes.len() < end {
+            self.bytes.resize(end, 0);
+        }
+        // Bits at and above `bit_idx` are always zero, so they can be or-ed into place a byte at a
+        // time.
+        while remaining > 0 {
+            let shift = self.bit_idx % 8;
+            let take = remaining.min(8 - shift);
+            self.bytes[self.bit_idx / 8] |= (bits as u8) << shift;
+            bits = bits.checked_shr(take as u32).unwrap_or(0);
+            remaining -= take;
+            self.bit_idx += take;
+        }
+    }
+
+    /// Pushes the bits of `atom` onto the end of this builder, from least significant to most
+    /// significant.
+    ///
+    /// The bits are copied a byte at a time regardless of whether the end of this builder is
+    /// byte-aligned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// let mut builder = Atom::builder();
+    /// builder.push_bits(0b01, 2);
+    /// builder.push_atom(&Atom::from(0x1ffu16));
+    /// assert_eq!(builder.pos(), 11);
+    /// assert_eq!(builder.into_atom(), 0b111_1111_1101u16);
+    /// ```
+    pub fn push_atom(&mut self, atom: &Atom) {
+        splice(
+            &mut self.bytes,
+            self.bit_idx,
+            atom.as_bytes(),
+            atom.bit_len(),
+        );
+        self.bit_idx += atom.bit_len();
+    }
+
+    /// Removes and returns the complete bytes at the start of this builder, leaving only the
+    /// partially built last byte, if any.
+    pub(crate) fn drain_bytes(&mut self) -> Vec<u8> {
+        let byte_len = self.bit_idx / 8;
+        // Bytes past the last partially built byte are always zero, so they can be discarded.
+        let rest = self.bytes[byte_len..self.bit_idx.div_ceil(8)].to_vec();
+        self.bit_idx -= byte_len * 8;
+        self.bytes.truncate(byte_len);
+        mem::replace(&mut self.bytes, rest)
+    }
+
     /// Converts this builder into an `Atom`, consuming the builder.
     pub fn into_atom(self) -> Atom {
-        let bytes = self.bytes;
-        let bit_len = bit_len(&bytes[..]);
-        Atom { bytes, bit_len }
+        Atom::from(self.bytes)
     }
 }
 
@@ -70,6 +147,7 @@ impl Builder {
 ///   like primitive unsigned integers, strings, and string slices;
 /// - iterated over a single bit at a time;
 /// - compared to other atoms and other atom-like types;
+/// - combined with other atoms arithmetically and bitwise;
 /// - pretty-printed as a hexadecimal number;
 /// - converted into a noun, a primitive unsigned integer type, or a string slice.
 ///
@@ -90,6 +168,8 @@ impl Builder {
 /// let atom = Atom::from(0u8);
 /// assert_eq!(atom, 0u8);
 /// ```
+// `bit_len` is determined by `bytes`, so comparing only `bytes` agrees with the derived `Hash`.
+#[allow(clippy::derived_hash_with_manual_eq)]
 #[derive(Eq, Clone, Debug, Hash)]
 pub struct Atom {
     bytes: Vec<u8>,
@@ -146,6 +226,23 @@ impl Atom {
         hasher.finish()
     }
 
+    /// Computes the [mug] of this atom, Urbit's 31-bit hash of a noun.
+    ///
+    /// Unlike [`hash()`](Self::hash()), the mug is stable across Rust versions and equal to the mug
+    /// the Urbit runtime computes for the same atom.
+    ///
+    /// [mug]: https://developers.urbit.org/reference/hoon/stdlib/2e#mug
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::null().mug(), 0x79ff_04e8);
+    /// assert_eq!(Atom::from("Hello, world!").mug(), 0x4d44_1035);
+    /// ```
+    pub fn mug(&self) -> u32 {
+        mug::mug_bytes(self.as_bytes())
+    }
+
     /// Converts this atom into a byte slice.
     pub fn as_bytes(&self) -> &[u8] {
         &self.bytes
@@ -254,7 +351,7 @@ impl Atom {
     }
 
     /// Returns a bitwise iterator over this atom.
-    pub fn iter(&self) -> Iter {
+    pub fn iter(&self) -> Iter<'_> {
         Iter {
             atom: self,
             bit_idx: 0,
@@ -264,19 +361,16 @@ impl Atom {
 }
 
 impl Display for Atom {
+    /// Prints this atom in hexadecimal as Hoon does (`@ux`), with digits in groups of four.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(0x100u16).to_string(), "0x100");
+    /// assert_eq!(Atom::from(0xdead_beefu32).to_string(), "0xdead.beef");
+    /// ```
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
-        write!(f, "0x")?;
-        if self.bytes.is_empty() {
-            write!(f, "0")
-        } else {
-            for (i, byte) in (&self.bytes).iter().enumerate() {
-                if i > 0 && i % 4 == 0 {
-                    write!(f, ".")?;
-                }
-                write!(f, "{:x}", byte)?;
-            }
-            Ok(())
-        }
+        f.write_str(&Aura::Ux.render(self))
     }
 }
 
@@ -335,6 +429,19 @@ impl From<Vec<u8>> for Atom {
     }
 }
 
+impl Ord for Atom {
+    /// Compares atoms numerically.
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_bytes(self.as_bytes(), other.as_bytes())
+    }
+}
+
+impl PartialOrd for Atom {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl PartialEq for Atom {
     fn eq(&self, other: &Self) -> bool {
         self.bytes == other.bytes
@@ -389,6 +496,800 @@ impl_partial_eq_uint_for_atom!(u64, as_u64);
 impl_partial_eq_uint_for_atom!(u128, as_u128);
 impl_partial_eq_uint_for_atom!(usize, as_usize);
 
+impl Atom {
+    /// Subtracts `rhs` from this atom, returning `None` if `rhs` is greater than this atom.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(10u8).checked_sub(&Atom::from(3u8)), Some(Atom::from(7u8)));
+    /// assert_eq!(Atom::from(3u8).checked_sub(&Atom::from(10u8)), None);
+    /// ```
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        sub_bytes(self.as_bytes(), rhs.as_bytes()).map(Self::from)
+    }
+
+    /// Divides this atom by `rhs`, returning the quotient and the remainder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is `0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// let (quot, rem) = Atom::from(1_000u16).div_rem(&Atom::from(7u8));
+    /// assert_eq!(quot, 142u8);
+    /// assert_eq!(rem, 6u8);
+    /// ```
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let (quot, rem) = div_rem_bytes(self.as_bytes(), rhs.as_bytes());
+        (Self::from(quot), Self::from(rem))
+    }
+
+    /// Raises this atom to the power of `exp`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(2u8).pow(100), Atom::from(1u128 << 100));
+    /// assert_eq!(Atom::from(0u8).pow(0), 1u8);
+    /// ```
+    pub fn pow(&self, mut exp: u32) -> Self {
+        let mut base = self.clone();
+        let mut acc = Self::from(1u8);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = &acc * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        acc
+    }
+}
+
+/// Hoon's bit-block primitives ([stdlib 2c]).
+///
+/// Each of these methods operates on blocks ("bloqs") of `2^bloq` bits, where `bloq` is the
+/// power-of-two exponent that Hoon calls a bloq size: bloq `0` is a bit, bloq `3` is a byte, and
+/// bloq `5` is a 32-bit word. Counts of bloqs ("steps") are given as `usize`.
+///
+/// Bloq sizes of `3` and above, and any other combination of bloq size and step that lands on a
+/// byte boundary, operate directly on the underlying bytes.
+///
+/// [stdlib 2c]: https://developers.urbit.org/reference/hoon/stdlib/2c
+impl Atom {
+    /// Returns the number of `bloq`-sized blocks needed to represent this atom (`++met`).
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(256u16).met(0), 9);
+    /// assert_eq!(Atom::from(256u16).met(3), 2);
+    /// assert_eq!(Atom::from(256u16).met(4), 1);
+    /// assert_eq!(Atom::null().met(3), 0);
+    /// ```
+    pub fn met(&self, bloq: u32) -> usize {
+        self.bit_len().div_ceil(bloq_bits(bloq, 1))
+    }
+
+    /// Returns the low `step` `bloq`-sized blocks of this atom (`++end`).
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(0b1_1011u8).end(0, 3), 0b011u8);
+    /// assert_eq!(Atom::from(0x1234u16).end(3, 1), 0x34u8);
+    /// ```
+    pub fn end(&self, bloq: u32, step: usize) -> Self {
+        Self::from(end_bytes(self.as_bytes(), bloq_bits(bloq, step)))
+    }
+
+    /// Shifts this atom right by `step` `bloq`-sized blocks (`++rsh`).
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(40u8).rsh(0, 2), 10u8);
+    /// assert_eq!(Atom::from(0x1234u16).rsh(3, 1), 0x12u8);
+    /// ```
+    pub fn rsh(&self, bloq: u32, step: usize) -> Self {
+        Self::from(shr_bytes(self.as_bytes(), bloq_bits(bloq, step)))
+    }
+
+    /// Shifts this atom left by `step` `bloq`-sized blocks (`++lsh`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shift is too large to represent in bits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(1u8).lsh(0, 2), 4u8);
+    /// assert_eq!(Atom::from(0x12u8).lsh(3, 1), 0x1200u16);
+    /// ```
+    pub fn lsh(&self, bloq: u32, step: usize) -> Self {
+        if self.is_null() {
+            return Self::null();
+        }
+        let shift = bloq_bits(bloq, step);
+        assert!(shift != usize::MAX, "attempt to shift left with overflow");
+        Self::from(shl_bytes(self.as_bytes(), shift))
+    }
+
+    /// Returns the `step` `bloq`-sized blocks of this atom that begin `offset` blocks from the
+    /// least significant end (`++cut`).
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(0x12_3456u32).cut(3, 1, 1), 0x34u8);
+    /// assert_eq!(Atom::from(0b1011u8).cut(0, 1, 2), 0b01u8);
+    /// ```
+    pub fn cut(&self, bloq: u32, offset: usize, step: usize) -> Self {
+        let start = bloq_bits(bloq, offset);
+        if start >= self.bit_len() {
+            return Self::null();
+        }
+        let bytes = self.as_bytes();
+        let bits = bloq_bits(bloq, step);
+        if start.is_multiple_of(8) {
+            Self::from(end_bytes(&bytes[start / 8..], bits))
+        } else {
+            Self::from(end_bytes(&shr_bytes(bytes, start), bits))
+        }
+    }
+
+    /// Concatenates this atom with `high`, placing `high` immediately above the `bloq`-sized
+    /// blocks of this atom (`++cat`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `high` isn't `0` and the position of `high` is too large to represent in bits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(1u8).cat(0, &Atom::from(1u8)), 0b11u8);
+    /// assert_eq!(Atom::from(0x100u16).cat(3, &Atom::from(0xffu8)), 0xff_0100u32);
+    /// ```
+    pub fn cat(&self, bloq: u32, high: &Self) -> Self {
+        assemble(bloq, [(self.met(bloq), self), (high.met(bloq), high)])
+    }
+
+    /// Assembles an atom from a sequence of `(step, atom)` pairs, taking the low `step`
+    /// `bloq`-sized blocks of each atom and laying them out from least significant to most
+    /// significant (`++can`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if a segment that isn't `0` starts at a position too large to represent in bits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// let atom = Atom::can(3, &[(1, Atom::from(1u8)), (1, Atom::from(2u8))]);
+    /// assert_eq!(atom, 0x201u16);
+    /// let atom = Atom::can(0, &[(2, Atom::from(0b111u8)), (3, Atom::from(0b101u8))]);
+    /// assert_eq!(atom, 0b10111u8);
+    /// ```
+    pub fn can(bloq: u32, segments: &[(usize, Self)]) -> Self {
+        assemble(bloq, segments.iter().map(|(step, atom)| (*step, atom)))
+    }
+
+    /// Assembles an atom from a sequence of atoms, taking the low `bloq`-sized block of each and
+    /// laying them out from least significant to most significant (`++rep`).
+    ///
+    /// This is the inverse of [`rip()`](Self::rip()).
+    ///
+    /// # Panics
+    ///
+    /// Panics if an atom that isn't `0` starts at a position too large to represent in bits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// let atom = Atom::rep(3, &[Atom::from(1u8), Atom::from(2u8), Atom::from(3u8)]);
+    /// assert_eq!(atom, 0x3_0201u32);
+    /// ```
+    pub fn rep(bloq: u32, atoms: &[Self]) -> Self {
+        assemble(bloq, atoms.iter().map(|atom| (1, atom)))
+    }
+
+    /// Splits this atom into `bloq`-sized blocks, from least significant to most significant
+    /// (`++rip`).
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(12_345u16).rip(3), vec![Atom::from(57u8), Atom::from(48u8)]);
+    /// assert_eq!(Atom::from(0b110u8).rip(0), vec![Atom::null(), Atom::from(1u8), Atom::from(1u8)]);
+    /// assert!(Atom::null().rip(3).is_empty());
+    /// ```
+    pub fn rip(&self, bloq: u32) -> Vec<Self> {
+        let bytes = self.as_bytes();
+        if bloq >= 3 {
+            let bloq_bytes = bloq_bits(bloq, 1) / 8;
+            return bytes
+                .chunks(bloq_bytes)
+                .map(|chunk| Self::from(chunk.to_vec()))
+                .collect();
+        }
+        let bloq_bits = bloq_bits(bloq, 1);
+        let mask = (1u16 << bloq_bits) as u8 - 1;
+        (0..self.met(bloq))
+            .map(|i| {
+                let bit = i * bloq_bits;
+                Self::from(vec![(bytes[bit / 8] >> (bit % 8)) & mask])
+            })
+            .collect()
+    }
+
+    /// Reverses the order of the `bloq`-sized blocks of this atom (`++swp`).
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from("abc").swp(3), "cba");
+    /// assert_eq!(Atom::from(0b1101u8).swp(0), 0b1011u8);
+    /// ```
+    pub fn swp(&self, bloq: u32) -> Self {
+        if bloq == 3 {
+            let mut bytes = self.to_vec();
+            bytes.reverse();
+            return Self::from(bytes);
+        }
+        let mut blocks = self.rip(bloq);
+        blocks.reverse();
+        Self::rep(bloq, &blocks)
+    }
+
+    /// Computes the bitwise exclusive or of this atom and `other` (`++mix`).
+    ///
+    /// This method is equivalent to `self ^ other`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(3u8).mix(&Atom::from(5u8)), 6u8);
+    /// ```
+    pub fn mix(&self, other: &Self) -> Self {
+        self ^ other
+    }
+}
+
+/// Conversions to and from Urbit's phonetic base. See [`ob`] for details.
+impl Atom {
+    /// Spells this atom as a ship name (`@p`).
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::null().to_patp(), "~zod");
+    /// assert_eq!(Atom::from(256u16).to_patp(), "~marzod");
+    /// assert_eq!(Atom::from(1_624_961_343u32).to_patp(), "~sampel-palnet");
+    /// ```
+    pub fn to_patp(&self) -> String {
+        ob::patp(self)
+    }
+
+    /// Parses a ship name (`@p`), which must be in canonical form.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::{atom::Atom, ob};
+    /// assert_eq!(Atom::from_patp("~sampel-palnet"), Ok(Atom::from(1_624_961_343u32)));
+    /// assert_eq!(Atom::from_patp("sampel-palnet"), Err(ob::Error::MissingSig));
+    /// ```
+    pub fn from_patp(name: &str) -> ob::Result<Self> {
+        ob::parse_patp(name)
+    }
+
+    /// Spells this atom in the unscrambled phonetic base (`@q`).
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(1u8).to_patq(), "~nec");
+    /// assert_eq!(Atom::from(0x1_0000u32).to_patq(), "~doznec-dozzod");
+    /// ```
+    pub fn to_patq(&self) -> String {
+        ob::patq(self)
+    }
+
+    /// Parses an atom spelled in the unscrambled phonetic base (`@q`), which must be in canonical
+    /// form.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::{atom::Atom, ob};
+    /// assert_eq!(Atom::from_patq("~marzod"), Ok(Atom::from(256u16)));
+    /// assert_eq!(Atom::from_patq("~doznec"), Err(ob::Error::NonCanonical));
+    /// ```
+    pub fn from_patq(name: &str) -> ob::Result<Self> {
+        ob::parse_patq(name)
+    }
+}
+
+/// Conversions to and from Urbit's absolute dates and relative durations. See [`time`] for
+/// details.
+impl Atom {
+    /// Spells this atom as an absolute date (`@da`), returning `None` if the atom is wider than 128
+    /// bits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// let date = Atom::from(0x8000_000c_ce9e_0d80_0000_0000_0000_0000u128);
+    /// assert_eq!(date.to_da().unwrap(), "~1970.1.1");
+    /// ```
+    pub fn to_da(&self) -> Option<String> {
+        self.as_u128().map(time::da)
+    }
+
+    /// Parses an absolute date (`@da`).
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::{atom::Atom, time};
+    /// let date = Atom::from_da("~2022.7.8..16.43.50..1a2b").unwrap();
+    /// assert_eq!(date.to_da().unwrap(), "~2022.7.8..16.43.50..1a2b");
+    /// assert_eq!(Atom::from_da("~2022.2.29"), Err(time::Error::OutOfRange { pos: 8 }));
+    /// ```
+    pub fn from_da(text: &str) -> time::Result<Self> {
+        time::parse_da(text).map(Self::from)
+    }
+
+    /// Spells this atom as a relative duration (`@dr`), returning `None` if the atom is wider than
+    /// 128 bits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from(90u128 << 64).to_dr().unwrap(), "~m1.s30");
+    /// ```
+    pub fn to_dr(&self) -> Option<String> {
+        self.as_u128().map(time::dr)
+    }
+
+    /// Parses a relative duration (`@dr`).
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// assert_eq!(Atom::from_dr("~m1.s30..8000"), Ok(Atom::from(90u128 << 64 | 1 << 63)));
+    /// ```
+    pub fn from_dr(text: &str) -> time::Result<Self> {
+        time::parse_dr(text).map(Self::from)
+    }
+
+    /// Converts a system time into an absolute date (`@da`), returning `None` if the system time
+    /// precedes the Urbit epoch.
+    ///
+    /// Fractions of a second are rounded up to the next 2^-64 second, so that
+    /// [`to_system_time()`](Self::to_system_time()) recovers the system time exactly.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// # use std::time::{Duration, UNIX_EPOCH};
+    /// let time = UNIX_EPOCH + Duration::new(1_657_298_630, 500_000_000);
+    /// let date = Atom::from_system_time(time).unwrap();
+    /// assert_eq!(date.to_da().unwrap(), "~2022.7.8..16.43.50..8000");
+    /// assert_eq!(date.to_system_time(), Some(time));
+    /// ```
+    pub fn from_system_time(time: SystemTime) -> Option<Self> {
+        time::from_system_time(time).map(Self::from)
+    }
+
+    /// Converts this atom as an absolute date (`@da`) into a system time, rounding down to the
+    /// nanosecond and returning `None` if the atom is wider than 128 bits or the date can't be
+    /// represented as a system time.
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        self.as_u128().and_then(time::to_system_time)
+    }
+
+    /// Converts a duration into a relative duration (`@dr`).
+    ///
+    /// Fractions of a second are rounded up to the next 2^-64 second, so that
+    /// [`to_duration()`](Self::to_duration()) recovers the duration exactly.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// # use std::time::Duration;
+    /// let duration = Atom::from_duration(Duration::from_millis(1500));
+    /// assert_eq!(duration.to_dr().unwrap(), "~s1..8000");
+    /// assert_eq!(duration.to_duration(), Some(Duration::from_millis(1500)));
+    /// ```
+    pub fn from_duration(duration: Duration) -> Self {
+        Self::from(time::from_duration(duration))
+    }
+
+    /// Converts this atom as a relative duration (`@dr`) into a duration, rounding down to the
+    /// nanosecond and returning `None` if the atom is wider than 128 bits.
+    pub fn to_duration(&self) -> Option<Duration> {
+        self.as_u128().map(time::to_duration)
+    }
+}
+
+/// Returns the number of bits in `step` blocks of `2^bloq` bits, saturating at `usize::MAX`.
+fn bloq_bits(bloq: u32, step: usize) -> usize {
+    checked_bloq_bits(bloq, step).unwrap_or(usize::MAX)
+}
+
+/// Returns the number of bits in `step` blocks of `2^bloq` bits, or `None` if the number doesn't
+/// fit in a `usize`.
+fn checked_bloq_bits(bloq: u32, step: usize) -> Option<usize> {
+    if step == 0 {
+        return Some(0);
+    }
+    if bloq >= usize::BITS {
+        return None;
+    }
+    (1usize << bloq).checked_mul(step)
+}
+
+/// Lays out the low `step` `bloq`-sized blocks of each atom in `segments` from least significant
+/// to most significant, panicking if a segment that isn't `0` starts at a position that doesn't fit
+/// in a `usize`.
+fn assemble<'a>(bloq: u32, segments: impl IntoIterator<Item = (usize, &'a Atom)>) -> Atom {
+    let mut bytes = Vec::new();
+    let mut pos = Some(0);
+    for (step, atom) in segments {
+        let bits = checked_bloq_bits(bloq, step);
+        if bits != Some(0) && !atom.is_null() {
+            let start = pos.expect("attempt to concatenate with overflow");
+            // A segment too wide to count in bits is wider than any atom, so the whole atom fits.
+            splice(
+                &mut bytes,
+                start,
+                atom.as_bytes(),
+                bits.unwrap_or(usize::MAX),
+            );
+        }
+        pos = pos.zip(bits).and_then(|(pos, bits)| pos.checked_add(bits));
+    }
+    Atom::from(bytes)
+}
+
+/// Returns the low `bits` bits of a little-endian byte sequence.
+fn end_bytes(bytes: &[u8], bits: usize) -> Vec<u8> {
+    let byte_len = bits / 8;
+    if byte_len >= bytes.len() {
+        return bytes.to_vec();
+    }
+    let mut end = bytes[..byte_len].to_vec();
+    if !bits.is_multiple_of(8) {
+        end.push(bytes[byte_len] & ((1 << (bits % 8)) - 1));
+    }
+    end
+}
+
+/// Writes the low `bits` bits of the little-endian byte sequence `src` into `dst` starting at bit
+/// `pos`, growing `dst` as needed.
+///
+/// The bits of `dst` at and above `pos` must be zero.
+fn splice(dst: &mut Vec<u8>, pos: usize, src: &[u8], bits: usize) {
+    let src = if bits >= src.len().saturating_mul(8) {
+        Cow::Borrowed(src)
+    } else {
+        Cow::Owned(end_bytes(src, bits))
+    };
+    if src.is_empty() {
+        return;
+    }
+    let byte_pos = pos / 8;
+    let bit_shift = pos % 8;
+    let end = byte_pos + src.len() + usize::from(bit_shift != 0);
+    if dst.len() < end {
+        dst.resize(end, 0);
+    }
+    if bit_shift == 0 {
+        dst[byte_pos..byte_pos + src.len()].copy_from_slice(&src);
+    } else {
+        for (i, byte) in src.iter().enumerate() {
+            dst[byte_pos + i] |= byte << bit_shift;
+            dst[byte_pos + i + 1] |= byte >> (8 - bit_shift);
+        }
+    }
+}
+
+/// Returns the `n` bits of the little-endian byte sequence `bytes` starting at bit `start` as an
+/// atom. `bytes` must have at least `start + n` bits.
+pub(crate) fn bits_to_atom(bytes: &[u8], start: usize, n: usize) -> Atom {
+    let bytes = &bytes[start / 8..(start + n).div_ceil(8)];
+    if start.is_multiple_of(8) {
+        Atom::from(end_bytes(bytes, n))
+    } else {
+        Atom::from(end_bytes(&shr_bytes(bytes, start % 8), n))
+    }
+}
+
+/// Compares two little-endian byte sequences without trailing zeros by numeric value.
+fn cmp_bytes(lh: &[u8], rh: &[u8]) -> Ordering {
+    lh.len()
+        .cmp(&rh.len())
+        .then_with(|| lh.iter().rev().cmp(rh.iter().rev()))
+}
+
+/// Adds two little-endian byte sequences.
+fn add_bytes(lh: &[u8], rh: &[u8]) -> Vec<u8> {
+    let (long, short) = if lh.len() >= rh.len() {
+        (lh, rh)
+    } else {
+        (rh, lh)
+    };
+    let mut sum = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u16;
+    for (i, byte) in long.iter().enumerate() {
+        let digit = u16::from(*byte) + u16::from(short.get(i).copied().unwrap_or(0)) + carry;
+        sum.push(digit as u8);
+        carry = digit >> u8::BITS;
+    }
+    if carry != 0 {
+        sum.push(carry as u8);
+    }
+    sum
+}
+
+/// Subtracts one little-endian byte sequence from another, returning `None` if the difference
+/// would be negative.
+fn sub_bytes(lh: &[u8], rh: &[u8]) -> Option<Vec<u8>> {
+    if cmp_bytes(lh, rh) == Ordering::Less {
+        return None;
+    }
+    let mut diff = Vec::with_capacity(lh.len());
+    let mut borrow = 0i16;
+    for (i, byte) in lh.iter().enumerate() {
+        let mut digit = i16::from(*byte) - i16::from(rh.get(i).copied().unwrap_or(0)) - borrow;
+        borrow = 0;
+        if digit < 0 {
+            digit += 1 << u8::BITS;
+            borrow = 1;
+        }
+        diff.push(digit as u8);
+    }
+    debug_assert_eq!(borrow, 0);
+    Some(diff)
+}
+
+/// Multiplies two little-endian byte sequences.
+fn mul_bytes(lh: &[u8], rh: &[u8]) -> Vec<u8> {
+    if lh.is_empty() || rh.is_empty() {
+        return Vec::new();
+    }
+    let mut product = vec![0u8; lh.len() + rh.len()];
+    for (i, lh_byte) in lh.iter().enumerate() {
+        if *lh_byte == 0 {
+            continue;
+        }
+        let mut carry = 0u32;
+        for (j, rh_byte) in rh.iter().enumerate() {
+            let digit =
+                u32::from(product[i + j]) + u32::from(*lh_byte) * u32::from(*rh_byte) + carry;
+            product[i + j] = digit as u8;
+            carry = digit >> u8::BITS;
+        }
+        product[i + rh.len()] = carry as u8;
+    }
+    product
+}
+
+/// Shifts a little-endian byte sequence left by `shift` bits.
+fn shl_bytes(bytes: &[u8], shift: usize) -> Vec<u8> {
+    if bytes.is_empty() {
+        return Vec::new();
+    }
+    let u8_bits = u8::BITS as usize;
+    let (byte_shift, bit_shift) = (shift / u8_bits, shift % u8_bits);
+    let mut shifted = vec![0u8; byte_shift];
+    shifted.reserve(bytes.len() + 1);
+    if bit_shift == 0 {
+        shifted.extend_from_slice(bytes);
+    } else {
+        let mut carry = 0u8;
+        for byte in bytes {
+            shifted.push((byte << bit_shift) | carry);
+            carry = byte >> (u8_bits - bit_shift);
+        }
+        shifted.push(carry);
+    }
+    shifted
+}
+
+/// Shifts a little-endian byte sequence right by `shift` bits.
+fn shr_bytes(bytes: &[u8], shift: usize) -> Vec<u8> {
+    let u8_bits = u8::BITS as usize;
+    let (byte_shift, bit_shift) = (shift / u8_bits, shift % u8_bits);
+    if byte_shift >= bytes.len() {
+        return Vec::new();
+    }
+    let bytes = &bytes[byte_shift..];
+    if bit_shift == 0 {
+        return bytes.to_vec();
+    }
+    (0..bytes.len())
+        .map(|i| {
+            let high = bytes.get(i + 1).copied().unwrap_or(0);
+            (bytes[i] >> bit_shift) | (high << (u8_bits - bit_shift))
+        })
+        .collect()
+}
+
+/// Divides one little-endian byte sequence without trailing zeros by another, returning the
+/// quotient and the remainder.
+///
+/// This is Knuth's Algorithm D (The Art of Computer Programming, Vol. 2, Section 4.3.1) with base
+/// 256 digits.
+fn div_rem_bytes(lh: &[u8], rh: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    assert!(!rh.is_empty(), "attempt to divide by zero");
+    if cmp_bytes(lh, rh) == Ordering::Less {
+        return (Vec::new(), lh.to_vec());
+    }
+
+    const BASE: i64 = 1 << u8::BITS;
+
+    // Short division by a single digit.
+    if rh.len() == 1 {
+        let divisor = u16::from(rh[0]);
+        let mut quot = vec![0u8; lh.len()];
+        let mut rem = 0u16;
+        for (i, byte) in lh.iter().enumerate().rev() {
+            let digit = (rem << u8::BITS) | u16::from(*byte);
+            quot[i] = (digit / divisor) as u8;
+            rem = digit % divisor;
+        }
+        return (quot, vec![rem as u8]);
+    }
+
+    // Normalize so that the most significant digit of the divisor has its high bit set, which
+    // guarantees that each estimated quotient digit is off by at most two.
+    let shift = rh.last().expect("nonempty divisor").leading_zeros() as usize;
+    let divisor = {
+        let mut divisor = shl_bytes(rh, shift);
+        divisor.truncate(rh.len());
+        divisor
+    };
+    let mut rem = shl_bytes(lh, shift);
+    rem.resize(lh.len() + 1, 0);
+
+    let n = divisor.len();
+    let m = lh.len() - n;
+    let top = i64::from(divisor[n - 1]);
+    let next = i64::from(divisor[n - 2]);
+    let mut quot = vec![0u8; m + 1];
+    for j in (0..=m).rev() {
+        // Estimate the quotient digit from the top two digits of the remainder.
+        let num = i64::from(rem[j + n]) * BASE + i64::from(rem[j + n - 1]);
+        let mut qhat = num / top;
+        let mut rhat = num % top;
+        while qhat >= BASE || qhat * next > rhat * BASE + i64::from(rem[j + n - 2]) {
+            qhat -= 1;
+            rhat += top;
+            if rhat >= BASE {
+                break;
+            }
+        }
+
+        // Multiply and subtract.
+        let mut borrow = 0i64;
+        for i in 0..n {
+            let product = qhat * i64::from(divisor[i]);
+            let digit = i64::from(rem[i + j]) - borrow - (product & 0xff);
+            rem[i + j] = digit as u8;
+            borrow = (product >> u8::BITS) - (digit >> u8::BITS);
+        }
+        let digit = i64::from(rem[j + n]) - borrow;
+        rem[j + n] = digit as u8;
+
+        // The estimate was one too large, so add the divisor back.
+        if digit < 0 {
+            qhat -= 1;
+            let mut carry = 0u16;
+            for i in 0..n {
+                let digit = u16::from(rem[i + j]) + u16::from(divisor[i]) + carry;
+                rem[i + j] = digit as u8;
+                carry = digit >> u8::BITS;
+            }
+            rem[j + n] = rem[j + n].wrapping_add(carry as u8);
+        }
+        quot[j] = qhat as u8;
+    }
+
+    rem.truncate(n);
+    (quot, shr_bytes(&rem, shift))
+}
+
+/// Implements a binary operator for every combination of owned and borrowed atoms in terms of a
+/// function of two little-endian byte sequences.
+macro_rules! impl_binary_op_for_atom {
+    ($trait:ident, $method:ident, $op:expr) => {
+        impl $trait<&Atom> for &Atom {
+            type Output = Atom;
+
+            fn $method(self, rhs: &Atom) -> Self::Output {
+                Atom::from($op(self.as_bytes(), rhs.as_bytes()))
+            }
+        }
+
+        impl $trait<Atom> for &Atom {
+            type Output = Atom;
+
+            fn $method(self, rhs: Atom) -> Self::Output {
+                self.$method(&rhs)
+            }
+        }
+
+        impl $trait<&Atom> for Atom {
+            type Output = Atom;
+
+            fn $method(self, rhs: &Atom) -> Self::Output {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl $trait<Atom> for Atom {
+            type Output = Atom;
+
+            fn $method(self, rhs: Atom) -> Self::Output {
+                (&self).$method(&rhs)
+            }
+        }
+    };
+}
+
+impl_binary_op_for_atom!(Add, add, add_bytes);
+impl_binary_op_for_atom!(Sub, sub, |lh, rh| {
+    sub_bytes(lh, rh).expect("attempt to subtract with overflow")
+});
+impl_binary_op_for_atom!(Mul, mul, mul_bytes);
+impl_binary_op_for_atom!(Div, div, |lh, rh| div_rem_bytes(lh, rh).0);
+impl_binary_op_for_atom!(Rem, rem, |lh, rh| div_rem_bytes(lh, rh).1);
+impl_binary_op_for_atom!(BitAnd, bitand, |lh, rh| zip_bytes(lh, rh, |lh, rh| lh & rh));
+impl_binary_op_for_atom!(BitOr, bitor, |lh, rh| zip_bytes(lh, rh, |lh, rh| lh | rh));
+impl_binary_op_for_atom!(BitXor, bitxor, |lh, rh| zip_bytes(lh, rh, |lh, rh| lh ^ rh));
+
+/// Combines two little-endian byte sequences byte by byte, padding the shorter one with zeros.
+fn zip_bytes(lh: &[u8], rh: &[u8], op: impl Fn(u8, u8) -> u8) -> Vec<u8> {
+    (0..lh.len().max(rh.len()))
+        .map(|i| {
+            op(
+                lh.get(i).copied().unwrap_or(0),
+                rh.get(i).copied().unwrap_or(0),
+            )
+        })
+        .collect()
+}
+
+/// Implements a bitwise shift operator for owned and borrowed atoms.
+macro_rules! impl_shift_op_for_atom {
+    ($trait:ident, $method:ident, $op:ident) => {
+        impl $trait<usize> for &Atom {
+            type Output = Atom;
+
+            fn $method(self, rhs: usize) -> Self::Output {
+                Atom::from($op(self.as_bytes(), rhs))
+            }
+        }
+
+        impl $trait<usize> for Atom {
+            type Output = Atom;
+
+            fn $method(self, rhs: usize) -> Self::Output {
+                (&self).$method(rhs)
+            }
+        }
+    };
+}
+
+impl_shift_op_for_atom!(Shl, shl, shl_bytes);
+impl_shift_op_for_atom!(Shr, shr, shr_bytes);
+
 /// An iterator over the bits of an [`Atom`].
 ///
 /// Iteration starts with the least significant bit of the [`Atom`] and ends with the most
@@ -402,11 +1303,89 @@ pub struct Iter<'a> {
     bit_mask: u8,
 }
 
-impl<'a> Iter<'_> {
+impl Iter<'_> {
     /// Returns the current bitwise position of this iterator.
     pub fn pos(&self) -> usize {
         self.bit_idx
     }
+
+    /// Returns the number of bits remaining in this iterator.
+    pub fn remaining(&self) -> usize {
+        self.atom.bit_len - self.bit_idx
+    }
+
+    /// Advances this iterator by `n` bits, returning the bits as the low `n` bits of a `u64`, or
+    /// `None` (without advancing) if fewer than `n` bits remain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than 64.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// let atom = Atom::from(0b10_1101u8);
+    /// let mut iter = atom.iter();
+    /// assert_eq!(iter.next(), Some(true));
+    /// assert_eq!(iter.next_bits(4), Some(0b0110));
+    /// assert_eq!(iter.next_bits(2), None);
+    /// assert_eq!(iter.next_bits(1), Some(1));
+    /// ```
+    pub fn next_bits(&mut self, n: usize) -> Option<u64> {
+        assert!(n <= 64, "cannot read more than 64 bits at a time");
+        if n > self.remaining() {
+            return None;
+        }
+        if n == 0 {
+            return Some(0);
+        }
+        // A 64-bit read at an unaligned position spans at most nine bytes.
+        let byte_idx = self.bit_idx / 8;
+        let mut word = 0u128;
+        for (i, byte) in self.atom.bytes[byte_idx..].iter().take(9).enumerate() {
+            word |= u128::from(*byte) << (8 * i);
+        }
+        let bits = (word >> (self.bit_idx % 8)) as u64;
+        self.advance(n);
+        Some(if n == 64 { bits } else { bits & ((1 << n) - 1) })
+    }
+
+    /// Advances this iterator by `n` bits, returning the bits as a new atom, or `None` (without
+    /// advancing) if fewer than `n` bits remain.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::atom::Atom;
+    /// let atom = Atom::from(0xabcdu16);
+    /// let mut iter = atom.iter();
+    /// assert_eq!(iter.next_bits(4), Some(0xd));
+    /// assert_eq!(iter.next_atom(8), Some(Atom::from(0xbcu8)));
+    /// assert_eq!(iter.next_atom(8), None);
+    /// ```
+    pub fn next_atom(&mut self, n: usize) -> Option<Atom> {
+        if n > self.remaining() {
+            return None;
+        }
+        let atom = bits_to_atom(&self.atom.bytes, self.bit_idx, n);
+        self.advance(n);
+        Some(atom)
+    }
+
+    /// Advances this iterator by `n` bits without reading them, returning `None` (without
+    /// advancing) if fewer than `n` bits remain.
+    pub fn skip_bits(&mut self, n: usize) -> Option<()> {
+        if n > self.remaining() {
+            return None;
+        }
+        self.advance(n);
+        Some(())
+    }
+
+    /// Advances this iterator by `n` bits, which must not exceed the number of remaining bits.
+    fn advance(&mut self, n: usize) {
+        self.bit_idx += n;
+        self.bit_mask = 1 << (self.bit_idx % 8);
+    }
 }
 
 impl Iterator for Iter<'_> {
@@ -454,6 +1433,182 @@ mod tests {
         }
     }
 
+    #[test]
+    fn arithmetic() {
+        // Cross-check against `u128` arithmetic on operands whose results fit in 128 bits.
+        {
+            let mut state = 0x2545_f491_4f6c_dd1du64;
+            let mut next = || {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                // Vary the width of the operands so that every byte length is exercised.
+                state >> (state % 64)
+            };
+            for _ in 0..1_000 {
+                let (lh, rh) = (u128::from(next()), u128::from(next()));
+                let (lh_atom, rh_atom) = (Atom::from(lh), Atom::from(rh));
+                assert_eq!(&lh_atom + &rh_atom, lh + rh);
+                assert_eq!(&lh_atom * &rh_atom, lh * rh);
+                assert_eq!(&lh_atom & &rh_atom, lh & rh);
+                assert_eq!(&lh_atom | &rh_atom, lh | rh);
+                assert_eq!(&lh_atom ^ &rh_atom, lh ^ rh);
+                assert_eq!(&lh_atom << 17, lh << 17);
+                assert_eq!(&lh_atom >> 9, lh >> 9);
+                match lh.checked_sub(rh) {
+                    Some(diff) => assert_eq!(lh_atom.checked_sub(&rh_atom).unwrap(), diff),
+                    None => assert_eq!(lh_atom.checked_sub(&rh_atom), None),
+                }
+                if rh != 0 {
+                    let product = lh * rh + rh / 2;
+                    let (quot, rem) = Atom::from(product).div_rem(&rh_atom);
+                    assert_eq!(quot, product / rh);
+                    assert_eq!(rem, product % rh);
+                }
+            }
+        }
+
+        // Results wider than any primitive type.
+        {
+            let big = Atom::from(3u8).pow(200);
+            let divisor = Atom::from(7u8).pow(40) + Atom::from(12_345u16);
+            let (quot, rem) = big.div_rem(&divisor);
+            assert!(rem.checked_sub(&divisor).is_none());
+            assert_eq!(&quot * &divisor + &rem, big);
+            assert_eq!(&big / &divisor, quot);
+            assert_eq!(&big % &divisor, rem);
+            assert_eq!(&big - &big, 0u8);
+            assert_eq!((&big << 1000) >> 1000, big);
+            assert_eq!((Atom::from(1u8) << 200).bit_len(), 201);
+            assert_eq!((&big ^ &big).bit_len(), 0);
+            assert_eq!(
+                Atom::from(u128::MAX) + Atom::from(1u8),
+                Atom::from(1u8) << 128
+            );
+        }
+
+        // Edge cases.
+        {
+            assert_eq!(Atom::null() + Atom::null(), 0u8);
+            assert_eq!(Atom::null() * Atom::from(u64::MAX), 0u8);
+            assert_eq!(Atom::from(5u8) / Atom::from(10u8), 0u8);
+            assert_eq!(Atom::from(5u8) % Atom::from(10u8), 5u8);
+            assert_eq!(Atom::from(0x100u16) >> 9, 0u8);
+            assert_eq!(Atom::null() << 10, 0u8);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn sub_overflow() {
+        let _ = Atom::from(1u8) - Atom::from(2u8);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to divide by zero")]
+    fn div_by_zero() {
+        let _ = Atom::from(1u8) / Atom::null();
+    }
+
+    #[test]
+    fn bloq() {
+        let atom = Atom::from(0x0123_4567_89ab_cdef_0011_2233_4455_6677u128);
+
+        // Splitting and reassembling round-trips at every bloq size.
+        for bloq in 0..8 {
+            let blocks = atom.rip(bloq);
+            assert_eq!(blocks.len(), atom.met(bloq));
+            assert_eq!(Atom::rep(bloq, &blocks), atom);
+            assert_eq!(atom.swp(bloq).swp(bloq), atom);
+        }
+
+        // Byte-aligned and unaligned cuts agree with shifting and masking.
+        for (bloq, offset, step) in [(3, 2, 4), (0, 3, 17), (1, 5, 9), (2, 7, 3), (4, 1, 2)] {
+            let bits = (1 << bloq) * step;
+            let mask = (Atom::from(1u8) << bits) - Atom::from(1u8);
+            let expected = (&atom >> ((1 << bloq) * offset)) & mask;
+            assert_eq!(atom.cut(bloq, offset, step), expected);
+            assert_eq!(atom.rsh(bloq, offset).end(bloq, step), expected);
+        }
+
+        // Unaligned concatenation.
+        {
+            let atom = Atom::can(
+                0,
+                &[
+                    (3, Atom::from(0b101u8)),
+                    (9, Atom::from(0x1ffu16)),
+                    (1, Atom::null()),
+                    (4, Atom::from(0xfu8)),
+                ],
+            );
+            // 0b1111 ++ 0b0 ++ 0b1_1111_1111 ++ 0b101
+            assert_eq!(atom, 0b1_1110_1111_1111_1101u32);
+            assert_eq!(Atom::from(0b101u8).cat(0, &Atom::from(0b11u8)), 0b1_1101u8);
+            assert_eq!(Atom::from(0x1u8).cat(2, &Atom::from(0x1u8)), 0x11u8);
+        }
+
+        // Out-of-range shifts and cuts.
+        {
+            assert_eq!(atom.rsh(7, 1), 0u8);
+            assert_eq!(atom.cut(3, 16, 1), 0u8);
+            assert_eq!(atom.end(3, 100), atom);
+            assert_eq!(atom.end(100, 1), atom);
+            assert_eq!(atom.rsh(100, 1), 0u8);
+            assert_eq!(Atom::null().lsh(100, 1), 0u8);
+        }
+
+        // Bloq sizes too large to count in bits.
+        {
+            let one = Atom::from(1u8);
+            assert_eq!(atom.met(64), 1);
+            assert_eq!(atom.cat(64, &Atom::null()), atom);
+            assert_eq!(Atom::null().cat(200, &atom), atom);
+            assert_eq!(Atom::can(200, &[(1, one.clone()), (3, Atom::null())]), one);
+            assert_eq!(Atom::rep(u32::MAX, &[one.clone(), Atom::null()]), one);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to concatenate with overflow")]
+    fn cat_overflow() {
+        let _ = Atom::from(1u8).cat(64, &Atom::from(1u8));
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to concatenate with overflow")]
+    fn can_overflow() {
+        let _ = Atom::can(1, &[(usize::MAX, Atom::from(1u8)), (1, Atom::from(1u8))]);
+    }
+
+    #[test]
+    fn bulk_bits() {
+        // Write fields of every width at every alignment, then read them back.
+        let atom = Atom::from(vec![
+            0xde, 0xad, 0xbe, 0xef, 0x01, 0x23, 0x45, 0x67, 0x89, 0xab,
+        ]);
+        let mut builder = Atom::builder();
+        for n in 0..=64 {
+            builder.push_bits(u64::MAX - n as u64, n);
+            builder.push_atom(&atom);
+        }
+        builder.push_bit(true);
+        let written = builder.into_atom();
+
+        let mut iter = written.iter();
+        for n in 0..=64 {
+            let mask = if n == 64 { u64::MAX } else { (1 << n) - 1 };
+            assert_eq!(iter.next_bits(n), Some((u64::MAX - n as u64) & mask));
+            assert_eq!(iter.next_atom(atom.bit_len()), Some(atom.clone()));
+        }
+        assert_eq!(iter.remaining(), 1);
+        assert_eq!(iter.next_bits(2), None);
+        assert_eq!(iter.next_atom(2), None);
+        assert_eq!(iter.skip_bits(2), None);
+        assert_eq!(iter.skip_bits(1), Some(()));
+        assert_eq!(iter.next(), None);
+    }
+
     #[test]
     fn is_null() {
         assert!(Atom::from(0u8).is_null());
@@ -564,4 +1719,21 @@ mod tests {
             uint_ne_test!(64_222u16, 127usize);
         }
     }
+
+    #[test]
+    fn ord() {
+        let mut atoms: Vec<_> = [300u16, 0, 255, 256, 1]
+            .into_iter()
+            .map(Atom::from)
+            .collect();
+        atoms.sort();
+        assert_eq!(atoms, [0u16, 1, 255, 256, 300].map(Atom::from));
+        assert_eq!(
+            Atom::from(u128::MAX).cmp(&Atom::from(u64::MAX)),
+            Ordering::Greater
+        );
+        assert!(Atom::from(vec![0, 0, 1]) > Atom::from(vec![0xff, 0xff]));
+        assert_eq!(Atom::from("ab").cmp(&Atom::from("ab")), Ordering::Equal);
+        assert_eq!(Atom::from("ab").cmp(&Atom::from("b")), Ordering::Greater);
+    }
 }
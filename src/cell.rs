@@ -1,9 +1,10 @@
-use crate::{atom::Atom, noun::Noun, Rc};
+use crate::{atom::Atom, mug, noun::Noun, Rc};
 use std::{
-    collections::hash_map::DefaultHasher,
-    fmt::{Display, Error, Formatter},
+    cmp::Ordering,
+    fmt::{Debug, Display, Error, Formatter},
     hash::{Hash, Hasher},
-    mem::MaybeUninit,
+    mem::{self, MaybeUninit},
+    ptr,
 };
 
 /// A pair of reference-counted nouns.
@@ -33,16 +34,65 @@ use std::{
 /// assert_eq!(*cell.head(), Noun::from(Atom::from(0u8)));
 /// assert_eq!(*cell.tail(), Noun::from(Cell::from([2u8, 4u8, 8u8])));
 /// ```
-#[derive(Clone, Debug, Eq, Hash)]
+#[derive(Clone)]
 pub struct Cell {
     head: Rc<Noun>,
     tail: Rc<Noun>,
+    mug: CachedMug,
+}
+
+/// Storage for the mug of a cell, shareable between threads when the `thread-safe` feature is
+/// enabled.
+#[cfg(not(feature = "thread-safe"))]
+type MugSlot = std::cell::Cell<u32>;
+
+/// Storage for the mug of a cell, shareable between threads when the `thread-safe` feature is
+/// enabled.
+#[cfg(feature = "thread-safe")]
+type MugSlot = std::sync::atomic::AtomicU32;
+
+/// The lazily computed mug of a cell. A mug is never `0`, so `0` means that the mug hasn't been
+/// computed yet.
+#[derive(Default)]
+struct CachedMug(MugSlot);
+
+impl CachedMug {
+    /// Returns the mug, or `None` if it hasn't been computed yet.
+    fn get(&self) -> Option<u32> {
+        #[cfg(not(feature = "thread-safe"))]
+        let mug = self.0.get();
+        #[cfg(feature = "thread-safe")]
+        let mug = self.0.load(std::sync::atomic::Ordering::Relaxed);
+        (mug != 0).then_some(mug)
+    }
+
+    /// Records the computed mug.
+    fn set(&self, mug: u32) {
+        #[cfg(not(feature = "thread-safe"))]
+        self.0.set(mug);
+        #[cfg(feature = "thread-safe")]
+        self.0.store(mug, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+impl Clone for CachedMug {
+    fn clone(&self) -> Self {
+        let mug = Self::default();
+        if let Some(cached) = self.get() {
+            mug.set(cached);
+        }
+        mug
+    }
 }
 
 impl Cell {
     /// Constructs a new cell.
     fn new(head: Rc<Noun>, tail: Rc<Noun>) -> Self {
-        Self { head, tail }
+        Self {
+            head,
+            tail,
+            mug: CachedMug::default(),
+        }
     }
 
     /// Returns the head of this cell.
@@ -55,6 +105,11 @@ impl Cell {
         &self.head
     }
 
+    /// Returns the pointer to the head of this cell without cloning it.
+    pub(crate) fn head_rc(&self) -> &Rc<Noun> {
+        &self.head
+    }
+
     /// Returns the tail of this cell.
     pub fn tail(&self) -> Rc<Noun> {
         self.tail.clone()
@@ -65,12 +120,71 @@ impl Cell {
         &self.tail
     }
 
-    /// Computes the hash of this cell.
+    /// Returns the pointer to the tail of this cell without cloning it.
+    pub(crate) fn tail_rc(&self) -> &Rc<Noun> {
+        &self.tail
+    }
+
+    /// Returns the hash of this cell, which is its [mug](Self::mug()). Like the mug, it's computed
+    /// once and cached.
     pub fn hash(&self) -> u64 {
-        let mut hasher = DefaultHasher::new();
-        hasher.write_u64((&*self.head()).hash());
-        hasher.write_u64((&*self.tail()).hash());
-        hasher.finish()
+        u64::from(self.mug())
+    }
+
+    /// Computes the [mug] of this cell, Urbit's 31-bit hash of a noun.
+    ///
+    /// The mug is stable across Rust versions and equal to the mug the Urbit runtime computes for
+    /// the same cell. The mug of a cell and of every cell below it is computed once and cached, so
+    /// later calls are constant-time.
+    ///
+    /// [mug]: https://developers.urbit.org/reference/hoon/stdlib/2e#mug
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::cell::Cell;
+    /// assert_eq!(Cell::from([0u8, 0u8]).mug(), 0x192f_5588);
+    /// ```
+    pub fn mug(&self) -> u32 {
+        enum Step<'a> {
+            /// Mugs a noun, pushing the result.
+            Mug(&'a Noun),
+            /// Pops the mugs of a tail and a head, caching the mug of the cell of the two in the cell
+            /// and pushing it.
+            Both(&'a Cell),
+        }
+
+        if let Some(mug) = self.mug.get() {
+            return mug;
+        }
+        let mut steps = vec![
+            Step::Both(self),
+            Step::Mug(self.tail_ref()),
+            Step::Mug(self.head_ref()),
+        ];
+        let mut mugs = Vec::new();
+        while let Some(step) = steps.pop() {
+            match step {
+                Step::Mug(Noun::Atom(atom)) => mugs.push(atom.mug()),
+                Step::Mug(Noun::Cell(cell)) => {
+                    if let Some(mug) = cell.mug.get() {
+                        mugs.push(mug);
+                    } else {
+                        steps.push(Step::Both(cell));
+                        steps.push(Step::Mug(cell.tail_ref()));
+                        steps.push(Step::Mug(cell.head_ref()));
+                    }
+                }
+                Step::Both(cell) => {
+                    let tail = mugs.pop().expect("tail mug");
+                    let head = mugs.pop().expect("head mug");
+                    let mug = mug::mug_both(head, tail);
+                    cell.mug.set(mug);
+                    mugs.push(mug);
+                }
+            }
+        }
+        mugs.pop().expect("cell mug")
     }
 
     /// Unpacks this cell into an array of length `N`, returning `None` if the cell is not of the
@@ -103,6 +217,7 @@ impl Cell {
         let mut nouns: [MaybeUninit<Rc<Noun>>; N] = unsafe { MaybeUninit::uninit().assume_init() };
         nouns[0] = MaybeUninit::new(self.head());
         let mut noun = self.tail();
+        #[allow(clippy::needless_range_loop)]
         for i in 1..N {
             match *noun {
                 Noun::Atom(_) if i < N - 1 => return None,
@@ -156,29 +271,166 @@ impl Cell {
 
     /// Converts this cell into its head and tail, consuming the cell.
     pub fn into_parts(self) -> (Rc<Noun>, Rc<Noun>) {
-        (self.head, self.tail)
+        // Fields can't be moved out of a type that implements `Drop`.
+        (self.head.clone(), self.tail.clone())
+    }
+}
+
+/// Moves the cell out of `noun` if `noun` is a uniquely owned cell, leaving `0` in its place, and
+/// pushes it onto `cells`.
+fn take_unique_cell(noun: &mut Rc<Noun>, cells: &mut Vec<Cell>) {
+    if let Some(noun @ Noun::Cell(_)) = Rc::get_mut(noun) {
+        if let Noun::Cell(cell) = mem::replace(noun, Noun::null()) {
+            cells.push(cell);
+        }
+    }
+}
+
+impl Drop for Cell {
+    fn drop(&mut self) {
+        // The default drop glue recurses into the head and tail, which overflows the stack on deep
+        // nouns like long lists. Instead, uniquely owned descendants are detached and dropped one at
+        // a time, each with no uniquely owned cells left below it.
+        let mut cells = Vec::new();
+        take_unique_cell(&mut self.head, &mut cells);
+        take_unique_cell(&mut self.tail, &mut cells);
+        while let Some(mut cell) = cells.pop() {
+            take_unique_cell(&mut cell.head, &mut cells);
+            take_unique_cell(&mut cell.tail, &mut cells);
+        }
+    }
+}
+
+impl PartialEq for Cell {
+    /// Compares cells structurally, skipping subtrees that the two cells share, such as those of
+    /// [interned](crate::intern) nouns, and stopping at the first pair of cells with different
+    /// [mugs](Self::mug()).
+    fn eq(&self, other: &Self) -> bool {
+        if ptr::eq(self, other) {
+            return true;
+        }
+        if self.mug() != other.mug() {
+            return false;
+        }
+        let mut pairs = vec![
+            (self.tail_ref(), other.tail_ref()),
+            (self.head_ref(), other.head_ref()),
+        ];
+        while let Some(pair) = pairs.pop() {
+            if ptr::eq(pair.0, pair.1) {
+                continue;
+            }
+            match pair {
+                (Noun::Atom(this), Noun::Atom(that)) => {
+                    if this != that {
+                        return false;
+                    }
+                }
+                (Noun::Cell(this), Noun::Cell(that)) => {
+                    // Both mugs were cached when the mugs of `self` and `other` were computed.
+                    if this.mug() != that.mug() {
+                        return false;
+                    }
+                    pairs.push((this.tail_ref(), that.tail_ref()));
+                    pairs.push((this.head_ref(), that.head_ref()));
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+impl Eq for Cell {}
+
+impl Ord for Cell {
+    /// Compares cells by head and then by tail, where atoms come before cells and atoms are
+    /// compared numerically. This is Hoon's `dor` order.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let mut pairs = vec![
+            (self.tail_ref(), other.tail_ref()),
+            (self.head_ref(), other.head_ref()),
+        ];
+        while let Some(pair) = pairs.pop() {
+            let ordering = match pair {
+                (Noun::Atom(this), Noun::Atom(that)) => this.cmp(that),
+                (Noun::Atom(_), Noun::Cell(_)) => Ordering::Less,
+                (Noun::Cell(_), Noun::Atom(_)) => Ordering::Greater,
+                (Noun::Cell(this), Noun::Cell(that)) => {
+                    pairs.push((this.tail_ref(), that.tail_ref()));
+                    pairs.push((this.head_ref(), that.head_ref()));
+                    Ordering::Equal
+                }
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+impl PartialOrd for Cell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Hash for Cell {
+    /// Feeds the [mug](Self::mug()) of this cell to `state`, which takes constant time once the mug
+    /// is cached.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u32(self.mug());
+    }
+}
+
+impl Debug for Cell {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
+        f.debug_struct("Cell")
+            .field("head", &self.head)
+            .field("tail", &self.tail)
+            .finish()
     }
 }
 
 impl Display for Cell {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
-        // This is unfortunately more complicated than
-        // `write!(f, "[{} {}]", self.head(), self.tail())` to handle the fact that brackets are
-        // left-associative and therefore need not always be printed.
-        write!(f, "[")?;
-        match (&*self.head(), &*self.tail()) {
-            (head, Noun::Atom(tail)) => write!(f, "{} {}", head, tail)?,
-            (head, _) => {
-                write!(f, "{} ", head)?;
-                let mut tail = self.tail();
-                while let Noun::Cell(cell) = &*tail {
-                    write!(f, "{} ", cell.head())?;
-                    tail = cell.tail();
+        enum Token<'a> {
+            Noun(&'a Noun),
+            Text(&'static str),
+        }
+
+        /// Pushes the tokens of `cell` in reverse order. Brackets are left-associative and
+        /// therefore need not be printed around a cell in tail position.
+        fn push_cell<'a>(cell: &'a Cell, tokens: &mut Vec<Token<'a>>) {
+            let mut elems = vec![cell.head_ref()];
+            let mut tail = cell.tail_ref();
+            while let Noun::Cell(cell) = tail {
+                elems.push(cell.head_ref());
+                tail = cell.tail_ref();
+            }
+            elems.push(tail);
+
+            tokens.push(Token::Text("]"));
+            for (i, elem) in elems.into_iter().enumerate().rev() {
+                tokens.push(Token::Noun(elem));
+                if i > 0 {
+                    tokens.push(Token::Text(" "));
                 }
-                write!(f, "{}", tail)?;
             }
+            tokens.push(Token::Text("["));
         }
-        write!(f, "]")
+
+        let mut tokens = Vec::new();
+        push_cell(self, &mut tokens);
+        while let Some(token) = tokens.pop() {
+            match token {
+                Token::Noun(Noun::Atom(atom)) => write!(f, "{}", atom)?,
+                Token::Noun(Noun::Cell(cell)) => push_cell(cell, &mut tokens),
+                Token::Text(text) => f.write_str(text)?,
+            }
+        }
+        Ok(())
     }
 }
 
@@ -295,15 +547,10 @@ impl From<Vec<Rc<Noun>>> for Cell {
     }
 }
 
-impl PartialEq for Cell {
-    fn eq(&self, other: &Self) -> bool {
-        self.head == other.head && self.tail == other.tail
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::hash_map::DefaultHasher;
 
     #[test]
     fn to_array() {
@@ -383,6 +630,48 @@ mod tests {
     }
 
     #[test]
+    fn mug_cache() {
+        // A list too long to mug recursively, built twice so that the two share no cells.
+        let list = || {
+            let mut list = Rc::new(Noun::null());
+            for n in 0..100_000u32 {
+                list = Rc::<Noun>::from(Cell::from([Rc::<Noun>::from(Atom::from(n)), list]));
+            }
+            list
+        };
+        let (Noun::Cell(this), Noun::Cell(that)) = (&*list(), &*list()) else {
+            panic!("expected cells");
+        };
+        assert_eq!(this.mug.get(), None);
+        let mug = this.mug();
+        assert_eq!(this.mug.get(), Some(mug));
+        assert_eq!(this.clone().mug.get(), Some(mug));
+        assert_eq!(that.mug(), mug);
+        assert_eq!(this, that);
+
+        assert_eq!(this.hash(), u64::from(mug));
+        // A second call reads the cached mug rather than walking the list again.
+        this.mug.set(7);
+        assert_eq!(this.hash(), 7);
+        assert_eq!(Noun::Cell(this.clone()).hash(), 7);
+        this.mug.set(mug);
+
+        let mut hasher = DefaultHasher::new();
+        Hash::hash(this, &mut hasher);
+        let mut other = DefaultHasher::new();
+        Hash::hash(that, &mut other);
+        assert_eq!(hasher.finish(), other.finish());
+
+        assert_eq!(Cell::from([0u8, 0u8]).mug(), 0x192f_5588);
+        assert_ne!(Cell::from([1u8, 2u8]), Cell::from([2u8, 1u8]));
+        assert_ne!(
+            Cell::from([Cell::from([1u8, 2u8]), Cell::from([3u8, 4u8])]),
+            Cell::from([Cell::from([1u8, 2u8]), Cell::from([3u8, 5u8])])
+        );
+    }
+
+    #[test]
+    #[allow(clippy::just_underscores_and_digits)]
     fn from_vec() {
         {
             let _0 = Rc::<Noun>::from(Atom::from(0u8));
@@ -1,6 +1,18 @@
-//! Conversions to and from [`Noun`](crate::noun::Noun).
+//! Conversions to and from [`Noun`].
+//!
+//! Types convert to nouns with [`ToNoun`] and from nouns with [`FromNoun`]. With the `derive`
+//! feature enabled, both can be derived for structs and enums with `#[derive(ToNoun, FromNoun)]`,
+//! as described in the `noun-derive` crate.
 
-use std::fmt::{self, Display, Formatter};
+use crate::{atom::Atom, cell::Cell, noun::Noun, print::Aura, Rc};
+use std::{
+    fmt::{self, Display, Formatter},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+#[cfg(feature = "derive")]
+pub use noun_derive::{FromNoun, ToNoun};
 
 /// Errors that occur when converting from a noun.
 #[derive(Debug)]
@@ -11,8 +23,16 @@ pub enum Error {
     AtomToStr,
     /// A null atom was expected.
     ExpectedNull,
+    /// Converting a field failed, where `path` is the `.`-separated path to the field.
+    Field { path: String, err: Box<Error> },
     /// An error specific to the implementing type occurred.
     ImplType,
+    /// An atom was not valid in the aura it was being converted from.
+    InvalidAura,
+    /// The head of a tagged noun was not one of the tags the implementing type recognizes.
+    InvalidTag,
+    /// A noun that should be a treap, the structure of a map or set, is malformed.
+    InvalidTreap,
     /// No value exists at a particular axis of a cell.
     MissingValue,
     /// Encountered an atom when a cell was expected.
@@ -30,7 +50,11 @@ impl Display for Error {
             ),
             Self::AtomToStr => write!(f, "the atom is not composed of valid UTF-8 bytes"),
             Self::ExpectedNull => write!(f, "a null atom was expected"),
+            Self::Field { path, err } => write!(f, "failed to convert field `{}`: {}", path, err),
             Self::ImplType => write!(f, "an error specific to the implementing type occurred"),
+            Self::InvalidAura => write!(f, "the atom is not valid in the expected aura"),
+            Self::InvalidTag => write!(f, "the noun's tag was not recognized"),
+            Self::InvalidTreap => write!(f, "the noun is not a well-formed treap"),
             Self::MissingValue => write!(f, "the noun does not have a value at this axis"),
             Self::UnexpectedAtom => write!(f, "an atom was encountered when a cell was expected"),
             Self::UnexpectedCell => write!(f, "a cell was encountered when an atom was expected"),
@@ -38,6 +62,441 @@ impl Display for Error {
     }
 }
 
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Field { err, .. } => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Error {
+    /// Records that this error occurred converting the field `name`, prepending `name` to the path
+    /// of an error that occurred converting a field nested within it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::convert::Error;
+    /// let err = Error::UnexpectedCell.in_field("ship").in_field("0");
+    /// assert_eq!(
+    ///     err.to_string(),
+    ///     "failed to convert field `0.ship`: a cell was encountered when an atom was expected"
+    /// );
+    /// ```
+    pub fn in_field(self, name: &str) -> Self {
+        match self {
+            Self::Field { path, err } => Self::Field {
+                path: format!("{}.{}", name, path),
+                err,
+            },
+            err => Self::Field {
+                path: String::from(name),
+                err: Box::new(err),
+            },
+        }
+    }
+}
+
+/// Converts a value into a [`Noun`].
+///
+/// Unsigned integers and strings convert to atoms, `bool` to a loobean (`true` is `0`), [`Option`]
+/// to a unit (`~` or `[~ x]`), [`Vec`] to a null-terminated list, and tuples to cells that nest to
+/// the right.
+///
+/// # Examples
+/// ```
+/// # use noun::{convert::ToNoun, Noun};
+/// let noun = (7u8, vec!["a", "b"], Some(true)).to_noun();
+/// assert_eq!(noun, "[7 ~['a' 'b'] [~ %.y]]".parse::<Noun>().unwrap());
+/// ```
+pub trait ToNoun {
+    /// Converts this value into a noun.
+    fn to_noun(&self) -> Noun;
+}
+
+/// Converts a [`Noun`] into a value, the inverse of [`ToNoun`].
+///
+/// # Examples
+/// ```
+/// # use noun::{convert::FromNoun, Noun};
+/// let noun: Noun = "[7 ~['a' 'b'] [~ %.y]]".parse().unwrap();
+/// let value = <(u8, Vec<String>, Option<bool>)>::from_noun(&noun).unwrap();
+/// assert_eq!(value, (7, vec![String::from("a"), String::from("b")], Some(true)));
+/// ```
+pub trait FromNoun: Sized {
+    /// Converts `noun` into a value of this type.
+    fn from_noun(noun: &Noun) -> Result<Self, Error>;
+}
+
+/// Returns the atom `noun` is, failing if it's a cell.
+fn atom(noun: &Noun) -> Result<&Atom, Error> {
+    match noun {
+        Noun::Atom(atom) => Ok(atom),
+        Noun::Cell(_) => Err(Error::UnexpectedCell),
+    }
+}
+
+impl ToNoun for Noun {
+    fn to_noun(&self) -> Noun {
+        self.clone()
+    }
+}
+
+impl FromNoun for Noun {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        Ok(noun.clone())
+    }
+}
+
+impl ToNoun for Rc<Noun> {
+    fn to_noun(&self) -> Noun {
+        (**self).clone()
+    }
+}
+
+impl FromNoun for Rc<Noun> {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        Ok(Rc::new(noun.clone()))
+    }
+}
+
+impl ToNoun for Atom {
+    fn to_noun(&self) -> Noun {
+        Noun::from(self.clone())
+    }
+}
+
+impl FromNoun for Atom {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        atom(noun).cloned()
+    }
+}
+
+impl ToNoun for Cell {
+    fn to_noun(&self) -> Noun {
+        Noun::from(self.clone())
+    }
+}
+
+impl FromNoun for Cell {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        match noun {
+            Noun::Atom(_) => Err(Error::UnexpectedAtom),
+            Noun::Cell(cell) => Ok(cell.clone()),
+        }
+    }
+}
+
+macro_rules! impl_noun_conversions_for_uint {
+    ($uint:ty, $as_uint:ident) => {
+        impl ToNoun for $uint {
+            fn to_noun(&self) -> Noun {
+                Noun::from(Atom::from(*self))
+            }
+        }
+
+        impl FromNoun for $uint {
+            fn from_noun(noun: &Noun) -> Result<Self, Error> {
+                atom(noun)?.$as_uint().ok_or(Error::AtomToUint)
+            }
+        }
+    };
+}
+
+impl_noun_conversions_for_uint!(u8, as_u8);
+impl_noun_conversions_for_uint!(u16, as_u16);
+impl_noun_conversions_for_uint!(u32, as_u32);
+impl_noun_conversions_for_uint!(u64, as_u64);
+impl_noun_conversions_for_uint!(u128, as_u128);
+impl_noun_conversions_for_uint!(usize, as_usize);
+
+impl ToNoun for bool {
+    fn to_noun(&self) -> Noun {
+        Noun::from(Atom::from(u8::from(!*self)))
+    }
+}
+
+impl FromNoun for bool {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        match atom(noun)?.as_u8() {
+            Some(0) => Ok(true),
+            Some(1) => Ok(false),
+            _ => Err(Error::InvalidAura),
+        }
+    }
+}
+
+impl ToNoun for str {
+    fn to_noun(&self) -> Noun {
+        Noun::from(Atom::from(self))
+    }
+}
+
+impl ToNoun for &str {
+    fn to_noun(&self) -> Noun {
+        Noun::from(Atom::from(*self))
+    }
+}
+
+impl ToNoun for String {
+    fn to_noun(&self) -> Noun {
+        Noun::from(Atom::from(self.as_str()))
+    }
+}
+
+impl FromNoun for String {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        Self::try_from(noun)
+    }
+}
+
+impl<T: ToNoun + ?Sized> ToNoun for Box<T> {
+    fn to_noun(&self) -> Noun {
+        (**self).to_noun()
+    }
+}
+
+impl<T: FromNoun> FromNoun for Box<T> {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        T::from_noun(noun).map(Box::new)
+    }
+}
+
+impl<T: ToNoun> ToNoun for Option<T> {
+    fn to_noun(&self) -> Noun {
+        match self {
+            Some(value) => Noun::from(Cell::from([Noun::null(), value.to_noun()])),
+            None => Noun::null(),
+        }
+    }
+}
+
+impl<T: FromNoun> FromNoun for Option<T> {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        match noun {
+            Noun::Atom(atom) if atom.is_null() => Ok(None),
+            Noun::Atom(_) => Err(Error::ExpectedNull),
+            Noun::Cell(cell) if cell.head_ref().is_null() => {
+                T::from_noun(cell.tail_ref()).map(Some)
+            }
+            Noun::Cell(_) => Err(Error::ExpectedNull),
+        }
+    }
+}
+
+impl<T: ToNoun> ToNoun for Vec<T> {
+    fn to_noun(&self) -> Noun {
+        let mut list = Rc::new(Noun::null());
+        for elem in self.iter().rev() {
+            list = Rc::<Noun>::from(Cell::from([Rc::new(elem.to_noun()), list]));
+        }
+        Rc::try_unwrap(list).unwrap_or_else(|list| (*list).clone())
+    }
+}
+
+impl<T: FromNoun> FromNoun for Vec<T> {
+    fn from_noun(mut noun: &Noun) -> Result<Self, Error> {
+        let mut elems = Vec::new();
+        loop {
+            match noun {
+                Noun::Atom(atom) if atom.is_null() => return Ok(elems),
+                Noun::Atom(_) => return Err(Error::ExpectedNull),
+                Noun::Cell(cell) => {
+                    let elem = T::from_noun(cell.head_ref())
+                        .map_err(|err| err.in_field(&elems.len().to_string()))?;
+                    elems.push(elem);
+                    noun = cell.tail_ref();
+                }
+            }
+        }
+    }
+}
+
+macro_rules! impl_noun_conversions_for_tuple {
+    ($($elem:ident $var:ident $idx:tt),+; $last:ident $last_var:ident $last_idx:tt) => {
+        impl<$($elem: ToNoun,)+ $last: ToNoun> ToNoun for ($($elem,)+ $last) {
+            fn to_noun(&self) -> Noun {
+                Noun::from(Cell::from([$(self.$idx.to_noun(),)+ self.$last_idx.to_noun()]))
+            }
+        }
+
+        impl<$($elem: FromNoun,)+ $last: FromNoun> FromNoun for ($($elem,)+ $last) {
+            fn from_noun(noun: &Noun) -> Result<Self, Error> {
+                let mut rest = noun;
+                $(
+                    let $var = {
+                        let field = stringify!($idx);
+                        let (head, tail) = split_cell(rest).map_err(|err| err.in_field(field))?;
+                        rest = tail;
+                        $elem::from_noun(head).map_err(|err| err.in_field(field))?
+                    };
+                )+
+                let $last_var = $last::from_noun(rest).map_err(|err| err.in_field(stringify!($last_idx)))?;
+                Ok(($($var,)+ $last_var))
+            }
+        }
+    };
+}
+
+impl_noun_conversions_for_tuple!(A a 0; B b 1);
+impl_noun_conversions_for_tuple!(A a 0, B b 1; C c 2);
+impl_noun_conversions_for_tuple!(A a 0, B b 1, C c 2; D d 3);
+impl_noun_conversions_for_tuple!(A a 0, B b 1, C c 2, D d 3; E e 4);
+
+/// Returns the head and tail of `noun`, failing if it's an atom.
+#[doc(hidden)]
+pub fn split_cell(noun: &Noun) -> Result<(&Noun, &Noun), Error> {
+    match noun {
+        Noun::Atom(_) => Err(Error::UnexpectedAtom),
+        Noun::Cell(cell) => Ok((cell.head_ref(), cell.tail_ref())),
+    }
+}
+
+/// Types naming the auras of [`AuraAtom`]s.
+pub mod aura {
+    use crate::print::Aura;
+    use std::{fmt::Debug, hash::Hash};
+
+    /// A type naming an [`Aura`], the type parameter of an [`AuraAtom`](super::AuraAtom).
+    pub trait Marker: Copy + Debug + Eq + Hash {
+        /// The aura this type names.
+        const AURA: Aura;
+    }
+
+    macro_rules! markers {
+        ($($name:ident: $doc:literal,)+) => {
+            $(
+                #[doc = $doc]
+                #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+                pub enum $name {}
+
+                impl Marker for $name {
+                    const AURA: Aura = Aura::$name;
+                }
+            )+
+        };
+    }
+
+    markers! {
+        Ud: "Names [`Aura::Ud`], unsigned decimal.",
+        Ux: "Names [`Aura::Ux`], unsigned hexadecimal.",
+        Ub: "Names [`Aura::Ub`], unsigned binary.",
+        Uv: "Names [`Aura::Uv`], unsigned base32.",
+        Uw: "Names [`Aura::Uw`], unsigned base64.",
+        T: "Names [`Aura::T`], UTF-8 text.",
+        Tas: "Names [`Aura::Tas`], a symbol.",
+        P: "Names [`Aura::P`], a ship name.",
+        Da: "Names [`Aura::Da`], an absolute date.",
+    }
+}
+
+/// An atom that's valid in the aura named by `A`, which is written as text in that aura.
+///
+/// A field of this type stores its atom in a derived conversion. Every atom is valid in the
+/// numeric auras and in `@p`, while a `@t` must be UTF-8, a `@tas` must be a symbol or null, and a
+/// `@da` must fit in 128 bits. Because the atom is checked when an `AuraAtom` is created, converting
+/// one to a noun can't fail, and converting that noun back gives the same `AuraAtom`.
+///
+/// The text of a `@t` or `@tas` is the text itself, and the text of any other aura is an atom
+/// written in Hoon's literal syntax.
+///
+/// # Examples
+/// ```
+/// # use noun::{atom::Atom, convert::{aura, AuraAtom, Error, ToNoun}, Noun};
+/// let ship: AuraAtom<aura::P> = "~marzod".parse().unwrap();
+/// assert_eq!(ship.to_noun(), Noun::from(Atom::from(256u16)));
+/// assert_eq!(ship.to_string(), "~marzod");
+///
+/// let mark: AuraAtom<aura::Tas> = "json".parse().unwrap();
+/// assert_eq!(mark.to_noun(), Noun::from(Atom::from("json")));
+/// assert!(matches!("Json".parse::<AuraAtom<aura::Tas>>(), Err(Error::InvalidAura)));
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct AuraAtom<A: aura::Marker> {
+    atom: Atom,
+    aura: PhantomData<A>,
+}
+
+impl<A: aura::Marker> AuraAtom<A> {
+    /// Creates an `AuraAtom` from `atom`, failing with [`Error::InvalidAura`] if `atom` isn't
+    /// valid in the aura.
+    pub fn new(atom: Atom) -> Result<Self, Error> {
+        let valid = match A::AURA {
+            Aura::T => std::str::from_utf8(atom.as_bytes()).is_ok(),
+            Aura::Tas => atom.is_null() || crate::print::is_term(atom.as_bytes()),
+            Aura::Da => atom.as_u128().is_some(),
+            _ => true,
+        };
+        if valid {
+            Ok(Self {
+                atom,
+                aura: PhantomData,
+            })
+        } else {
+            Err(Error::InvalidAura)
+        }
+    }
+
+    /// Returns the atom.
+    pub fn as_atom(&self) -> &Atom {
+        &self.atom
+    }
+
+    /// Returns the atom, consuming this `AuraAtom`.
+    pub fn into_atom(self) -> Atom {
+        self.atom
+    }
+}
+
+/// The null atom, which is valid in every aura.
+impl<A: aura::Marker> Default for AuraAtom<A> {
+    fn default() -> Self {
+        Self {
+            atom: Atom::null(),
+            aura: PhantomData,
+        }
+    }
+}
+
+impl<A: aura::Marker> FromStr for AuraAtom<A> {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match A::AURA {
+            Aura::T | Aura::Tas => Self::new(Atom::from(text)),
+            _ => match text.parse() {
+                Ok(Noun::Atom(atom)) => Self::new(atom),
+                _ => Err(Error::InvalidAura),
+            },
+        }
+    }
+}
+
+impl<A: aura::Marker> Display for AuraAtom<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match A::AURA {
+            Aura::T | Aura::Tas => {
+                f.write_str(std::str::from_utf8(self.atom.as_bytes()).expect("UTF-8"))
+            }
+            Aura::Da => f.write_str(&self.atom.to_da().expect("128-bit date")),
+            aura => f.write_str(&aura.render(&self.atom)),
+        }
+    }
+}
+
+impl<A: aura::Marker> ToNoun for AuraAtom<A> {
+    fn to_noun(&self) -> Noun {
+        Noun::from(self.atom.clone())
+    }
+}
+
+impl<A: aura::Marker> FromNoun for AuraAtom<A> {
+    fn from_noun(noun: &Noun) -> Result<Self, Error> {
+        Self::new(atom(noun)?.clone())
+    }
+}
+
 /// Converts [`Noun`](crate::Noun)s to and from other complex types.
 ///
 /// There are three forms of this macro:
@@ -220,22 +679,36 @@ macro_rules! convert {
 
 #[cfg(test)]
 mod tests {
-    use crate::{atom::Atom, cell::Cell, noun::Noun};
+    use super::*;
+    use crate::noun;
+    use noun_derive::{FromNoun, ToNoun};
+
+    /// Asserts that `value` converts to the noun written as `text` and back.
+    fn round_trip<T: FromNoun + ToNoun + PartialEq + fmt::Debug>(value: T, text: &str) {
+        let noun: Noun = text.parse().expect("noun");
+        assert_eq!(value.to_noun(), noun, "{}", text);
+        assert_eq!(T::from_noun(&noun).expect("from noun"), value, "{}", text);
+    }
+
+    /// Returns the path and error of a failure to convert a field.
+    fn field_err(err: Error) -> (String, Error) {
+        match err {
+            Error::Field { path, err } => (path, *err),
+            err => panic!("expected a field error: {:?}", err),
+        }
+    }
 
     #[test]
     fn convert() {
         // Noun -> Vec<String>: expect failure.
         {
             {
-                let noun = Noun::from(Cell::from(["no", "null", "terminator"]));
+                let noun = noun!["no" "null" "terminator"];
                 assert!(convert!(&noun => Vec<String>).is_err());
             }
 
             {
-                let noun = Noun::from(Cell::from([
-                    Noun::from(Cell::from(["unexpected", "cell"])),
-                    Noun::null(),
-                ]));
+                let noun = noun![["unexpected" "cell"] ~];
                 assert!(convert!(&noun => Vec<String>).is_err());
             }
         }
@@ -245,16 +718,241 @@ mod tests {
             {
                 let strings = ["a", "b", "c"];
                 let noun = convert!(strings.iter() => Noun).expect("&[str] to Noun");
-                assert_eq!(
-                    noun,
-                    Noun::from(Cell::from([
-                        Atom::from("a"),
-                        Atom::from("b"),
-                        Atom::from("c"),
-                        Atom::null()
-                    ]))
-                );
+                assert_eq!(noun, noun!["a" "b" "c" ~]);
             }
         }
     }
+
+    #[test]
+    fn primitives() {
+        round_trip(0x1_0000u32, "0x1.0000");
+        round_trip(u128::MAX, "0xffff.ffff.ffff.ffff.ffff.ffff.ffff.ffff");
+        round_trip(true, "%.y");
+        round_trip(false, "%.n");
+        round_trip(String::from("héllo"), "'héllo'");
+        round_trip(Some(Some(7u8)), "[~ ~ 7]");
+        round_trip(None::<u8>, "~");
+        round_trip(vec![1u8, 2, 3], "~[1 2 3]");
+        round_trip(Vec::<u8>::new(), "~");
+        round_trip(Box::new(5u8), "5");
+        round_trip((1u8, (2u8, 3u8), String::from("a")), "[1 [2 3] 'a']");
+        round_trip((1u8, 2u8, 3u8, 4u8, 5u8), "[1 2 3 4 5]");
+        assert_eq!("foo".to_noun(), noun!["foo"]);
+
+        assert!(matches!(
+            u8::from_noun(&noun![256u16]),
+            Err(Error::AtomToUint)
+        ));
+        assert!(matches!(
+            bool::from_noun(&noun![2]),
+            Err(Error::InvalidAura)
+        ));
+        assert!(matches!(
+            Option::<u8>::from_noun(&noun![1 2]),
+            Err(Error::ExpectedNull)
+        ));
+        let (path, err) = field_err(Vec::<u8>::from_noun(&noun![1 [2 3] ~]).unwrap_err());
+        assert_eq!(path, "1");
+        assert!(matches!(err, Error::UnexpectedCell));
+        let (path, err) = field_err(<(u8, u8, u8)>::from_noun(&noun![1 2]).unwrap_err());
+        assert_eq!(path, "1");
+        assert!(matches!(err, Error::UnexpectedAtom));
+    }
+
+    #[test]
+    fn aura_atom() {
+        fn text<A: aura::Marker>(text: &str) -> Result<String, Error> {
+            let atom = text.parse::<AuraAtom<A>>()?;
+            let noun = atom.to_noun();
+            assert_eq!(AuraAtom::<A>::from_noun(&noun).expect("from noun"), atom);
+            Ok(atom.to_string())
+        }
+
+        assert_eq!(text::<aura::Ud>("1.000").unwrap(), "1.000");
+        assert_eq!(text::<aura::Ux>("0x1.0000").unwrap(), "0x1.0000");
+        assert_eq!(text::<aura::P>("~sampel-palnet").unwrap(), "~sampel-palnet");
+        assert_eq!(text::<aura::T>("Héllo").unwrap(), "Héllo");
+        assert_eq!(text::<aura::Tas>("work-done").unwrap(), "work-done");
+        assert_eq!(text::<aura::Tas>("").unwrap(), "");
+        assert_eq!(
+            text::<aura::Da>("~2022.7.8..16.43.50").unwrap(),
+            "~2022.7.8..16.43.50"
+        );
+        assert!(matches!(text::<aura::P>(""), Err(Error::InvalidAura)));
+        assert!(matches!(text::<aura::Ud>("[1 2]"), Err(Error::InvalidAura)));
+        assert!(matches!(text::<aura::Tas>("Json"), Err(Error::InvalidAura)));
+        assert!(matches!(text::<aura::Tas>("1x"), Err(Error::InvalidAura)));
+
+        assert!(matches!(
+            AuraAtom::<aura::T>::new(Atom::from(vec![0xffu8])),
+            Err(Error::InvalidAura)
+        ));
+        assert!(matches!(
+            AuraAtom::<aura::Da>::from_noun(&Noun::from(Atom::from(vec![1u8; 17]))),
+            Err(Error::InvalidAura)
+        ));
+        assert!(matches!(
+            AuraAtom::<aura::Ud>::from_noun(&noun![1 2]),
+            Err(Error::UnexpectedCell)
+        ));
+        assert_eq!(AuraAtom::<aura::P>::default().to_string(), "~zod");
+    }
+
+    #[derive(Debug, Default, FromNoun, PartialEq, ToNoun)]
+    #[noun(crate = "crate")]
+    struct Ship {
+        name: AuraAtom<aura::P>,
+        life: u32,
+        #[noun(nullable)]
+        sponsor: Option<Box<Ship>>,
+        #[noun(skip)]
+        cached: Option<u64>,
+    }
+
+    #[derive(Debug, FromNoun, PartialEq, ToNoun)]
+    #[noun(crate = "crate")]
+    struct Pair(u8, String);
+
+    #[derive(Debug, FromNoun, PartialEq, ToNoun)]
+    #[noun(crate = "crate")]
+    struct Meters(u64);
+
+    #[derive(Debug, FromNoun, PartialEq, ToNoun)]
+    #[noun(crate = "crate")]
+    struct Nothing;
+
+    #[derive(Debug, FromNoun, PartialEq, ToNoun)]
+    #[noun(crate = "crate", tag = "ping")]
+    struct Ping {
+        now: AuraAtom<aura::Da>,
+    }
+
+    #[derive(Debug, FromNoun, PartialEq, ToNoun)]
+    #[noun(crate = "crate")]
+    struct Note {
+        #[noun(aura = "t")]
+        text: Atom,
+        #[noun(aura = "ux")]
+        id: Atom,
+    }
+
+    #[test]
+    fn derive_struct() {
+        let zod = Ship {
+            name: "~zod".parse().unwrap(),
+            life: 1,
+            sponsor: None,
+            cached: None,
+        };
+        round_trip(
+            Ship {
+                name: "~marzod".parse().unwrap(),
+                life: 2,
+                sponsor: Some(Box::new(zod)),
+                cached: None,
+            },
+            "[~marzod 2 ~zod 1 ~]",
+        );
+        assert_eq!(Ship::default().to_noun(), noun![0 0 0]);
+        let ship = Ship {
+            name: "~zod".parse().unwrap(),
+            cached: Some(7),
+            ..Ship::default()
+        };
+        assert_eq!(ship.to_noun(), noun![0 0 0]);
+        round_trip(Pair(1, String::from("a")), "[1 'a']");
+        round_trip(Meters(42), "42");
+        round_trip(Nothing, "~");
+        round_trip(
+            Ping {
+                now: "~2022.7.8..16.43.50".parse().unwrap(),
+            },
+            "[%ping ~2022.7.8..16.43.50]",
+        );
+        round_trip(
+            Note {
+                text: Atom::from("hi"),
+                id: Atom::from(0xbeefu16),
+            },
+            "['hi' 0xbeef]",
+        );
+        assert_eq!(Noun::from(Meters(42)), noun![42]);
+        assert_eq!(Meters::try_from(&noun![42]).unwrap(), Meters(42));
+
+        let (path, err) = field_err(Ship::from_noun(&noun![0 0 [0 [1 2] 0]]).unwrap_err());
+        assert_eq!(path, "sponsor.life");
+        assert!(matches!(err, Error::UnexpectedCell));
+        let (path, err) = field_err(Ship::from_noun(&noun![0 0]).unwrap_err());
+        assert_eq!(path, "life");
+        assert!(matches!(err, Error::UnexpectedAtom));
+        assert!(matches!(
+            Ping::from_noun(&noun![%pong 0]),
+            Err(Error::InvalidTag)
+        ));
+        let (path, err) =
+            field_err(Ping::from_noun(&noun![%ping (Atom::from(vec![1u8; 17]))]).unwrap_err());
+        assert_eq!(path, "now");
+        assert!(matches!(err, Error::InvalidAura));
+        let (path, err) =
+            field_err(Note::from_noun(&noun![(Atom::from(vec![0xffu8])) 0]).unwrap_err());
+        assert_eq!(path, "text");
+        assert!(matches!(err, Error::InvalidAura));
+        assert!(matches!(
+            Nothing::from_noun(&noun![1]),
+            Err(Error::ExpectedNull)
+        ));
+    }
+
+    #[derive(Debug, FromNoun, PartialEq, ToNoun)]
+    #[noun(crate = "crate")]
+    enum Event<T> {
+        Poke {
+            mark: AuraAtom<aura::Tas>,
+            data: T,
+        },
+        Watch(Vec<String>),
+        #[noun(tag = "kick")]
+        Leave,
+        WorkDone,
+    }
+
+    #[test]
+    fn derive_enum() {
+        round_trip(
+            Event::Poke {
+                mark: "json".parse().unwrap(),
+                data: (1u8, 2u8),
+            },
+            "[%poke %json 1 2]",
+        );
+        round_trip(
+            Event::<u8>::Watch(vec![String::from("/a")]),
+            "[%watch ~['/a']]",
+        );
+        round_trip(Event::<u8>::Leave, "%kick");
+        round_trip(Event::<u8>::WorkDone, "%work-done");
+        let events = convert!(&noun![%kick %work-done ~] => Vec<Event<u8>>).unwrap();
+        assert_eq!(events, vec![Event::Leave, Event::WorkDone]);
+
+        assert!(matches!(
+            Event::<u8>::from_noun(&noun![%leave]),
+            Err(Error::InvalidTag)
+        ));
+        assert!(matches!(
+            Event::<u8>::from_noun(&noun![%kick 0]),
+            Err(Error::InvalidTag)
+        ));
+        assert!(matches!(
+            Event::<u8>::from_noun(&noun![[1 2] 0]),
+            Err(Error::UnexpectedCell)
+        ));
+        let (path, err) = field_err(Event::<u8>::from_noun(&noun![%poke "Json" 0]).unwrap_err());
+        assert_eq!(path, "Poke.mark");
+        assert!(matches!(err, Error::InvalidAura));
+        let err = Event::<u8>::from_noun(&noun![%watch "/a" [1 2] ~]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "failed to convert field `Watch.0.1`: a cell was encountered when an atom was expected"
+        );
+    }
 }
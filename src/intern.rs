@@ -0,0 +1,219 @@
+//! Hash-consing of nouns.
+
+use crate::{
+    atom::Atom,
+    cell::Cell,
+    noun::{Noun, WordMap},
+    Rc,
+};
+use std::{
+    borrow::Borrow,
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
+
+/// An arena that maps every distinct noun to a single canonical, shared [`Rc<Noun>`].
+///
+/// Nouns that are built or cued separately often hold many structurally equal but separately
+/// allocated subtrees. Interning a noun rebuilds it bottom up from canonical subtrees, so equal
+/// subtrees end up as one allocation, and equal interned nouns are pointer-equal, which makes
+/// comparing them cheap.
+///
+/// The interner keeps every noun it has interned alive until it's dropped or [cleared].
+///
+/// [cleared]: NounInterner::clear
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, intern::NounInterner, Noun, Rc};
+/// let mut interner = NounInterner::new();
+/// let a = Rc::<Noun>::from(Cell::from([Cell::from([1u8, 2u8]), Cell::from([1u8, 2u8])]));
+/// let a = a.intern(&mut interner);
+/// let Noun::Cell(cell) = &*a else { unreachable!() };
+/// assert!(Rc::ptr_eq(&cell.head(), &cell.tail()));
+///
+/// let b = Rc::<Noun>::from(Cell::from([Cell::from([1u8, 2u8]), Cell::from([1u8, 2u8])]));
+/// assert!(Rc::ptr_eq(&b.intern(&mut interner), &a));
+/// ```
+#[derive(Debug, Default)]
+pub struct NounInterner {
+    /// The canonical atoms.
+    atoms: HashSet<AtomKey>,
+    /// The canonical cells, keyed by the addresses of their canonical head and tail. Because the
+    /// head and tail of a canonical cell are themselves canonical, two cells are equal exactly when
+    /// their heads and tails are at the same addresses.
+    cells: WordMap<(usize, usize), Rc<Noun>>,
+}
+
+impl NounInterner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of distinct nouns, counting every subtree, in this interner.
+    pub fn len(&self) -> usize {
+        self.atoms.len() + self.cells.len()
+    }
+
+    /// Returns `true` if this interner has no nouns.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes every noun from this interner. Nouns interned afterwards won't be pointer-equal to
+    /// nouns interned before.
+    pub fn clear(&mut self) {
+        self.atoms.clear();
+        self.cells.clear();
+    }
+
+    /// Returns the canonical noun equal to `noun`, adding `noun` and its subtrees to this interner
+    /// if they aren't in it yet. See [`Noun::intern()`].
+    pub fn intern(&mut self, noun: &Rc<Noun>) -> Rc<Noun> {
+        enum Step<'a> {
+            /// Interns a noun.
+            Intern(&'a Rc<Noun>),
+            /// Interns a cell whose head and tail are already interned.
+            Both(&'a Rc<Noun>, &'a Cell),
+        }
+
+        // The canonical noun of each subtree of `noun` visited so far, by address, so that shared
+        // subtrees are only visited once.
+        let mut canonical = WordMap::<*const Noun, Rc<Noun>>::default();
+        let mut steps = vec![Step::Intern(noun)];
+        while let Some(step) = steps.pop() {
+            match step {
+                Step::Intern(noun) if canonical.contains_key(&Rc::as_ptr(noun)) => {}
+                Step::Intern(noun) => match &**noun {
+                    Noun::Atom(atom) => {
+                        let interned = match self.atoms.get(atom) {
+                            Some(AtomKey(interned)) => interned.clone(),
+                            None => {
+                                self.atoms.insert(AtomKey(noun.clone()));
+                                noun.clone()
+                            }
+                        };
+                        canonical.insert(Rc::as_ptr(noun), interned);
+                    }
+                    Noun::Cell(cell) => {
+                        steps.push(Step::Both(noun, cell));
+                        steps.push(Step::Intern(cell.tail_rc()));
+                        steps.push(Step::Intern(cell.head_rc()));
+                    }
+                },
+                Step::Both(noun, cell) => {
+                    let head = canonical[&Rc::as_ptr(cell.head_rc())].clone();
+                    let tail = canonical[&Rc::as_ptr(cell.tail_rc())].clone();
+                    let key = (Rc::as_ptr(&head) as usize, Rc::as_ptr(&tail) as usize);
+                    let interned = self.cells.entry(key).or_insert_with(|| {
+                        if Rc::ptr_eq(&head, cell.head_rc()) && Rc::ptr_eq(&tail, cell.tail_rc()) {
+                            noun.clone()
+                        } else {
+                            Rc::new(Noun::from(Cell::from([head, tail])))
+                        }
+                    });
+                    canonical.insert(Rc::as_ptr(noun), interned.clone());
+                }
+            }
+        }
+        canonical.remove(&Rc::as_ptr(noun)).expect("interned noun")
+    }
+}
+
+/// A canonical atom in a [`NounInterner`], which hashes and compares as the atom it holds so that
+/// it can be looked up by [`Atom`] without storing the atom's bytes a second time.
+#[derive(Debug)]
+struct AtomKey(Rc<Noun>);
+
+impl AtomKey {
+    fn atom(&self) -> &Atom {
+        match &*self.0 {
+            Noun::Atom(atom) => atom,
+            Noun::Cell(_) => unreachable!("interned atoms are atoms"),
+        }
+    }
+}
+
+impl Borrow<Atom> for AtomKey {
+    fn borrow(&self) -> &Atom {
+        self.atom()
+    }
+}
+
+impl Hash for AtomKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(self.atom(), state);
+    }
+}
+
+impl PartialEq for AtomKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.atom() == other.atom()
+    }
+}
+
+impl Eq for AtomKey {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serdes::{Cue, Jam};
+
+    #[test]
+    fn intern() {
+        let mut interner = NounInterner::new();
+        assert!(interner.is_empty());
+
+        let atom = Rc::<Noun>::from(Atom::from(7u8));
+        let interned = atom.intern(&mut interner);
+        assert!(Rc::ptr_eq(&interned, &atom));
+        // The interner holds the atom itself rather than a copy of its bytes.
+        assert_eq!(Rc::strong_count(&atom), 3);
+        let other = Rc::<Noun>::from(Atom::from(7u8));
+        assert!(Rc::ptr_eq(&other.intern(&mut interner), &atom));
+        assert_eq!(interner.len(), 1);
+
+        // [[1 2] [1 2] 7]
+        let noun = Rc::<Noun>::from(Cell::from([
+            Noun::from(Cell::from([1u8, 2u8])),
+            Noun::from(Cell::from([1u8, 2u8])),
+            Noun::from(Atom::from(7u8)),
+        ]));
+        let interned = noun.intern(&mut interner);
+        assert_eq!(interned, noun);
+        let [first, second, third] = match &*interned {
+            Noun::Cell(cell) => cell.to_array::<3>().expect("triple"),
+            Noun::Atom(_) => panic!("expected a cell"),
+        };
+        assert!(Rc::ptr_eq(&first, &second));
+        assert!(Rc::ptr_eq(&third, &atom));
+        // 1, 2, 7, [1 2], [[1 2] 7], and the noun itself.
+        assert_eq!(interner.len(), 6);
+
+        assert!(Rc::ptr_eq(&interned.intern(&mut interner), &interned));
+        let copy = Rc::<Noun>::cue(noun.clone().jam()).expect("cue");
+        assert!(Rc::ptr_eq(&copy.intern(&mut interner), &interned));
+        assert_eq!(interner.len(), 6);
+
+        interner.clear();
+        assert!(interner.is_empty());
+        assert!(!Rc::ptr_eq(&copy.intern(&mut interner), &interned));
+    }
+
+    #[test]
+    fn intern_deep() {
+        // A long list whose elements are all equal but separately allocated.
+        let mut list = Rc::new(Noun::null());
+        for _ in 0..100_000 {
+            let elem = Noun::from(Cell::from([1u8, 2u8]));
+            list = Rc::new(Noun::from(Cell::from([Rc::new(elem), list])));
+        }
+        let mut interner = NounInterner::new();
+        let interned = list.intern(&mut interner);
+        assert_eq!(interned, list);
+        // 0, 1, 2, [1 2], and one cell per element.
+        assert_eq!(interner.len(), 100_004);
+    }
+}
@@ -16,17 +16,37 @@ pub mod atom;
 #[doc(hidden)]
 pub mod cell;
 pub mod convert;
+pub mod intern;
+pub mod map;
 pub mod marker;
+pub mod mop;
+mod mug;
+pub mod newt;
+pub mod nock;
 #[doc(hidden)]
 pub mod noun;
+pub mod ob;
+pub mod parse;
+pub mod print;
+pub mod qeu;
+#[cfg(feature = "serde")]
+pub mod serde;
 pub mod serdes;
+pub mod serf;
+pub mod set;
+pub mod time;
+mod treap;
 
 #[doc(inline)]
 pub use crate::atom::{Atom, Builder as AtomBuilder, Iter as AtomIter};
 #[doc(inline)]
 pub use crate::cell::*;
 #[doc(inline)]
+pub use crate::nock::Error as NockError;
+#[doc(inline)]
 pub use crate::noun::*;
+#[cfg(feature = "serde")]
+pub use crate::serde::{from_noun, to_noun};
 
 /// A reference-counting pointer.
 ///
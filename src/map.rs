@@ -0,0 +1,325 @@
+//! Hoon maps: treaps of key-value pairs.
+
+use crate::{
+    cell::Cell,
+    convert::{self, FromNoun, ToNoun},
+    noun::Noun,
+    treap::{self, Order},
+    Rc,
+};
+use std::{
+    fmt::{self, Debug, Formatter},
+    iter::FusedIterator,
+};
+
+/// A map from nouns to nouns with exactly the structure of a Hoon `map`.
+///
+/// A map is a treap of `[key value]` nodes ordered by `gor` and balanced by `mor`, so its noun is
+/// identical to the one that Hoon's `++by` would build from the same entries, no matter the order
+/// in which they were added. Maps are persistent: cloning one is cheap, and a changed map shares
+/// the parts of its noun that didn't change.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, map::HoonMap, Noun};
+/// let mut map = HoonMap::new();
+/// map.put(Atom::from("zod"), Atom::from(0u8));
+/// map.put(Atom::from("nec"), Atom::from(1u8));
+/// assert_eq!(map.get(&Noun::from(Atom::from("nec"))), Some(&Noun::from(Atom::from(1u8))));
+///
+/// let other: HoonMap = [(Atom::from("nec"), Atom::from(1u8)), (Atom::from("zod"), Atom::from(0u8))]
+///     .into_iter()
+///     .collect();
+/// assert_eq!(Noun::from(map), Noun::from(other));
+/// ```
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct HoonMap {
+    tree: Rc<Noun>,
+}
+
+impl HoonMap {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self {
+            tree: Rc::new(Noun::null()),
+        }
+    }
+
+    /// Returns whether this map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_null()
+    }
+
+    /// Returns the value of `key`, or `None` if this map doesn't have `key`.
+    pub fn get(&self, key: &Noun) -> Option<&Noun> {
+        match treap::get(&self.tree, key, Order::MAP)? {
+            Noun::Cell(node) => Some(node.tail_ref()),
+            Noun::Atom(_) => None,
+        }
+    }
+
+    /// Returns whether this map has `key`.
+    pub fn has(&self, key: &Noun) -> bool {
+        treap::get(&self.tree, key, Order::MAP).is_some()
+    }
+
+    /// Sets the value of `key` to `value`, replacing the previous value of `key` if there was one.
+    pub fn put(&mut self, key: impl Into<Rc<Noun>>, value: impl Into<Rc<Noun>>) {
+        let node = Rc::<Noun>::from(Cell::from([key.into(), value.into()]));
+        self.tree = treap::put(&self.tree, node, Order::MAP);
+    }
+
+    /// Removes `key` and its value from this map if this map has `key`.
+    pub fn del(&mut self, key: &Noun) {
+        self.tree = treap::del(&self.tree, key, Order::MAP);
+    }
+
+    /// Returns an iterator over the entries of this map in the order Hoon's `tap` lists them.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            nodes: treap::Nodes::new(&self.tree),
+        }
+    }
+
+    /// Returns the map of the entries of this map and `other`, taking the value from `other` when
+    /// both have a key.
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            tree: treap::uni(&self.tree, &other.tree, Order::MAP),
+        }
+    }
+
+    /// Returns the map of the entries of this map whose keys `other` doesn't have.
+    pub fn diff(&self, other: &Self) -> Self {
+        Self {
+            tree: treap::dif(&self.tree, &other.tree, Order::MAP),
+        }
+    }
+
+    /// Returns the noun of this map.
+    pub fn as_noun(&self) -> &Rc<Noun> {
+        &self.tree
+    }
+}
+
+impl Debug for HoonMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl Default for HoonMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Into<Rc<Noun>>, V: Into<Rc<Noun>>> Extend<(K, V)> for HoonMap {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.put(key, value);
+        }
+    }
+}
+
+impl From<HoonMap> for Noun {
+    fn from(map: HoonMap) -> Self {
+        Rc::try_unwrap(map.tree).unwrap_or_else(|tree| (*tree).clone())
+    }
+}
+
+impl From<HoonMap> for Rc<Noun> {
+    fn from(map: HoonMap) -> Self {
+        map.tree
+    }
+}
+
+impl<K: Into<Rc<Noun>>, V: Into<Rc<Noun>>> FromIterator<(K, V)> for HoonMap {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a> IntoIterator for &'a HoonMap {
+    type Item = (&'a Noun, &'a Noun);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl TryFrom<Rc<Noun>> for HoonMap {
+    type Error = convert::Error;
+
+    /// Converts a noun into a map, failing with [`convert::Error::InvalidTreap`] if the noun isn't
+    /// a well-formed treap of `[key value]` cells.
+    fn try_from(tree: Rc<Noun>) -> Result<Self, Self::Error> {
+        let nodes_are_cells = || treap::Nodes::new(&tree).all(|node| matches!(node, Noun::Cell(_)));
+        if treap::apt(&tree, Order::MAP) && nodes_are_cells() {
+            Ok(Self { tree })
+        } else {
+            Err(convert::Error::InvalidTreap)
+        }
+    }
+}
+
+impl TryFrom<&Noun> for HoonMap {
+    type Error = convert::Error;
+
+    fn try_from(noun: &Noun) -> Result<Self, Self::Error> {
+        Self::try_from(Rc::new(noun.clone()))
+    }
+}
+
+impl ToNoun for HoonMap {
+    fn to_noun(&self) -> Noun {
+        (*self.tree).clone()
+    }
+}
+
+impl FromNoun for HoonMap {
+    fn from_noun(noun: &Noun) -> Result<Self, convert::Error> {
+        Self::try_from(noun)
+    }
+}
+
+/// An iterator over the entries of a [`HoonMap`], created by [`HoonMap::iter`].
+#[derive(Clone)]
+pub struct Iter<'a> {
+    nodes: treap::Nodes<'a>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a Noun, &'a Noun);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.nodes.next()? {
+            Noun::Cell(node) => Some((node.head_ref(), node.tail_ref())),
+            Noun::Atom(_) => None,
+        }
+    }
+}
+
+impl FusedIterator for Iter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{atom::Atom, noun};
+
+    /// Returns the atom `n` as a noun.
+    fn atom(n: u32) -> Noun {
+        Noun::from(Atom::from(n))
+    }
+
+    /// Returns a map of `n` to `n * n` for every `n` in `keys`.
+    fn squares(keys: impl IntoIterator<Item = u32>) -> HoonMap {
+        treap::tests::squares::<Atom, _>(keys)
+    }
+
+    #[test]
+    fn put_get_del() {
+        let mut map = HoonMap::new();
+        assert!(map.is_empty());
+        assert_eq!(Noun::from(map.clone()), noun!(~));
+
+        map.put(atom(1), atom(2));
+        assert_eq!(Noun::from(map.clone()), noun!([[1 2] ~ ~]));
+        map.put(atom(1), atom(3));
+        assert_eq!(Noun::from(map.clone()), noun!([[1 3] ~ ~]));
+
+        let mut map = squares(0..100);
+        assert!(HoonMap::try_from(map.as_noun().clone()).is_ok());
+        for n in 0..100 {
+            assert_eq!(map.get(&atom(n)), Some(&atom(n * n)));
+            assert!(map.has(&atom(n)));
+        }
+        assert_eq!(map.get(&atom(100)), None);
+        assert!(!map.has(&atom(100)));
+        assert_eq!(map, squares((0..100).rev()));
+        assert_eq!(map.iter().count(), 100);
+
+        let before = map.clone();
+        map.put(atom(5), atom(25));
+        assert!(Rc::ptr_eq(map.as_noun(), before.as_noun()));
+        map.del(&atom(100));
+        assert!(Rc::ptr_eq(map.as_noun(), before.as_noun()));
+
+        for n in (0..100).step_by(2) {
+            map.del(&atom(n));
+        }
+        assert_eq!(map, squares((1..100).step_by(2)));
+        assert!(HoonMap::try_from(map.as_noun().clone()).is_ok());
+        for n in (1..100).step_by(2) {
+            map.del(&atom(n));
+        }
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn union_diff() {
+        let evens = squares((0..60).step_by(2));
+        let threes = squares((0..60).step_by(3));
+        let union = evens.union(&threes);
+        assert_eq!(union, squares((0..60).filter(|n| n % 2 == 0 || n % 3 == 0)));
+        assert_eq!(union, threes.union(&evens));
+        assert!(HoonMap::try_from(union.as_noun().clone()).is_ok());
+
+        let diff = evens.diff(&threes);
+        assert_eq!(diff, squares((0..60).filter(|n| n % 2 == 0 && n % 3 != 0)));
+        assert!(HoonMap::try_from(diff.as_noun().clone()).is_ok());
+        assert_eq!(evens.diff(&evens), HoonMap::new());
+        assert_eq!(evens.diff(&HoonMap::new()), evens);
+
+        let mut ones = HoonMap::new();
+        ones.put(atom(2), atom(1));
+        ones.put(atom(3), atom(1));
+        let union = evens.union(&ones);
+        assert_eq!(union.get(&atom(2)), Some(&atom(1)));
+        assert_eq!(union.get(&atom(3)), Some(&atom(1)));
+        assert_eq!(union.get(&atom(4)), Some(&atom(16)));
+        let union = ones.union(&evens);
+        assert_eq!(union.get(&atom(2)), Some(&atom(4)));
+    }
+
+    #[test]
+    fn hoon() {
+        // (jam (malt (turn (gulf 1 8) |=(n=@ [n (mul n n)]))))
+        let noun = treap::tests::cue(
+            "0xb7.f1c9.0d0b.bf8f.3159.890a.c184.c2d4.e585.c2b8.d0f8.5981.c208.2b25.0d85",
+        );
+        assert_eq!(
+            noun,
+            noun!([[6 36] [[8 64] ~ [7 49] [[5 25] ~ ~] ~] [4 16] [[2 4] [[1 1] ~ ~] [3 9] ~ ~] ~])
+        );
+        for keys in [(1..9).collect::<Vec<_>>(), (1..9).rev().collect()] {
+            assert_eq!(Noun::from(squares(keys)), noun);
+        }
+        assert_eq!(HoonMap::try_from(&noun).expect("map"), squares(1..9));
+    }
+
+    #[test]
+    fn try_from() {
+        assert!(HoonMap::try_from(&noun!([[1 2] ~ ~])).is_ok());
+        treap::tests::check_try_from(
+            squares(0..10),
+            [
+                noun!(1),
+                noun!([[1 2] ~]),
+                noun!([1 ~ ~]),
+                noun!([[1 2] [[1 3] ~ ~] ~]),
+            ],
+        );
+        let map = squares(0..2);
+        let Noun::Cell(tree) = &**map.as_noun() else {
+            panic!("expected a cell");
+        };
+        let [n, l, r] = tree.to_array::<3>().expect("treap");
+        let swapped = Noun::from(Cell::from([n, r, l]));
+        assert!(HoonMap::try_from(&swapped).is_err());
+    }
+}
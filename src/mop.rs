@@ -0,0 +1,458 @@
+//! Hoon ordered maps (`mop`s): treaps of key-value pairs ordered by a comparator.
+
+use crate::{
+    cell::Cell,
+    convert::{self, FromNoun, ToNoun},
+    noun::Noun,
+    treap::{self, map_key, Order},
+    Rc,
+};
+use std::{
+    fmt::{self, Debug, Formatter},
+    iter::FusedIterator,
+    marker::PhantomData,
+};
+
+/// A comparator that orders the keys of a [`Mop`], like the gate passed to Hoon's `++on`.
+pub trait Comparator {
+    /// Returns whether key `a` comes strictly before key `b`.
+    fn before(a: &Noun, b: &Noun) -> bool;
+}
+
+/// Orders keys from least to greatest, like Hoon's `lth`.
+///
+/// `lth` only compares atoms, so cells, which it can't compare, come after atoms, in the order of
+/// [`Noun`]'s [`Ord`] implementation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Lth;
+
+impl Comparator for Lth {
+    fn before(a: &Noun, b: &Noun) -> bool {
+        a < b
+    }
+}
+
+/// Orders keys from greatest to least, like Hoon's `gth`.
+///
+/// `gth` only compares atoms, so cells, which it can't compare, come before atoms, in the reverse
+/// order of [`Noun`]'s [`Ord`] implementation.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Gth;
+
+impl Comparator for Gth {
+    fn before(a: &Noun, b: &Noun) -> bool {
+        a > b
+    }
+}
+
+/// An ordered map with exactly the structure of a Hoon `mop`, built by `((on K V) C)`.
+///
+/// A mop is a treap of `[key value]` nodes ordered by the comparator `C` and balanced by `mor`.
+/// Unlike a [`HoonMap`](crate::map::HoonMap), its entries are listed in key order. Keys and
+/// values are converted to and from nouns with [`ToNoun`] and [`FromNoun`].
+///
+/// # Examples
+///
+/// ```
+/// # use noun::mop::{Gth, Mop};
+/// let mut mop: Mop<u64, String> = Mop::new();
+/// mop.put(3, String::from("c"));
+/// mop.put(1, String::from("a"));
+/// mop.put(2, String::from("b"));
+/// assert_eq!(mop.get(&2), Some(String::from("b")));
+/// assert_eq!(mop.tap(), [(1, String::from("a")), (2, String::from("b")), (3, String::from("c"))]);
+/// assert_eq!(mop.lot(Some(&1), None).tap(), [(2, String::from("b")), (3, String::from("c"))]);
+///
+/// let descending: Mop<u64, String, Gth> = mop.iter().collect();
+/// assert_eq!(descending.iter().next(), Some((3, String::from("c"))));
+/// ```
+pub struct Mop<K, V, C = Lth> {
+    tree: Rc<Noun>,
+    _marker: PhantomData<fn() -> (K, V)>,
+    _comparator: PhantomData<fn() -> C>,
+}
+
+impl<K, V, C> Mop<K, V, C>
+where
+    K: FromNoun + ToNoun,
+    V: FromNoun + ToNoun,
+    C: Comparator,
+{
+    /// The order of this mop's nodes.
+    const ORDER: Order = Order::new(map_key, C::before);
+
+    /// Creates an empty mop.
+    pub fn new() -> Self {
+        Self::from_tree(Rc::new(Noun::null()))
+    }
+
+    fn from_tree(tree: Rc<Noun>) -> Self {
+        Self {
+            tree,
+            _marker: PhantomData,
+            _comparator: PhantomData,
+        }
+    }
+
+    /// Returns whether this mop has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_null()
+    }
+
+    /// Returns the value of `key`, or `None` if this mop doesn't have `key`.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let node = treap::get(&self.tree, &key.to_noun(), Self::ORDER)?;
+        Some(entry::<K, V>(node).1)
+    }
+
+    /// Returns whether this mop has `key`.
+    pub fn has(&self, key: &K) -> bool {
+        treap::get(&self.tree, &key.to_noun(), Self::ORDER).is_some()
+    }
+
+    /// Sets the value of `key` to `value`, replacing the previous value of `key` if there was one.
+    pub fn put(&mut self, key: K, value: V) {
+        let node = Rc::<Noun>::from(Cell::from([key.to_noun(), value.to_noun()]));
+        self.tree = treap::put(&self.tree, node, Self::ORDER);
+    }
+
+    /// Removes `key` from this mop, returning its value, or `None` if this mop doesn't have `key`.
+    pub fn del(&mut self, key: &K) -> Option<V> {
+        let key = key.to_noun();
+        let (_, value) = entry::<K, V>(treap::get(&self.tree, &key, Self::ORDER)?);
+        self.tree = treap::del(&self.tree, &key, Self::ORDER);
+        Some(value)
+    }
+
+    /// Removes the first entry from this mop, returning it, or `None` if this mop is empty.
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        let (first, tree) = treap::pop(&self.tree)?;
+        self.tree = tree;
+        Some(entry(&first))
+    }
+
+    /// Returns the entries of this mop in key order.
+    pub fn tap(&self) -> Vec<(K, V)> {
+        self.iter().collect()
+    }
+
+    /// Returns the mop of the entries of this mop whose keys come strictly after `start` and
+    /// strictly before `end`, where `None` leaves a side unbounded.
+    ///
+    /// If `start` doesn't come before `end`, the mop is empty.
+    pub fn lot(&self, start: Option<&K>, end: Option<&K>) -> Self {
+        let (start, end) = (start.map(K::to_noun), end.map(K::to_noun));
+        if let (Some(start), Some(end)) = (&start, &end) {
+            if !C::before(start, end) {
+                return Self::new();
+            }
+        }
+        Self::from_tree(treap::lot(
+            &self.tree,
+            start.as_ref(),
+            end.as_ref(),
+            Self::ORDER,
+        ))
+    }
+
+    /// Returns an iterator over the entries of this mop in key order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            nodes: treap::Nodes::in_order(&self.tree),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the noun of this mop.
+    pub fn as_noun(&self) -> &Rc<Noun> {
+        &self.tree
+    }
+}
+
+/// Converts a mop's `[key value]` node into its key and value.
+///
+/// # Panics
+///
+/// Panics if the key or value doesn't convert. A mop's entries always do: [`Mop::put`] builds them
+/// from a `K` and a `V`, and converting a noun into a mop converts every entry first.
+fn entry<K: FromNoun, V: FromNoun>(node: &Noun) -> (K, V) {
+    <(K, V)>::from_noun(node).expect("mop entry")
+}
+
+impl<K, V, C> Clone for Mop<K, V, C> {
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree.clone(),
+            _marker: PhantomData,
+            _comparator: PhantomData,
+        }
+    }
+}
+
+impl<K, V, C> Debug for Mop<K, V, C>
+where
+    K: Debug + FromNoun + ToNoun,
+    V: Debug + FromNoun + ToNoun,
+    C: Comparator,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, C> Default for Mop<K, V, C>
+where
+    K: FromNoun + ToNoun,
+    V: FromNoun + ToNoun,
+    C: Comparator,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, C> PartialEq for Mop<K, V, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.tree == other.tree
+    }
+}
+
+impl<K, V, C> Eq for Mop<K, V, C> {}
+
+impl<K, V, C> Extend<(K, V)> for Mop<K, V, C>
+where
+    K: FromNoun + ToNoun,
+    V: FromNoun + ToNoun,
+    C: Comparator,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.put(key, value);
+        }
+    }
+}
+
+impl<K, V, C> From<Mop<K, V, C>> for Noun {
+    fn from(mop: Mop<K, V, C>) -> Self {
+        Rc::try_unwrap(mop.tree).unwrap_or_else(|tree| (*tree).clone())
+    }
+}
+
+impl<K, V, C> From<Mop<K, V, C>> for Rc<Noun> {
+    fn from(mop: Mop<K, V, C>) -> Self {
+        mop.tree
+    }
+}
+
+impl<K, V, C> FromIterator<(K, V)> for Mop<K, V, C>
+where
+    K: FromNoun + ToNoun,
+    V: FromNoun + ToNoun,
+    C: Comparator,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut mop = Self::new();
+        mop.extend(iter);
+        mop
+    }
+}
+
+impl<'a, K, V, C> IntoIterator for &'a Mop<K, V, C>
+where
+    K: FromNoun + ToNoun,
+    V: FromNoun + ToNoun,
+    C: Comparator,
+{
+    type Item = (K, V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<K, V, C> TryFrom<Rc<Noun>> for Mop<K, V, C>
+where
+    K: FromNoun + ToNoun,
+    V: FromNoun + ToNoun,
+    C: Comparator,
+{
+    type Error = convert::Error;
+
+    /// Converts a noun into a mop, failing with [`convert::Error::InvalidTreap`] if the noun isn't
+    /// a well-formed treap ordered by `C`, or with the error of the key or value that doesn't
+    /// convert.
+    fn try_from(tree: Rc<Noun>) -> Result<Self, Self::Error> {
+        if !treap::apt(&tree, Self::ORDER) {
+            return Err(convert::Error::InvalidTreap);
+        }
+        for node in treap::Nodes::in_order(&tree) {
+            <(K, V)>::from_noun(node)?;
+        }
+        Ok(Self::from_tree(tree))
+    }
+}
+
+impl<K, V, C> TryFrom<&Noun> for Mop<K, V, C>
+where
+    K: FromNoun + ToNoun,
+    V: FromNoun + ToNoun,
+    C: Comparator,
+{
+    type Error = convert::Error;
+
+    fn try_from(noun: &Noun) -> Result<Self, Self::Error> {
+        Self::try_from(Rc::new(noun.clone()))
+    }
+}
+
+impl<K, V, C> ToNoun for Mop<K, V, C> {
+    fn to_noun(&self) -> Noun {
+        (*self.tree).clone()
+    }
+}
+
+impl<K, V, C> FromNoun for Mop<K, V, C>
+where
+    K: FromNoun + ToNoun,
+    V: FromNoun + ToNoun,
+    C: Comparator,
+{
+    fn from_noun(noun: &Noun) -> Result<Self, convert::Error> {
+        Self::try_from(noun)
+    }
+}
+
+/// An iterator over the entries of a [`Mop`] in key order, created by [`Mop::iter`].
+pub struct Iter<'a, K, V> {
+    nodes: treap::Nodes<'a>,
+    _marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K: FromNoun, V: FromNoun> Iterator for Iter<'_, K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes.next().map(entry)
+    }
+}
+
+impl<K: FromNoun, V: FromNoun> FusedIterator for Iter<'_, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{noun, treap::tests::squares};
+
+    #[test]
+    fn put_get_del() {
+        let mut mop: Mop<u32, u32> = Mop::new();
+        assert!(mop.is_empty());
+        mop.put(1, 2);
+        assert_eq!(Noun::from(mop.clone()), noun!([[1 2] ~ ~]));
+
+        let mut mop: Mop<u32, u32> = squares((0..100).rev());
+        assert_eq!(mop, squares(0..100));
+        assert!(Mop::<u32, u32>::try_from(mop.as_noun().clone()).is_ok());
+        for n in 0..100 {
+            assert_eq!(mop.get(&n), Some(n * n));
+            assert!(mop.has(&n));
+        }
+        assert_eq!(mop.get(&100), None);
+        assert_eq!(mop.tap(), (0..100).map(|n| (n, n * n)).collect::<Vec<_>>());
+
+        for n in (0..100).step_by(2) {
+            assert_eq!(mop.del(&n), Some(n * n));
+        }
+        assert_eq!(mop.del(&0), None);
+        assert_eq!(mop, squares((1..100).step_by(2)));
+        assert!(Mop::<u32, u32>::try_from(mop.as_noun().clone()).is_ok());
+    }
+
+    #[test]
+    fn order() {
+        let lth: Mop<u32, u32, Lth> = squares(0..50);
+        let gth: Mop<u32, u32, Gth> = squares(0..50);
+        let keys = |iter: &mut dyn Iterator<Item = (u32, u32)>| {
+            iter.map(|(key, _)| key).collect::<Vec<_>>()
+        };
+        assert_eq!(keys(&mut lth.iter()), (0..50).collect::<Vec<_>>());
+        assert_eq!(keys(&mut gth.iter()), (0..50).rev().collect::<Vec<_>>());
+        assert!(Mop::<u32, u32, Lth>::try_from(gth.as_noun().clone()).is_err());
+        assert!(Mop::<u32, u32, Gth>::try_from(lth.as_noun().clone()).is_err());
+
+        let mut mop = gth.clone();
+        for n in (0..50).rev() {
+            assert_eq!(mop.pop(), Some((n, n * n)));
+            assert!(Mop::<u32, u32, Gth>::try_from(mop.as_noun().clone()).is_ok());
+        }
+        assert_eq!(mop.pop(), None);
+    }
+
+    #[test]
+    fn lot() {
+        let mop: Mop<u32, u32> = squares(0..50);
+        let keys = |mop: Mop<u32, u32>| mop.iter().map(|(key, _)| key).collect::<Vec<_>>();
+        assert_eq!(mop.lot(None, None), mop);
+        assert_eq!(
+            keys(mop.lot(Some(&10), Some(&20))),
+            (11..20).collect::<Vec<_>>()
+        );
+        assert_eq!(keys(mop.lot(Some(&45), None)), (46..50).collect::<Vec<_>>());
+        assert_eq!(keys(mop.lot(None, Some(&3))), (0..3).collect::<Vec<_>>());
+        assert_eq!(keys(mop.lot(Some(&100), None)), []);
+        assert_eq!(keys(mop.lot(Some(&20), Some(&10))), []);
+        for (start, end) in [(Some(5), Some(30)), (Some(0), None), (None, Some(49))] {
+            let lot = mop.lot(start.as_ref(), end.as_ref());
+            assert!(Mop::<u32, u32>::try_from(lot.as_noun().clone()).is_ok());
+        }
+
+        let gth: Mop<u32, u32, Gth> = squares(0..50);
+        let lot = gth.lot(Some(&20), Some(&10));
+        assert_eq!(
+            lot.iter().map(|(key, _)| key).collect::<Vec<_>>(),
+            (11..20).rev().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn hoon() {
+        // (jam (gas:((on @ud @) lth) ~ (turn (gulf 1 8) |=(n=@ [n (mul n n)]))))
+        let noun = treap::tests::cue(
+            "0xb8.71e3.43e1.581c.2082.d70e.3921.a169.cc56.6242.b061.30ae.585c.2b25.0d85",
+        );
+        assert_eq!(
+            noun,
+            noun!([[6 36] [[5 25] [[4 16] [[2 4] [[1 1] ~ ~] [3 9] ~ ~] ~] ~] [8 64] [[7 49] ~ ~] ~])
+        );
+        for keys in [(1..9).collect::<Vec<_>>(), (1..9).rev().collect()] {
+            assert_eq!(Noun::from(squares::<u32, Mop<u32, u32>>(keys)), noun);
+        }
+        assert_eq!(
+            Mop::<u32, u32>::try_from(&noun).expect("mop").tap(),
+            (1..9).map(|n| (n, n * n)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn try_from() {
+        treap::tests::check_try_from::<Mop<u32, u32>>(
+            squares(0..10),
+            [noun!(1), noun!([[1 2] ~]), noun!([[1 2] [[3 4] ~ ~] ~])],
+        );
+        assert!(matches!(
+            Mop::<u32, String>::try_from(&noun!([[1 [2 3]] ~ ~])),
+            Err(convert::Error::Field { .. })
+        ));
+        assert!(Mop::<u32, u32>::try_from(&noun!([1 ~ ~])).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+//! Urbit's 31-bit noun hash.
+//!
+//! A [mug] is computed with 32-bit [MurmurHash3] (`x86_32` variant). An atom is hashed over its
+//! little-endian bytes with the seed `0xcafebabe`. A cell is hashed over the little-endian bytes of
+//! the mug of its head followed by the mug of its tail (`(cat 5 head-mug tail-mug)`) with the seed
+//! `0xdeadbeef`. In both cases, the high bit of the 32-bit hash is folded into the low 31 bits, and
+//! if the result is `0`, the seed is incremented and the hash retried, up to eight times, after
+//! which a fixed fallback is used.
+//!
+//! [mug]: https://developers.urbit.org/reference/hoon/stdlib/2e#mug
+//! [MurmurHash3]: https://github.com/aappleby/smhasher/blob/master/src/MurmurHash3.cpp
+
+/// Seed used to mug an atom.
+const ATOM_SEED: u32 = 0xcafe_babe;
+
+/// Mug of an atom if every retry hashes to `0`.
+const ATOM_FALLBACK: u32 = 0x7fff;
+
+/// Seed used to mug a cell.
+const CELL_SEED: u32 = 0xdead_beef;
+
+/// Mug of a cell if every retry hashes to `0`.
+const CELL_FALLBACK: u32 = 0xfffe;
+
+/// Maximum number of times a hash is retried with a new seed before falling back.
+const MAX_TRIES: u32 = 8;
+
+/// Computes 32-bit MurmurHash3 (`x86_32` variant) of `bytes` with `seed`.
+pub(crate) fn murmur3(bytes: &[u8], seed: u32) -> u32 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+
+    let mut hash = seed;
+    let mut blocks = bytes.chunks_exact(4);
+    for block in &mut blocks {
+        let mut k = u32::from_le_bytes([block[0], block[1], block[2], block[3]]);
+        k = k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+        hash ^= k;
+        hash = hash
+            .rotate_left(13)
+            .wrapping_mul(5)
+            .wrapping_add(0xe654_6b64);
+    }
+
+    let tail = blocks.remainder();
+    if !tail.is_empty() {
+        let mut k = 0u32;
+        for (i, byte) in tail.iter().enumerate() {
+            k |= u32::from(*byte) << (8 * i);
+        }
+        k = k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+        hash ^= k;
+    }
+
+    // The length is mixed in modulo 2^32, as in the reference implementation.
+    hash ^= bytes.len() as u32;
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^= hash >> 16;
+    hash
+}
+
+/// Hashes `bytes` into a nonzero 31-bit value, retrying with successive seeds (`++mum`).
+fn mum(bytes: &[u8], mut seed: u32, fallback: u32) -> u32 {
+    for _ in 0..MAX_TRIES {
+        let hash = murmur3(bytes, seed);
+        let mug = (hash >> 31) ^ (hash & 0x7fff_ffff);
+        if mug != 0 {
+            return mug;
+        }
+        seed = seed.wrapping_add(1);
+    }
+    fallback
+}
+
+/// Computes the mug of an atom from its little-endian bytes, which must not have trailing zeros.
+pub(crate) fn mug_bytes(bytes: &[u8]) -> u32 {
+    mum(bytes, ATOM_SEED, ATOM_FALLBACK)
+}
+
+/// Computes the mug of a cell from the mugs of its head and tail.
+pub(crate) fn mug_both(head: u32, tail: u32) -> u32 {
+    let mut bytes = [0u8; 8];
+    bytes[..4].copy_from_slice(&head.to_le_bytes());
+    bytes[4..].copy_from_slice(&tail.to_le_bytes());
+    // A mug is never `0`, so the tail always has at least one significant byte.
+    let len = 4 + (u32::BITS - tail.leading_zeros()).div_ceil(u8::BITS);
+    mum(&bytes[..len as usize], CELL_SEED, CELL_FALLBACK)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn murmur3() {
+        // Reference values from the SMHasher test suite.
+        assert_eq!(super::murmur3(b"", 0), 0);
+        assert_eq!(super::murmur3(b"", 1), 0x514e_28b7);
+        assert_eq!(super::murmur3(b"", 0xffff_ffff), 0x81f1_6f39);
+        assert_eq!(super::murmur3(&[0, 0, 0, 0], 0), 0x2362_f9de);
+        assert_eq!(super::murmur3(b"aaaa", 0x9747_b28c), 0x5a97_808a);
+        assert_eq!(super::murmur3(b"abc", 0), 0xb3dd_93fa);
+        assert_eq!(super::murmur3(b"Hello, world!", 0x9747_b28c), 0x2488_4cba);
+    }
+
+    #[test]
+    fn mug_both() {
+        // The tail mug occupies only as many bytes as it needs.
+        assert_ne!(super::mug_both(1, 1), super::mug_both(1, 0x100));
+        assert_ne!(super::mug_both(1, 2), super::mug_both(2, 1));
+    }
+}
@@ -0,0 +1,328 @@
+//! [Newt] framing of jammed nouns over byte streams.
+//!
+//! The Urbit runtime exchanges nouns with its worker ("serf") process over pipes. Each message is
+//! a frame consisting of a version byte (currently always `0`), the length of the body as a 4-byte
+//! little-endian integer, and the body: the little-endian bytes of a jammed noun.
+//!
+//! ```text
+//!  version     length       body
+//! |_______|  |_________|  |______ ... ______|
+//!  1 byte      4 bytes      `length` bytes
+//! ```
+//!
+//! [`NewtWriter`] writes nouns as frames to an [`io::Write`], and [`NewtReader`] reads frames from
+//! an [`io::Read`] back into nouns. Both block on the underlying stream.
+//!
+//! # Examples
+//!
+//! ```
+//! # use noun::{cell::Cell, newt::{NewtReader, NewtWriter}, Noun};
+//! let noun = Noun::from(Cell::from(["live", "exit"]));
+//!
+//! let mut writer = NewtWriter::new(Vec::new());
+//! writer.write(&noun).unwrap();
+//! let bytes = writer.into_inner();
+//!
+//! let mut reader = NewtReader::new(&bytes[..]);
+//! assert_eq!(reader.read().unwrap(), Some(noun));
+//! assert_eq!(reader.read().unwrap(), None);
+//! ```
+//!
+//! [Newt]: https://github.com/urbit/vere/blob/develop/pkg/vere/newt.c
+
+use crate::{
+    atom::Atom,
+    noun::Noun,
+    serdes::{self, CueLimits, Jam},
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    io::{self, Read, Write},
+    result,
+};
+
+/// Version byte at the start of every frame.
+pub const VERSION: u8 = 0;
+
+/// Length in bytes of a frame's header.
+pub const HEADER_LEN: usize = 5;
+
+/// Default maximum length in bytes of a frame's body that a [`NewtReader`] accepts.
+pub const DEFAULT_MAX_LEN: usize = 1 << 30;
+
+/// Errors that occur when reading or writing frames.
+#[derive(Debug)]
+pub enum Error {
+    /// Reading from or writing to the underlying stream failed.
+    Io(io::Error),
+    /// A frame's body was longer than `max` bytes.
+    Oversized { len: u64, max: u64 },
+    /// The stream ended partway through a frame, after `read` of the `expected` bytes of its
+    /// header, or of its header and body if the header was complete.
+    Truncated { expected: u64, read: u64 },
+    /// A frame's header began with a version other than [`VERSION`].
+    UnsupportedVersion(u8),
+    /// A frame's body wasn't a valid jammed noun.
+    Cue(serdes::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
+        match self {
+            Self::Io(err) => write!(f, "failed to read or write a frame: {}", err),
+            Self::Oversized { len, max } => write!(
+                f,
+                "the frame's body is {} bytes long, which exceeds the maximum of {} bytes",
+                len, max
+            ),
+            Self::Truncated { expected, read } => write!(
+                f,
+                "the stream ended after {} of the frame's {} bytes",
+                read, expected
+            ),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "the frame has unsupported version {}", version)
+            }
+            Self::Cue(err) => write!(f, "the frame's body is not a jammed noun: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Cue(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// A specialized [`Result`] type for newt operations that return [`newt::Error`] on error.
+///
+/// [`newt::Error`]: [`Error`]
+pub type Result<T> = result::Result<T, Error>;
+
+/// Reads nouns from newt frames.
+pub struct NewtReader<R> {
+    reader: R,
+    max_len: usize,
+}
+
+impl<R: Read> NewtReader<R> {
+    /// Creates a reader that accepts frames with bodies of up to [`DEFAULT_MAX_LEN`] bytes.
+    pub fn new(reader: R) -> Self {
+        Self::with_max_len(reader, DEFAULT_MAX_LEN)
+    }
+
+    /// Creates a reader that accepts frames with bodies of up to `max_len` bytes.
+    pub fn with_max_len(reader: R, max_len: usize) -> Self {
+        Self { reader, max_len }
+    }
+
+    /// Reads the next frame and cues its body, returning `None` if the stream ends before the
+    /// start of a frame.
+    pub fn read(&mut self) -> Result<Option<Noun>> {
+        let mut header = [0; HEADER_LEN];
+        let read = read_full(&mut self.reader, &mut header)?;
+        if read == 0 {
+            return Ok(None);
+        }
+        if read < HEADER_LEN {
+            return Err(Error::Truncated {
+                expected: HEADER_LEN as u64,
+                read: read as u64,
+            });
+        }
+        if header[0] != VERSION {
+            return Err(Error::UnsupportedVersion(header[0]));
+        }
+
+        let len = u32::from_le_bytes([header[1], header[2], header[3], header[4]]);
+        if u64::from(len) > self.max_len as u64 {
+            return Err(Error::Oversized {
+                len: u64::from(len),
+                max: self.max_len as u64,
+            });
+        }
+        // The body is read incrementally rather than into a buffer of the claimed length, so a
+        // truncated frame can't cause a large allocation.
+        let mut body = Vec::new();
+        let read = (&mut self.reader)
+            .take(u64::from(len))
+            .read_to_end(&mut body)?;
+        if read < len as usize {
+            return Err(Error::Truncated {
+                expected: (HEADER_LEN as u64) + u64::from(len),
+                read: (HEADER_LEN + read) as u64,
+            });
+        }
+        // The other end of a newt stream is the runtime, which is trusted to send nouns larger than
+        // the default cue limits allow.
+        Noun::cue_with_limits(Atom::from(body), &CueLimits::unlimited())
+            .map(Some)
+            .map_err(Error::Cue)
+    }
+
+    /// Returns the underlying reader, consuming this reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read> Iterator for NewtReader<R> {
+    type Item = Result<Noun>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read().transpose()
+    }
+}
+
+/// Reads from `reader` until `buf` is full or the stream ends, returning the number of bytes read.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..]) {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(read)
+}
+
+/// Writes nouns as newt frames.
+pub struct NewtWriter<W> {
+    writer: W,
+}
+
+impl<W: Write> NewtWriter<W> {
+    /// Creates a writer.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Jams `noun` and writes it as a frame, flushing the underlying writer so that the frame is
+    /// delivered immediately.
+    ///
+    /// Nouns whose jammed bytes don't fit in a frame are rejected with [`Error::Oversized`] before
+    /// anything is written.
+    pub fn write(&mut self, noun: &Noun) -> Result<()> {
+        let jammed = noun.jam();
+        let body = jammed.as_bytes();
+        let len = u32::try_from(body.len()).map_err(|_| Error::Oversized {
+            len: body.len() as u64,
+            max: u64::from(u32::MAX),
+        })?;
+
+        let mut header = [VERSION; HEADER_LEN];
+        header[1..].copy_from_slice(&len.to_le_bytes());
+        self.writer.write_all(&header)?;
+        self.writer.write_all(body)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Returns the underlying writer, consuming this writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cell::Cell, Rc};
+    use std::thread;
+
+    /// Nouns to exchange, including one whose frame is larger than a pipe's buffer.
+    fn nouns() -> Vec<Noun> {
+        let mut list = Rc::new(Noun::null());
+        for i in 0..50_000u32 {
+            list = Rc::<Noun>::from(Cell::from([Rc::<Noun>::from(Atom::from(i)), list]));
+        }
+        vec![
+            Noun::null(),
+            Noun::from(Cell::from(["live", "exit"])),
+            Noun::from(Atom::from(vec![0xff; 100_000])),
+            Rc::try_unwrap(list).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn pipe() {
+        let (pipe_reader, pipe_writer) = io::pipe().expect("pipe");
+        let writer = thread::spawn(move || {
+            let mut writer = NewtWriter::new(pipe_writer);
+            for noun in nouns() {
+                writer.write(&noun).expect("write");
+            }
+        });
+
+        let reader = NewtReader::new(pipe_reader);
+        let received: Vec<_> = reader.map(|noun| noun.expect("read")).collect();
+        writer.join().expect("join");
+        assert_eq!(received, nouns());
+    }
+
+    #[test]
+    fn pipe_truncated() {
+        let (pipe_reader, mut pipe_writer) = io::pipe().expect("pipe");
+        let writer = thread::spawn(move || {
+            let mut frame = Vec::new();
+            NewtWriter::new(&mut frame)
+                .write(&Noun::from(Atom::from("truncated")))
+                .expect("write");
+            pipe_writer.write_all(&frame[..8]).expect("write");
+        });
+
+        let mut reader = NewtReader::new(pipe_reader);
+        let err = reader.read().unwrap_err();
+        writer.join().expect("join");
+        assert!(matches!(
+            err,
+            Error::Truncated {
+                expected: 16,
+                read: 8
+            }
+        ));
+    }
+
+    #[test]
+    fn errors() {
+        let read = |bytes: &[u8], max_len| NewtReader::with_max_len(bytes, max_len).read();
+
+        assert!(matches!(
+            read(&[0, 1, 0], 10),
+            Err(Error::Truncated {
+                expected: 5,
+                read: 3
+            })
+        ));
+        assert!(matches!(
+            read(&[1, 1, 0, 0, 0, 0], 10),
+            Err(Error::UnsupportedVersion(1))
+        ));
+        assert!(matches!(
+            read(&[0, 11, 0, 0, 0], 10),
+            Err(Error::Oversized { len: 11, max: 10 })
+        ));
+        // A frame claiming the largest possible body that contains none of it.
+        assert!(matches!(
+            read(&[0, 0xff, 0xff, 0xff, 0xff], usize::MAX),
+            Err(Error::Truncated {
+                expected: 0x1_0000_0004,
+                read: 5
+            })
+        ));
+        // An empty body isn't a jammed noun.
+        assert!(matches!(read(&[0, 0, 0, 0, 0], 10), Err(Error::Cue(_))));
+    }
+}
@@ -0,0 +1,453 @@
+//! [Nock 4K] evaluation.
+//!
+//! [Nock] is Urbit's combinator-based instruction set. A Nock computation applies a formula to a
+//! subject, both of which are nouns, and either produces a product noun or crashes ("bails").
+//!
+//! The interpreter in this module is iterative: rather than recursing on every nested formula, it
+//! drives an explicit work stack, so the depth of a computation is bounded by available heap memory
+//! instead of the size of the Rust stack. Tail positions (opcodes `2`, `6`, `7`, `8`, `9`, and `11`)
+//! replace the current work item instead of stacking a new one, so looping formulas run in constant
+//! space.
+//!
+//! # Examples
+//!
+//! ```
+//! # use noun::{atom::Atom, cell::Cell, nock, Noun, Rc};
+//! // *[42 [4 0 1]] = 43
+//! let subject = Rc::<Noun>::from(Atom::from(42u8));
+//! let formula = Noun::from(Cell::from([4u8, 0u8, 1u8]));
+//! let product = nock::eval(subject, &formula).unwrap();
+//! assert_eq!(*product, Noun::from(Atom::from(43u8)));
+//! ```
+//!
+//! [Nock 4K]: https://developers.urbit.org/reference/nock/definition
+//! [Nock]: https://developers.urbit.org/reference/nock/definition
+
+use crate::{atom::Atom, cell::Cell, noun::Noun, Rc};
+use std::{
+    fmt::{self, Display, Formatter},
+    result,
+};
+
+/// Errors that cause a Nock computation to crash ("bail").
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A formula was an atom.
+    AtomicFormula,
+    /// The opcode of a formula was not in the range `0..=11`.
+    InvalidOpcode,
+    /// The operands of a formula did not have the shape its opcode requires.
+    MalformedFormula,
+    /// A tree address was `0` or referenced a nonexistent subtree.
+    InvalidAxis,
+    /// Opcode `4` was applied to a cell.
+    IncrementCell,
+    /// Opcode `6` was given a test result other than `0` or `1`.
+    InvalidBranch,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
+        match self {
+            Self::AtomicFormula => write!(f, "the formula was an atom"),
+            Self::InvalidOpcode => write!(f, "the formula had an unrecognized opcode"),
+            Self::MalformedFormula => {
+                write!(f, "the formula's operands did not match its opcode")
+            }
+            Self::InvalidAxis => write!(f, "the axis does not exist in the noun"),
+            Self::IncrementCell => write!(f, "a cell cannot be incremented"),
+            Self::InvalidBranch => write!(f, "a conditional's test was neither 0 nor 1"),
+        }
+    }
+}
+
+/// A specialized [`Result`] type for Nock evaluation that returns [`nock::Error`] on error.
+///
+/// [`nock::Error`]: [`Error`]
+pub type Result<T> = result::Result<T, Error>;
+
+/// A unit of pending work for the interpreter.
+///
+/// Every variant other than `Eval` consumes one or more products from the product stack and
+/// either pushes a product back or schedules further work.
+enum Work {
+    /// Evaluates a formula against a subject, pushing the product.
+    Eval(Rc<Noun>, Rc<Noun>),
+    /// Pops a tail and a head, pushing the cell of the two.
+    Cons,
+    /// Pops a formula and a subject, evaluating the formula against the subject (opcode `2`).
+    Nock,
+    /// Pops a noun, pushing `0` if it's a cell and `1` if it's an atom (opcode `3`).
+    IsCell,
+    /// Pops an atom, pushing its successor (opcode `4`).
+    Increment,
+    /// Pops two nouns, pushing `0` if they're equal and `1` otherwise (opcode `5`).
+    IsEqual,
+    /// Pops a test result, evaluating one of two formulas against a subject (opcode `6`).
+    Branch {
+        subject: Rc<Noun>,
+        yes: Rc<Noun>,
+        no: Rc<Noun>,
+    },
+    /// Pops a subject, evaluating a formula against it (opcode `7`).
+    Compose(Rc<Noun>),
+    /// Pops a noun, evaluating a formula against the noun pinned to a subject (opcode `8`).
+    Push {
+        subject: Rc<Noun>,
+        formula: Rc<Noun>,
+    },
+    /// Pops a core, evaluating the arm at an axis of the core against the core (opcode `9`).
+    Invoke(Atom),
+    /// Pops a target and a replacement, editing the target at an axis (opcode `10`).
+    Edit(Atom),
+    /// Pops a product, discarding it (opcode `11`).
+    Discard,
+}
+
+/// Evaluates `formula` against `subject`, returning the product.
+///
+/// All Nock 4K opcodes (`0` through `11`) and autocons (a formula whose head is a cell) are
+/// supported. Hints (opcode `11`) are evaluated for their crash semantics and otherwise ignored.
+pub fn eval(subject: Rc<Noun>, formula: &Noun) -> Result<Rc<Noun>> {
+    let mut work = vec![Work::Eval(subject, Rc::new(formula.clone()))];
+    let mut products: Vec<Rc<Noun>> = Vec::new();
+
+    while let Some(item) = work.pop() {
+        match item {
+            Work::Eval(subject, formula) => step(subject, formula, &mut work, &mut products)?,
+            Work::Cons => {
+                let tail = pop(&mut products);
+                let head = pop(&mut products);
+                products.push(Rc::new(Noun::from(Cell::from([head, tail]))));
+            }
+            Work::Nock => {
+                let formula = pop(&mut products);
+                let subject = pop(&mut products);
+                work.push(Work::Eval(subject, formula));
+            }
+            Work::IsCell => {
+                let noun = pop(&mut products);
+                products.push(loobean(matches!(*noun, Noun::Cell(_))));
+            }
+            Work::Increment => match &*pop(&mut products) {
+                Noun::Atom(atom) => products.push(Rc::new(Noun::from(atom + Atom::from(1u8)))),
+                Noun::Cell(_) => return Err(Error::IncrementCell),
+            },
+            Work::IsEqual => {
+                let rh = pop(&mut products);
+                let lh = pop(&mut products);
+                products.push(loobean(lh == rh));
+            }
+            Work::Branch { subject, yes, no } => match &*pop(&mut products) {
+                Noun::Atom(test) if *test == 0u8 => work.push(Work::Eval(subject, yes)),
+                Noun::Atom(test) if *test == 1u8 => work.push(Work::Eval(subject, no)),
+                _ => return Err(Error::InvalidBranch),
+            },
+            Work::Compose(formula) => {
+                let subject = pop(&mut products);
+                work.push(Work::Eval(subject, formula));
+            }
+            Work::Push { subject, formula } => {
+                let head = pop(&mut products);
+                let subject = Rc::new(Noun::from(Cell::from([head, subject])));
+                work.push(Work::Eval(subject, formula));
+            }
+            Work::Invoke(axis) => {
+                let core = pop(&mut products);
+                let arm = slot(&axis, &core)?;
+                work.push(Work::Eval(core, arm));
+            }
+            Work::Edit(axis) => {
+                let target = pop(&mut products);
+                let value = pop(&mut products);
+                products.push(target.edit_atom(&axis, value).ok_or(Error::InvalidAxis)?);
+            }
+            Work::Discard => {
+                pop(&mut products);
+            }
+        }
+    }
+
+    debug_assert_eq!(products.len(), 1);
+    Ok(pop(&mut products))
+}
+
+/// Decodes a single formula, scheduling the work needed to evaluate it.
+fn step(
+    subject: Rc<Noun>,
+    formula: Rc<Noun>,
+    work: &mut Vec<Work>,
+    products: &mut Vec<Rc<Noun>>,
+) -> Result<()> {
+    let formula = match &*formula {
+        Noun::Atom(_) => return Err(Error::AtomicFormula),
+        Noun::Cell(formula) => formula,
+    };
+    let opcode = match formula.head_ref() {
+        // Autocons: *[a [b c] d] = [*[a b c] *[a d]].
+        Noun::Cell(_) => {
+            work.push(Work::Cons);
+            work.push(Work::Eval(subject.clone(), formula.tail()));
+            work.push(Work::Eval(subject, formula.head()));
+            return Ok(());
+        }
+        Noun::Atom(opcode) => opcode.as_u8().ok_or(Error::InvalidOpcode)?,
+    };
+    let args = formula.tail();
+    match opcode {
+        // *[a 0 b] = /[b a]
+        0 => match &*args {
+            Noun::Atom(axis) => products.push(slot(axis, &subject)?),
+            Noun::Cell(_) => return Err(Error::MalformedFormula),
+        },
+        // *[a 1 b] = b
+        1 => products.push(args),
+        // *[a 2 b c] = *[*[a b] *[a c]]
+        2 => {
+            let [b, c] = operands(&args)?;
+            work.push(Work::Nock);
+            work.push(Work::Eval(subject.clone(), c));
+            work.push(Work::Eval(subject, b));
+        }
+        // *[a 3 b] = ?*[a b]
+        3 => {
+            work.push(Work::IsCell);
+            work.push(Work::Eval(subject, args));
+        }
+        // *[a 4 b] = +*[a b]
+        4 => {
+            work.push(Work::Increment);
+            work.push(Work::Eval(subject, args));
+        }
+        // *[a 5 b c] = =[*[a b] *[a c]]
+        5 => {
+            let [b, c] = operands(&args)?;
+            work.push(Work::IsEqual);
+            work.push(Work::Eval(subject.clone(), c));
+            work.push(Work::Eval(subject, b));
+        }
+        // *[a 6 b c d] = *[a *[[c d] 0 *[[2 3] 0 *[a 4 4 b]]]]
+        6 => {
+            let [b, rest] = operands(&args)?;
+            let [yes, no] = operands(&rest)?;
+            work.push(Work::Branch {
+                subject: subject.clone(),
+                yes,
+                no,
+            });
+            work.push(Work::Eval(subject, b));
+        }
+        // *[a 7 b c] = *[*[a b] c]
+        7 => {
+            let [b, c] = operands(&args)?;
+            work.push(Work::Compose(c));
+            work.push(Work::Eval(subject, b));
+        }
+        // *[a 8 b c] = *[[*[a b] a] c]
+        8 => {
+            let [b, c] = operands(&args)?;
+            work.push(Work::Push {
+                subject: subject.clone(),
+                formula: c,
+            });
+            work.push(Work::Eval(subject, b));
+        }
+        // *[a 9 b c] = *[*[a c] 2 [0 1] 0 b]
+        9 => {
+            let [b, c] = operands(&args)?;
+            let axis = match &*b {
+                Noun::Atom(axis) => axis.clone(),
+                Noun::Cell(_) => return Err(Error::MalformedFormula),
+            };
+            work.push(Work::Invoke(axis));
+            work.push(Work::Eval(subject, c));
+        }
+        // *[a 10 [b c] d] = #[b *[a c] *[a d]]
+        10 => {
+            let [hint, d] = operands(&args)?;
+            let [b, c] = operands(&hint)?;
+            let axis = match &*b {
+                Noun::Atom(axis) => axis.clone(),
+                Noun::Cell(_) => return Err(Error::MalformedFormula),
+            };
+            work.push(Work::Edit(axis));
+            work.push(Work::Eval(subject.clone(), d));
+            work.push(Work::Eval(subject, c));
+        }
+        // *[a 11 [b c] d] = *[[*[a c] *[a d]] 0 3]
+        // *[a 11 b c] = *[a c]
+        11 => {
+            let [hint, d] = operands(&args)?;
+            if let Noun::Cell(hint) = &*hint {
+                // The hint formula is evaluated first so that it crashes before the hinted formula
+                // runs, matching the reference semantics.
+                work.push(Work::Eval(subject.clone(), d));
+                work.push(Work::Discard);
+                work.push(Work::Eval(subject, hint.tail()));
+            } else {
+                work.push(Work::Eval(subject, d));
+            }
+        }
+        _ => return Err(Error::InvalidOpcode),
+    }
+    Ok(())
+}
+
+/// Splits the operands of a formula into a head and a tail.
+fn operands(args: &Noun) -> Result<[Rc<Noun>; 2]> {
+    match args {
+        Noun::Cell(cell) => Ok([cell.head(), cell.tail()]),
+        Noun::Atom(_) => Err(Error::MalformedFormula),
+    }
+}
+
+/// Pops a product off of the product stack.
+///
+/// Every work item is scheduled after the work items that produce its operands, so the product
+/// stack can never underflow.
+fn pop(products: &mut Vec<Rc<Noun>>) -> Rc<Noun> {
+    products.pop().expect("product stack underflow")
+}
+
+/// Converts a boolean into a Nock loobean, where `0` is yes and `1` is no.
+fn loobean(yes: bool) -> Rc<Noun> {
+    Rc::new(Noun::from(Atom::from(u8::from(!yes))))
+}
+
+/// Returns the subtree of `noun` at `axis` (the `/` operator).
+fn slot(axis: &Atom, noun: &Rc<Noun>) -> Result<Rc<Noun>> {
+    noun.slot_rc(axis).ok_or(Error::InvalidAxis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a noun from Hoon-like bracket syntax of integer literals.
+    macro_rules! n {
+        ([$($elem:tt)+]) => {
+            Noun::from(Cell::from([$(n!($elem)),+]))
+        };
+        ($atom:literal) => {
+            Noun::from(Atom::from($atom as u64))
+        };
+    }
+
+    fn nock(subject: Noun, formula: Noun) -> Result<Noun> {
+        eval(Rc::new(subject), &formula).map(|product| (*product).clone())
+    }
+
+    #[test]
+    fn slot() {
+        let subject = n!([[4 5] [6 14 15]]);
+        assert_eq!(nock(subject.clone(), n!([0 1])), Ok(subject.clone()));
+        assert_eq!(nock(subject.clone(), n!([0 2])), Ok(n!([4 5])));
+        assert_eq!(nock(subject.clone(), n!([0 7])), Ok(n!([14 15])));
+        assert_eq!(nock(subject.clone(), n!([0 15])), Ok(n!(15)));
+        assert_eq!(nock(subject.clone(), n!([0 0])), Err(Error::InvalidAxis));
+        assert_eq!(nock(subject.clone(), n!([0 8])), Err(Error::InvalidAxis));
+
+        // The subtree is shared with the subject rather than copied.
+        let subject = Rc::new(subject);
+        let product = eval(subject.clone(), &n!([0 6])).expect("slot");
+        assert!(std::ptr::eq(&*product, subject.slot(6).expect("slot")));
+    }
+
+    #[test]
+    fn constant() {
+        assert_eq!(nock(n!(42), n!([1 [153 218]])), Ok(n!([153 218])));
+    }
+
+    #[test]
+    fn evaluate() {
+        assert_eq!(
+            nock(n!(77), n!([2 [1 42] [1 1 153 218]])),
+            Ok(n!([153 218]))
+        );
+    }
+
+    #[test]
+    fn cell_test() {
+        assert_eq!(nock(n!([1 2]), n!([3 0 1])), Ok(n!(0)));
+        assert_eq!(nock(n!(1), n!([3 0 1])), Ok(n!(1)));
+    }
+
+    #[test]
+    fn increment() {
+        assert_eq!(nock(n!(57), n!([4 0 1])), Ok(n!(58)));
+        assert_eq!(nock(n!(255), n!([4 0 1])), Ok(n!(256)));
+        assert_eq!(
+            nock(Noun::from(Atom::from(u64::MAX)), n!([4 0 1])),
+            Ok(Noun::from(Atom::from(u128::from(u64::MAX) + 1)))
+        );
+        assert_eq!(nock(n!([1 2]), n!([4 0 1])), Err(Error::IncrementCell));
+    }
+
+    #[test]
+    fn equal() {
+        assert_eq!(nock(n!([2 2]), n!([5 [0 2] 0 3])), Ok(n!(0)));
+        assert_eq!(nock(n!([2 3]), n!([5 [0 2] 0 3])), Ok(n!(1)));
+    }
+
+    #[test]
+    fn branch() {
+        assert_eq!(nock(n!(42), n!([6 [1 0] [1 7] 1 8])), Ok(n!(7)));
+        assert_eq!(nock(n!(42), n!([6 [1 1] [1 7] 1 8])), Ok(n!(8)));
+        assert_eq!(
+            nock(n!(42), n!([6 [1 2] [1 7] 1 8])),
+            Err(Error::InvalidBranch)
+        );
+    }
+
+    #[test]
+    fn compose_and_push() {
+        assert_eq!(nock(n!(42), n!([7 [4 0 1] 4 0 1])), Ok(n!(44)));
+        assert_eq!(nock(n!(42), n!([8 [4 0 1] [0 1]])), Ok(n!([43 42])));
+    }
+
+    #[test]
+    fn invoke() {
+        // A core whose battery at axis 2 increments the sample at axis 3.
+        let core = n!([[4 0 3] 41]);
+        assert_eq!(nock(core, n!([9 2 0 1])), Ok(n!(42)));
+    }
+
+    #[test]
+    fn edit() {
+        assert_eq!(nock(n!([1 2 3]), n!([10 [2 1 9] 0 1])), Ok(n!([9 2 3])));
+        assert_eq!(nock(n!([1 2 3]), n!([10 [7 1 9] 0 1])), Ok(n!([1 2 9])));
+        assert_eq!(nock(n!([1 2 3]), n!([10 [1 1 9] 0 1])), Ok(n!(9)));
+        assert_eq!(nock(n!(1), n!([10 [2 1 9] 0 1])), Err(Error::InvalidAxis));
+    }
+
+    #[test]
+    fn hint() {
+        assert_eq!(nock(n!(42), n!([11 37 [4 0 1]])), Ok(n!(43)));
+        assert_eq!(nock(n!(42), n!([11 [37 [1 0]] [4 0 1]])), Ok(n!(43)));
+        assert_eq!(
+            nock(n!(42), n!([11 [37 [4 1 1 2]] [4 0 1]])),
+            Err(Error::IncrementCell)
+        );
+    }
+
+    #[test]
+    fn autocons() {
+        assert_eq!(nock(n!(42), n!([[4 0 1] [0 1]])), Ok(n!([43 42])));
+    }
+
+    #[test]
+    fn crash() {
+        assert_eq!(nock(n!(42), n!(0)), Err(Error::AtomicFormula));
+        assert_eq!(nock(n!(42), n!([12 0 1])), Err(Error::InvalidOpcode));
+        assert_eq!(nock(n!(42), n!([2 0])), Err(Error::MalformedFormula));
+    }
+
+    #[test]
+    fn decrement() {
+        // The canonical Nock decrement, which loops via opcode `9` until it finds the predecessor.
+        let dec = n!([8 [1 0] 8 [1 6 [5 [0 7] 4 0 6] [0 6] 9 2 [0 2] [4 0 6] 0 7] 9 2 0 1]);
+        assert_eq!(nock(n!(42), dec.clone()), Ok(n!(41)));
+        // Looping this many times would overflow the Rust stack of a recursive interpreter.
+        assert_eq!(nock(n!(200_000), dec), Ok(n!(199_999)));
+    }
+}
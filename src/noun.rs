@@ -1,18 +1,26 @@
 use crate::{
-    atom::{Atom, Builder as AtomBuilder, Iter as AtomIter},
+    atom::Atom,
     cell::Cell,
     convert,
-    serdes::{self, Cue, Jam},
+    intern::NounInterner,
+    parse,
+    print::Pretty,
+    serdes::{self, BitSink, BitSource, Cue, CueLimits, Entity, Jam},
     Rc,
 };
 use std::{
     collections::HashMap,
+    convert::Infallible,
     fmt::{Display, Error, Formatter},
-    mem::drop,
+    hash::{BuildHasherDefault, Hash, Hasher},
+    str::FromStr,
 };
 
 /// An [`Atom`] or a [`Cell`].
-#[derive(Clone, Debug, Eq, Hash)]
+///
+/// Nouns are ordered like Hoon's [`dor`](Noun::dor): atoms before cells, atoms numerically, and
+/// cells by head and then by tail.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Noun {
     /// An arbitrarily large unsigned integer.
     Atom(Atom),
@@ -35,103 +43,441 @@ impl Noun {
         }
     }
 
-    /// Computes the hash of this noun.
+    /// Returns a [`Display`] that prints this noun in Hoon's syntax, with the aura of each atom
+    /// inferred. See [`print`](crate::print) for details.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::{cell::Cell, Noun};
+    /// let noun = Noun::from(Cell::from(["poke", "hello world"]));
+    /// assert_eq!(noun.pretty().to_string(), "[%poke 'hello world']");
+    /// ```
+    pub fn pretty(&self) -> Pretty<'_> {
+        Pretty::new(self)
+    }
+
+    /// Computes the hash of this noun: [`Atom::hash()`] of an atom, or the cached
+    /// [`Cell::hash()`] of a cell.
     pub fn hash(&self) -> u64 {
         match self {
             Self::Atom(atom) => atom.hash(),
             Self::Cell(cell) => cell.hash(),
         }
     }
-}
 
-impl Cue for Noun {
-    fn cue(jammed_noun: Atom) -> serdes::Result<Self> {
-        fn decode_atom(bits: &mut AtomIter) -> serdes::Result<Atom> {
-            let len = {
-                let mut len_of_len = 0;
-                loop {
-                    match bits.next() {
-                        Some(true) => break,
-                        Some(false) => len_of_len += 1,
-                        None => return Err(serdes::Error::InvalidLen),
-                    }
+    /// Computes the [mug] of this noun, Urbit's 31-bit hash of a noun.
+    ///
+    /// See [`Atom::mug()`] and [`Cell::mug()`].
+    ///
+    /// [mug]: https://developers.urbit.org/reference/hoon/stdlib/2e#mug
+    pub fn mug(&self) -> u32 {
+        match self {
+            Self::Atom(atom) => atom.mug(),
+            Self::Cell(cell) => cell.mug(),
+        }
+    }
+
+    /// Returns whether this noun comes before or is `other` in depth-first tree order: atoms before
+    /// cells, atoms numerically, and cells by head and then by tail. This is Hoon's [`dor`], which
+    /// agrees with the [`Ord`] implementation of nouns.
+    ///
+    /// [`dor`]: https://developers.urbit.org/reference/hoon/stdlib/2da#dor
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{atom::Atom, cell::Cell, Noun};
+    /// let (one, two) = (Noun::from(Atom::from(1u8)), Noun::from(Atom::from(2u8)));
+    /// assert!(one.dor(&two));
+    /// assert!(!two.dor(&one));
+    /// assert!(two.dor(&Noun::from(Cell::from([0u8, 0u8]))));
+    /// ```
+    pub fn dor(&self, other: &Self) -> bool {
+        self <= other
+    }
+
+    /// Returns whether this noun comes before or is `other` in alphabetical order: atoms before
+    /// cells, atoms by their bytes from least to most significant, and cells by head and then by
+    /// tail. This is Hoon's [`aor`], which sorts cords alphabetically.
+    ///
+    /// [`aor`]: https://developers.urbit.org/reference/hoon/stdlib/2da#aor
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{atom::Atom, Noun};
+    /// let (ab, b) = (Noun::from(Atom::from("ab")), Noun::from(Atom::from("b")));
+    /// assert!(ab.aor(&b));
+    /// assert!(!ab.dor(&b));
+    /// ```
+    pub fn aor(&self, other: &Self) -> bool {
+        let (mut this, mut that) = (self, other);
+        loop {
+            match (this, that) {
+                (Self::Atom(this), Self::Atom(that)) => return this.as_bytes() <= that.as_bytes(),
+                (Self::Atom(_), Self::Cell(_)) => return true,
+                (Self::Cell(_), Self::Atom(_)) => return false,
+                (Self::Cell(this_cell), Self::Cell(that_cell)) => {
+                    (this, that) = if this_cell.head_ref() == that_cell.head_ref() {
+                        (this_cell.tail_ref(), that_cell.tail_ref())
+                    } else {
+                        (this_cell.head_ref(), that_cell.head_ref())
+                    };
                 }
+            }
+        }
+    }
 
-                if len_of_len == 0 {
-                    0
-                } else {
-                    // The most significant bit of the length is implicit because it's always 1.
-                    let len_bits = len_of_len - 1;
-                    let mut len: u64 = 1 << len_bits;
-                    for i in 0..len_bits {
-                        match bits.next() {
-                            Some(true) => len |= 1 << i,
-                            Some(false) => len &= !(1 << i),
-                            None => return Err(serdes::Error::InvalidLen),
-                        }
-                    }
-                    len
+    /// Returns whether this noun comes before or is `other` in mug order, falling back to
+    /// [`dor`](Noun::dor) when their mugs are equal. This is Hoon's [`gor`], which orders the
+    /// nodes of maps and sets.
+    ///
+    /// [`gor`]: https://developers.urbit.org/reference/hoon/stdlib/2da#gor
+    pub fn gor(&self, other: &Self) -> bool {
+        let (this_mug, that_mug) = (self.mug(), other.mug());
+        if this_mug == that_mug {
+            self.dor(other)
+        } else {
+            this_mug < that_mug
+        }
+    }
+
+    /// Returns whether this noun comes before or is `other` in double-mug order (by the mugs of
+    /// their mugs), falling back to [`dor`](Noun::dor) when their double mugs are equal. This is
+    /// Hoon's [`mor`], which balances maps, sets, mops, and queues.
+    ///
+    /// [`mor`]: https://developers.urbit.org/reference/hoon/stdlib/2da#mor
+    pub fn mor(&self, other: &Self) -> bool {
+        let double_mug = |noun: &Self| Atom::from(noun.mug()).mug();
+        let (this_mug, that_mug) = (double_mug(self), double_mug(other));
+        if this_mug == that_mug {
+            self.dor(other)
+        } else {
+            this_mug < that_mug
+        }
+    }
+
+    /// Returns the subtree of this noun at `axis`, returning `None` if `axis` is `0` or this noun
+    /// has no subtree at `axis`.
+    ///
+    /// This is Nock's slot (`/`) operator: axis `1` is the noun itself, and the head and tail of the
+    /// subtree at axis `n` are at axes `2n` and `2n + 1`, respectively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{atom::Atom, cell::Cell, Noun};
+    /// // [[4 5] [6 14 15]]
+    /// let noun = Noun::from(Cell::from([
+    ///     Noun::from(Cell::from([4u8, 5u8])),
+    ///     Noun::from(Cell::from([6u8, 14u8, 15u8])),
+    /// ]));
+    /// assert_eq!(noun.slot(1), Some(&noun));
+    /// assert_eq!(noun.slot(2), Some(&Noun::from(Cell::from([4u8, 5u8]))));
+    /// assert_eq!(noun.slot(7), Some(&Noun::from(Cell::from([14u8, 15u8]))));
+    /// assert_eq!(noun.slot(15), Some(&Noun::from(Atom::from(15u8))));
+    /// assert_eq!(noun.slot(8), None);
+    /// assert_eq!(noun.slot(0), None);
+    /// ```
+    pub fn slot(&self, axis: u64) -> Option<&Self> {
+        self.slot_atom(&Atom::from(axis))
+    }
+
+    /// Returns the subtree of this noun at an arbitrarily large `axis`, returning `None` if `axis`
+    /// is `0` or this noun has no subtree at `axis`.
+    ///
+    /// See [`slot()`](Self::slot()).
+    pub fn slot_atom(&self, axis: &Atom) -> Option<&Self> {
+        let mut noun = self;
+        for tail in axis_path(axis)? {
+            noun = match noun {
+                Self::Cell(cell) if tail => cell.tail_ref(),
+                Self::Cell(cell) => cell.head_ref(),
+                Self::Atom(_) => return None,
+            };
+        }
+        Some(noun)
+    }
+
+    /// Like [`slot_atom()`](Self::slot_atom()), but returns the subtree at `axis` as the
+    /// [`Rc<Noun>`] it's already stored in rather than a reference.
+    pub(crate) fn slot_rc(self: &Rc<Self>, axis: &Atom) -> Option<Rc<Self>> {
+        let mut noun = self;
+        for tail in axis_path(axis)? {
+            noun = match &**noun {
+                Self::Cell(cell) if tail => cell.tail_rc(),
+                Self::Cell(cell) => cell.head_rc(),
+                Self::Atom(_) => return None,
+            };
+        }
+        Some(Rc::clone(noun))
+    }
+
+    /// Returns a copy of this noun with the subtree at `axis` replaced by `value`, returning `None`
+    /// if `axis` is `0` or this noun has no subtree at `axis`.
+    ///
+    /// This is Nock's edit (`#`) operator. Only the cells along the path from the root to `axis`
+    /// are copied; every other subtree is shared with this noun.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{atom::Atom, cell::Cell, Noun, Rc};
+    /// let noun = Rc::<Noun>::from(Cell::from([1u8, 2u8, 3u8]));
+    /// let value = Rc::<Noun>::from(Atom::from(9u8));
+    /// assert_eq!(
+    ///     *noun.edit(6, value.clone()).unwrap(),
+    ///     Noun::from(Cell::from([1u8, 9u8, 3u8]))
+    /// );
+    /// assert_eq!(*noun.edit(1, value.clone()).unwrap(), *value);
+    /// assert_eq!(noun.edit(5, value), None);
+    /// ```
+    pub fn edit(self: &Rc<Self>, axis: u64, value: Rc<Self>) -> Option<Rc<Self>> {
+        self.edit_atom(&Atom::from(axis), value)
+    }
+
+    /// Returns a copy of this noun with the subtree at an arbitrarily large `axis` replaced by
+    /// `value`, returning `None` if `axis` is `0` or this noun has no subtree at `axis`.
+    ///
+    /// See [`edit()`](Self::edit()).
+    pub fn edit_atom(self: &Rc<Self>, axis: &Atom, value: Rc<Self>) -> Option<Rc<Self>> {
+        // The cells along the path to `axis`, each paired with whether the path continues into its
+        // tail.
+        let mut spine = Vec::new();
+        let mut noun = &**self;
+        for tail in axis_path(axis)? {
+            match noun {
+                Self::Cell(cell) => {
+                    spine.push((cell, tail));
+                    noun = if tail {
+                        cell.tail_ref()
+                    } else {
+                        cell.head_ref()
+                    };
                 }
+                Self::Atom(_) => return None,
+            }
+        }
+        let mut noun = value;
+        while let Some((cell, tail)) = spine.pop() {
+            noun = if tail {
+                Rc::<Self>::from(Cell::from([cell.head(), noun]))
+            } else {
+                Rc::<Self>::from(Cell::from([noun, cell.tail()]))
             };
-            if len == 0 {
-                Ok(Atom::from(0u8))
+        }
+        Some(noun)
+    }
+
+    /// Returns the canonical noun in `interner` equal to this noun, adding this noun to `interner`
+    /// if it isn't in it yet.
+    ///
+    /// The canonical noun is rebuilt bottom up from the canonical nouns of its subtrees, so equal
+    /// subtrees share one allocation and equal interned nouns are pointer-equal. Subtrees of this
+    /// noun that are already canonical are reused rather than copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use noun::{cell::Cell, intern::NounInterner, serdes::{Cue, Jam}, Noun, Rc};
+    /// let mut interner = NounInterner::new();
+    /// let noun = Rc::<Noun>::from(Cell::from([1u8, 2u8]));
+    /// let copy = Rc::<Noun>::cue(noun.clone().jam()).unwrap();
+    /// assert!(!Rc::ptr_eq(&noun, &copy));
+    /// assert!(Rc::ptr_eq(&noun.intern(&mut interner), &copy.intern(&mut interner)));
+    /// ```
+    pub fn intern(self: &Rc<Self>, interner: &mut NounInterner) -> Rc<Self> {
+        interner.intern(self)
+    }
+
+    /// Deserializes ("cues") a jammed noun like [`Cue::cue()`], but bounded by `limits` rather than
+    /// the [default](CueLimits::default()) limits.
+    ///
+    /// Use this with [`CueLimits::unlimited()`] to decode trusted input that may exceed the
+    /// defaults, such as a pier snapshot.
+    pub fn cue_with_limits(jammed_noun: Atom, limits: &CueLimits) -> serdes::Result<Self> {
+        // The cache used during decoding has been dropped by the time `cue_atom()` returns, which
+        // guarantees that the top level noun has exactly one reference and makes it safe to move
+        // out of the Rc.
+        let noun = cue_atom(&jammed_noun, limits)?;
+        Ok(Rc::try_unwrap(noun).unwrap())
+    }
+}
+
+/// Returns the bits of `axis` below its most significant bit, from most to least significant, or
+/// `None` if `axis` is `0`.
+///
+/// Each bit selects a branch when descending from the root of a noun: `false` for the head and
+/// `true` for the tail.
+fn axis_path(axis: &Atom) -> Option<impl Iterator<Item = bool> + '_> {
+    if axis.is_null() {
+        return None;
+    }
+    let bytes = axis.as_bytes();
+    Some(
+        (0..axis.bit_len() - 1)
+            .rev()
+            .map(move |i| bytes[i / 8] & (1 << (i % 8)) != 0),
+    )
+}
+
+/// Decodes a jammed noun held in memory, failing if decoding exceeds `limits`.
+fn cue_atom(jammed_noun: &Atom, limits: &CueLimits) -> serdes::Result<Rc<Noun>> {
+    if jammed_noun.bit_len() > limits.max_total_bits {
+        return Err(serdes::Error::MaxTotalBitsExceeded);
+    }
+    decode(&mut jammed_noun.iter(), limits)
+}
+
+/// Decodes a jammed noun from a bitstream, failing if decoding exceeds `limits`.
+pub(crate) fn decode(bits: &mut impl BitSource, limits: &CueLimits) -> serdes::Result<Rc<Noun>> {
+    /// Decodes the length and bits of the atom or backreference index of the entity that starts at
+    /// `pos`.
+    fn decode_atom(
+        bits: &mut impl BitSource,
+        max_bits: usize,
+        pos: u64,
+        entity: Entity,
+    ) -> serdes::Result<Atom> {
+        let eof = || serdes::Error::UnexpectedEof { pos, entity };
+        let len = {
+            let mut len_of_len = 0;
+            loop {
+                match bits.next_bit()? {
+                    Some(true) => break,
+                    Some(false) => len_of_len += 1,
+                    None => return Err(eof()),
+                }
+            }
+
+            if len_of_len == 0 {
+                0
             } else {
-                let mut atom_builder = Atom::builder();
-                for _ in 0..len {
-                    let bit = bits.next().ok_or(serdes::Error::AtomBuilding)?;
-                    atom_builder.push_bit(bit);
+                // The most significant bit of the length is implicit because it's always 1.
+                let len_bits = len_of_len - 1;
+                if len_bits >= u64::BITS as usize {
+                    return Err(serdes::Error::InvalidLen { pos, entity });
                 }
-                Ok(atom_builder.into_atom())
+                let len = bits.next_bits(len_bits)?.ok_or_else(eof)?;
+                len | (1 << len_bits)
             }
+        };
+        let len = usize::try_from(len).map_err(|_| serdes::Error::InvalidLen { pos, entity })?;
+        if len > max_bits {
+            return Err(serdes::Error::MaxAtomBitsExceeded { pos, entity });
         }
+        bits.next_atom(len)?.ok_or_else(eof)
+    }
 
-        fn decode(
-            bits: &mut AtomIter,
-            cache: &mut HashMap<u64, Rc<Noun>>,
-        ) -> serdes::Result<Rc<Noun>> {
-            let pos = bits.pos() as u64;
-            match bits.next() {
-                Some(true) => {
-                    match bits.next() {
-                        // Back reference tag = 0b11.
-                        Some(true) => {
-                            let idx = decode_atom(bits)?
-                                .as_u64()
-                                .ok_or(serdes::Error::InvalidBackref)?;
-                            let noun = cache.get(&idx).ok_or(serdes::Error::CacheMiss)?;
-                            Ok(noun.clone())
-                        }
-                        // Cell tag = 0b01.
-                        Some(false) => {
-                            let head = decode(bits, cache)?;
-                            let tail = decode(bits, cache)?;
-
-                            let cell = Rc::<Noun>::from(Cell::from([head, tail]));
-                            cache.insert(pos, cell.clone());
+    /// A cell whose head or tail is being decoded.
+    enum Frame {
+        /// The head of the cell at the given bit position is being decoded.
+        Head(u64),
+        /// The tail of the cell at the given bit position is being decoded, and its head has been.
+        Tail(u64, Rc<Noun>),
+    }
 
-                            Ok(cell)
+    let mut cache = WordMap::default();
+    let mut frames = Vec::new();
+    let mut cells = 0;
+    loop {
+        let pos = bits.pos();
+        let eof = || serdes::Error::UnexpectedEof {
+            pos,
+            entity: Entity::Tag,
+        };
+        let Some(tag) = bits.next_bit()? else {
+            // The input ended where the head or tail of a cell should have begun.
+            return Err(match frames.last() {
+                Some(Frame::Head(pos) | Frame::Tail(pos, _)) => serdes::Error::UnexpectedEof {
+                    pos: *pos,
+                    entity: Entity::Cell,
+                },
+                None => eof(),
+            });
+        };
+        let mut noun = match tag {
+            true => {
+                match bits.next_bit()?.ok_or_else(eof)? {
+                    // Back reference tag = 0b11.
+                    true => {
+                        let idx = decode_atom(bits, limits.max_atom_bits, pos, Entity::Backref)?;
+                        let idx = idx.as_u64().ok_or_else(|| serdes::Error::InvalidBackref {
+                            pos,
+                            idx: idx.clone(),
+                        })?;
+                        let noun = cache
+                            .get(&idx)
+                            .ok_or(serdes::Error::CacheMiss { pos, idx })?;
+                        Rc::clone(noun)
+                    }
+                    // Cell tag = 0b01.
+                    false => {
+                        if frames.len() == limits.max_depth {
+                            return Err(serdes::Error::MaxDepthExceeded { pos });
+                        }
+                        if cells == limits.max_cells {
+                            return Err(serdes::Error::MaxCellsExceeded { pos });
                         }
-                        None => return Err(serdes::Error::InvalidTag),
+                        cells += 1;
+                        frames.push(Frame::Head(pos));
+                        continue;
                     }
                 }
-                // Atom tag = 0b0.
-                Some(false) => {
-                    let atom = Rc::<Noun>::from(decode_atom(bits)?);
-                    cache.insert(pos, atom.clone());
-                    Ok(atom)
+            }
+            // Atom tag = 0b0.
+            false => {
+                let atom = decode_atom(bits, limits.max_atom_bits, pos, Entity::Atom)?;
+                let atom = Rc::<Noun>::from(atom);
+                cache.insert(pos, atom.clone());
+                atom
+            }
+        };
+
+        // Complete every cell whose tail was just decoded.
+        loop {
+            match frames.pop() {
+                Some(Frame::Head(pos)) => {
+                    frames.push(Frame::Tail(pos, noun));
+                    break;
+                }
+                Some(Frame::Tail(pos, head)) => {
+                    noun = Rc::<Noun>::from(Cell::from([head, noun]));
+                    cache.insert(pos, noun.clone());
                 }
-                None => unimplemented!(),
+                None => return Ok(noun),
             }
         }
+    }
+}
+
+impl Cue for Rc<Noun> {
+    fn cue(jammed_noun: Atom) -> serdes::Result<Self> {
+        cue_atom(&jammed_noun, &CueLimits::default())
+    }
+}
+
+impl Cue for Noun {
+    fn cue(jammed_noun: Atom) -> serdes::Result<Self> {
+        Self::cue_with_limits(jammed_noun, &CueLimits::default())
+    }
+}
+
+impl Cue for Atom {
+    fn cue(jammed_noun: Atom) -> serdes::Result<Self> {
+        match Noun::cue(jammed_noun)? {
+            Noun::Atom(atom) => Ok(atom),
+            Noun::Cell(_) => Err(serdes::Error::UnexpectedCell),
+        }
+    }
+}
 
-        let mut bits = jammed_noun.iter();
-        let mut cache = HashMap::new();
-        let noun = decode(&mut bits, &mut cache)?;
-        // Dropping the cache guarantees that the top level noun has exactly one reference, which
-        // makes it safe to move out of the Rc.
-        drop(cache);
-        let noun = Rc::try_unwrap(noun).unwrap();
-        Ok(noun)
+impl Cue for Cell {
+    fn cue(jammed_noun: Atom) -> serdes::Result<Self> {
+        match Noun::cue(jammed_noun)? {
+            Noun::Atom(_) => Err(serdes::Error::UnexpectedAtom),
+            Noun::Cell(cell) => Ok(cell),
+        }
     }
 }
 
@@ -168,85 +514,368 @@ impl From<Cell> for Rc<Noun> {
     }
 }
 
-impl Jam for Noun {
-    fn jam(self) -> Atom {
-        fn encode_len(mut len: u64, bits: &mut AtomBuilder) {
-            let len_of_len = u64::BITS - len.leading_zeros();
-            for _ in 0..len_of_len {
-                bits.push_bit(false);
-            }
-            bits.push_bit(true);
-            if len_of_len != 0 {
-                // Don't write the most significant bit of the length because it's always 1.
-                while len != 1 {
-                    bits.push_bit((len & 1) != 0);
-                    len >>= 1;
-                }
+impl FromStr for Noun {
+    type Err = parse::Error;
+
+    /// Parses a noun written in Hoon's literal syntax, as described in [`parse`].
+    fn from_str(text: &str) -> parse::Result<Self> {
+        let noun = parse::parse(text)?;
+        Ok(Rc::try_unwrap(noun).unwrap_or_else(|noun| (*noun).clone()))
+    }
+}
+
+/// Converts an element of a [`noun!`](crate::noun!) literal into a noun.
+///
+/// Implemented for everything that implements [`Into<Noun>`], for [`Rc<Noun>`] so that it's shared
+/// rather than copied, and for unsigned integers and string slices, which become atoms.
+#[doc(hidden)]
+pub trait IntoNounElement {
+    fn into_noun_element(self) -> Rc<Noun>;
+}
+
+impl<T: Into<Noun>> IntoNounElement for T {
+    fn into_noun_element(self) -> Rc<Noun> {
+        Rc::new(self.into())
+    }
+}
+
+impl IntoNounElement for Rc<Noun> {
+    fn into_noun_element(self) -> Rc<Noun> {
+        self
+    }
+}
+
+impl IntoNounElement for &Rc<Noun> {
+    fn into_noun_element(self) -> Rc<Noun> {
+        Rc::clone(self)
+    }
+}
+
+impl IntoNounElement for &str {
+    fn into_noun_element(self) -> Rc<Noun> {
+        Rc::<Noun>::from(Atom::from(self))
+    }
+}
+
+/// A literal element of a [`noun!`](crate::noun!) literal.
+///
+/// Unsuffixed integer literals are `i32`, which is only accepted as a literal: [`noun!`](crate::noun!)
+/// rejects negative literals at compile time, while a signed integer could be negative at runtime.
+#[doc(hidden)]
+pub struct Literal<T>(pub T);
+
+impl IntoNounElement for Literal<i32> {
+    fn into_noun_element(self) -> Rc<Noun> {
+        let uint = u32::try_from(self.0).expect("negative literals are rejected by noun!");
+        Rc::<Noun>::from(Atom::from(uint))
+    }
+}
+
+impl<T: IntoNounElement> IntoNounElement for Literal<T> {
+    fn into_noun_element(self) -> Rc<Noun> {
+        self.0.into_noun_element()
+    }
+}
+
+macro_rules! impl_into_noun_element_for_uint {
+    ($uint:ty) => {
+        impl IntoNounElement for $uint {
+            fn into_noun_element(self) -> Rc<Noun> {
+                Rc::<Noun>::from(Atom::from(self))
             }
         }
+    };
+}
 
-        fn encode_atom(atom: &Atom, bits: &mut AtomBuilder) {
-            // Atom tag = 0b0.
-            bits.push_bit(false);
-            encode_len(atom.bit_len() as u64, bits);
-            for bit in atom.iter() {
-                bits.push_bit(bit);
-            }
+impl_into_noun_element_for_uint!(u8);
+impl_into_noun_element_for_uint!(u16);
+impl_into_noun_element_for_uint!(u32);
+impl_into_noun_element_for_uint!(u64);
+impl_into_noun_element_for_uint!(u128);
+impl_into_noun_element_for_uint!(usize);
+
+/// Creates a [`Noun`] from Hoon-like literal syntax.
+///
+/// Elements are separated by whitespace, and a sequence of elements is a cell that associates to
+/// the right: `noun![a b c]` is `[a [b c]]`. Each element is one of:
+/// - `[a b ...]`: a nested cell of two or more elements.
+/// - `~`: null.
+/// - `%term`: a symbol, which may contain `-`, or a loobean `%.y` or `%.n`.
+/// - A string literal, which is a cord, or an integer literal. Negative integer literals are
+///   rejected at compile time.
+/// - An identifier or a parenthesized Rust expression whose type implements [`Into<Noun>`], or
+///   is [`Rc<Noun>`](crate::Rc) or `&Rc<Noun>`, which is shared rather than copied.
+///
+/// A null-terminated list ends in `~`, as in `noun![1 2 3 ~]`.
+///
+/// Cells are built from [`Rc<Noun>`](crate::Rc)s, so interpolated nouns aren't deep-copied.
+///
+/// # Examples
+/// ```
+/// # use ::noun::{atom::Atom, Noun, Rc};
+/// use noun::noun;
+///
+/// let payload = Rc::new(noun![1 2 ~]);
+/// let noun = noun![%x-cached ["HIT" 0x1f] (Rc::clone(&payload)) %.n ~];
+/// assert_eq!(noun, "[%x-cached ['HIT' 31] ~[1 2] %.n ~]".parse().unwrap());
+///
+/// let atom = Atom::from(7u8);
+/// assert_eq!(noun![atom], Noun::from(Atom::from(7u8)));
+/// ```
+///
+/// Atoms are unsigned:
+/// ```compile_fail
+/// use noun::noun;
+///
+/// let noun = noun![1 -2];
+/// ```
+///
+/// Signed integers can't be interpolated, since they may be negative:
+/// ```compile_fail
+/// use noun::noun;
+///
+/// let offset = -2;
+/// let noun = noun![1 (offset)];
+/// ```
+#[macro_export]
+macro_rules! noun {
+    // Collects the elements of the top-level noun or of a cell as `Rc<Noun>` expressions.
+    (@collect $kind:ident [$($done:expr,)*] ~ $($rest:tt)*) => {
+        $crate::noun!(@collect $kind [$($done,)* $crate::noun::IntoNounElement::into_noun_element(0u8),] $($rest)*)
+    };
+    (@collect $kind:ident [$($done:expr,)*] % . y $($rest:tt)*) => {
+        $crate::noun!(@collect $kind [$($done,)* $crate::noun::IntoNounElement::into_noun_element(0u8),] $($rest)*)
+    };
+    (@collect $kind:ident [$($done:expr,)*] % . n $($rest:tt)*) => {
+        $crate::noun!(@collect $kind [$($done,)* $crate::noun::IntoNounElement::into_noun_element(1u8),] $($rest)*)
+    };
+    (@collect $kind:ident [$($done:expr,)*] % $first:ident $($rest:tt)*) => {
+        $crate::noun!(@term $kind [$($done,)*] [stringify!($first)] $($rest)*)
+    };
+    (@collect $kind:ident [$($done:expr,)*] [$($cell:tt)*] $($rest:tt)*) => {
+        $crate::noun!(@collect $kind [$($done,)* $crate::noun!(@collect cell [] $($cell)*),] $($rest)*)
+    };
+    (@collect $kind:ident [$($done:expr,)*] ($elem:expr) $($rest:tt)*) => {
+        $crate::noun!(@collect $kind [$($done,)* $crate::noun::IntoNounElement::into_noun_element($elem),] $($rest)*)
+    };
+    (@collect $kind:ident [$($done:expr,)*] $elem:ident $($rest:tt)*) => {
+        $crate::noun!(@collect $kind [$($done,)* $crate::noun::IntoNounElement::into_noun_element($elem),] $($rest)*)
+    };
+    (@collect $kind:ident [$($done:expr,)*] - $elem:literal $($rest:tt)*) => {
+        compile_error!("atoms are unsigned, so integer literals mustn't be negative")
+    };
+    (@collect $kind:ident [$($done:expr,)*] $elem:literal $($rest:tt)*) => {
+        $crate::noun!(@collect $kind [$($done,)* $crate::noun::IntoNounElement::into_noun_element($crate::noun::Literal($elem)),] $($rest)*)
+    };
+    (@collect top [$only:expr,]) => {
+        $only
+    };
+    (@collect cell [$($only:expr,)?]) => {
+        compile_error!("a cell needs at least two elements")
+    };
+    (@collect $kind:ident [$($done:expr,)+]) => {
+        $crate::noun!(@nest $($done,)+)
+    };
+
+    // Joins the `-`-separated parts of a symbol.
+    (@term $kind:ident [$($done:expr,)*] [$($part:expr),+] - $next:tt $($rest:tt)*) => {
+        $crate::noun!(@term $kind [$($done,)*] [$($part,)+ "-", stringify!($next)] $($rest)*)
+    };
+    (@term $kind:ident [$($done:expr,)*] [$($part:expr),+] $($rest:tt)*) => {
+        $crate::noun!(@collect $kind [$($done,)* $crate::noun::IntoNounElement::into_noun_element(concat!($($part),+)),] $($rest)*)
+    };
+
+    // Nests elements into cells from the right.
+    (@nest $last:expr,) => {
+        $last
+    };
+    (@nest $head:expr, $($rest:expr,)+) => {
+        $crate::Rc::<$crate::noun::Noun>::from($crate::cell::Cell::from([$head, $crate::noun!(@nest $($rest,)+)]))
+    };
+
+    () => {
+        compile_error!("a noun literal needs at least one element")
+    };
+    ($($tokens:tt)+) => {{
+        let noun: $crate::Rc<$crate::noun::Noun> = $crate::noun!(@collect top [] $($tokens)+);
+        $crate::Rc::try_unwrap(noun).unwrap_or_else(|noun| (*noun).clone())
+    }};
+}
+
+/// Encodes the length of an atom into a bitstream.
+fn encode_len<S: BitSink>(len: u64, bits: &mut S) -> Result<(), S::Error> {
+    let len_of_len = (u64::BITS - len.leading_zeros()) as usize;
+    bits.push_bits(0, len_of_len)?;
+    bits.push_bits(1, 1)?;
+    if len_of_len != 0 {
+        // Don't write the most significant bit of the length because it's always 1.
+        bits.push_bits(len, len_of_len - 1)?;
+    }
+    Ok(())
+}
+
+/// Encodes an atom into a bitstream.
+fn encode_atom<S: BitSink>(atom: &Atom, bits: &mut S) -> Result<(), S::Error> {
+    // Atom tag = 0b0.
+    bits.push_bits(0, 1)?;
+    encode_len(atom.bit_len() as u64, bits)?;
+    bits.push_atom(atom)
+}
+
+/// A hasher for keys that are a single word, like addresses, bit positions, and mugs,
+/// which is much cheaper than the default hasher for the large maps built by jam and cue.
+#[derive(Default)]
+pub(crate) struct WordHasher(u64);
+
+impl Hasher for WordHasher {
+    fn finish(&self) -> u64 {
+        // Fold the well-mixed high bits into the low bits, which are zero for aligned addresses.
+        self.0 ^ (self.0 >> 32)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.write_u64(u64::from(*byte));
         }
+    }
 
-        fn encode(noun: Rc<Noun>, bits: &mut AtomBuilder, cache: &mut HashMap<Rc<Noun>, u64>) {
-            if let Some(idx) = cache.get(&noun) {
-                if let Noun::Atom(ref atom) = *noun {
-                    let idx_bit_len = u64::from(u64::BITS - idx.leading_zeros());
-                    let atom_bit_len = atom.bit_len() as u64;
-                    // Backreferences to atoms are only encoded if they're shorter than the atom it
-                    // would reference.
-                    if atom_bit_len <= idx_bit_len {
-                        encode_atom(atom, bits);
-                        return;
-                    }
-                }
-                let idx = Atom::from(*idx);
-                // Backreference tag = 0b11.
-                bits.push_bit(true);
-                bits.push_bit(true);
-                encode_len(idx.bit_len() as u64, bits);
-                for bit in idx.iter() {
-                    bits.push_bit(bit);
+    fn write_u64(&mut self, word: u64) {
+        self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    }
+
+    fn write_usize(&mut self, word: usize) {
+        self.write_u64(word as u64);
+    }
+}
+
+/// A map keyed by single words. See [`WordHasher`].
+pub(crate) type WordMap<K, V> = HashMap<K, V, BuildHasherDefault<WordHasher>>;
+
+/// A subtree of a noun being jammed, keyed by its mug. Cells cache their mugs, so looking a cell up
+/// in the backreference cache doesn't traverse it.
+struct Subtree<'a> {
+    noun: &'a Noun,
+    mug: u32,
+}
+
+impl<'a> From<&'a Noun> for Subtree<'a> {
+    fn from(noun: &'a Noun) -> Self {
+        Self {
+            noun,
+            mug: noun.mug(),
+        }
+    }
+}
+
+impl Hash for Subtree<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(u64::from(self.mug));
+    }
+}
+
+impl PartialEq for Subtree<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.mug == other.mug && self.noun == other.noun
+    }
+}
+
+impl Eq for Subtree<'_> {}
+
+/// Encodes nouns into a bitstream in the reverse of their order in `nouns`, replacing repeated
+/// nouns with backreferences.
+pub(crate) fn encode<S: BitSink>(mut nouns: Vec<&Noun>, bits: &mut S) -> Result<(), S::Error> {
+    let mut cache = WordMap::<_, u64>::default();
+    while let Some(noun) = nouns.pop() {
+        let subtree = Subtree::from(noun);
+        if let Some(idx) = cache.get(&subtree) {
+            if let Noun::Atom(atom) = noun {
+                let idx_bit_len = u64::from(u64::BITS - idx.leading_zeros());
+                let atom_bit_len = atom.bit_len() as u64;
+                // Backreferences to atoms are only encoded if they're shorter than the atom it
+                // would reference.
+                if atom_bit_len <= idx_bit_len {
+                    encode_atom(atom, bits)?;
+                    continue;
                 }
-                return;
             }
+            let idx_bit_len = (u64::BITS - idx.leading_zeros()) as usize;
+            // Backreference tag = 0b11.
+            bits.push_bits(0b11, 2)?;
+            encode_len(idx_bit_len as u64, bits)?;
+            bits.push_bits(*idx, idx_bit_len)?;
+            continue;
+        }
 
-            cache.insert(noun.clone(), bits.pos() as u64);
-            match *noun {
-                Noun::Atom(ref atom) => encode_atom(atom, bits),
-                Noun::Cell(ref cell) => {
-                    // Cell tag = 0b01.
-                    bits.push_bit(true);
-                    bits.push_bit(false);
-                    encode(cell.head(), bits, cache);
-                    encode(cell.tail(), bits, cache);
-                }
+        cache.insert(subtree, bits.pos());
+        match noun {
+            Noun::Atom(atom) => encode_atom(atom, bits)?,
+            Noun::Cell(cell) => {
+                // Cell tag = 0b01.
+                bits.push_bits(0b01, 2)?;
+                nouns.push(cell.tail_ref());
+                nouns.push(cell.head_ref());
             }
         }
+    }
+    Ok(())
+}
+
+/// Unwraps the result of writing to an [atom builder](crate::atom::Builder), which can't fail.
+fn infallible<T>(result: Result<T, Infallible>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(never) => match never {},
+    }
+}
 
-        let noun = Rc::new(self);
+impl Jam for &Noun {
+    fn jam(self) -> Atom {
         let mut bits = Atom::builder();
-        let mut cache = HashMap::new();
-        encode(noun, &mut bits, &mut cache);
+        infallible(encode(vec![self], &mut bits));
         bits.into_atom()
     }
 }
 
-impl PartialEq for Noun {
-    fn eq(&self, other: &Self) -> bool {
-        if let (Self::Atom(this), Self::Atom(that)) = (self, other) {
-            this == that
-        } else if let (Self::Cell(this), Self::Cell(that)) = (self, other) {
-            this == that
-        } else {
-            false
-        }
+impl Jam for Noun {
+    fn jam(self) -> Atom {
+        (&self).jam()
+    }
+}
+
+impl Jam for Rc<Noun> {
+    fn jam(self) -> Atom {
+        (&*self).jam()
+    }
+}
+
+impl Jam for &Atom {
+    fn jam(self) -> Atom {
+        let mut bits = Atom::builder();
+        infallible(encode_atom(self, &mut bits));
+        bits.into_atom()
+    }
+}
+
+impl Jam for Atom {
+    fn jam(self) -> Atom {
+        (&self).jam()
+    }
+}
+
+impl Jam for &Cell {
+    fn jam(self) -> Atom {
+        // The top level cell can't appear within itself, so it never needs to be cached.
+        let mut bits = Atom::builder();
+        // Cell tag = 0b01.
+        bits.push_bits(0b01, 2);
+        infallible(encode(vec![self.tail_ref(), self.head_ref()], &mut bits));
+        bits.into_atom()
+    }
+}
+
+impl Jam for Cell {
+    fn jam(self) -> Atom {
+        (&self).jam()
     }
 }
 
@@ -303,6 +932,285 @@ unsafe impl Sync for Noun {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::atom::Builder as AtomBuilder;
+
+    #[test]
+    fn slot() {
+        // [[4 5] [6 14 15]]
+        let noun = Noun::from(Cell::from([
+            Noun::from(Cell::from([4u8, 5u8])),
+            Noun::from(Cell::from([6u8, 14u8, 15u8])),
+        ]));
+        assert_eq!(noun.slot(0), None);
+        assert_eq!(noun.slot(1), Some(&noun));
+        assert_eq!(noun.slot(2), Some(&Noun::from(Cell::from([4u8, 5u8]))));
+        assert_eq!(
+            noun.slot(3),
+            Some(&Noun::from(Cell::from([6u8, 14u8, 15u8])))
+        );
+        assert_eq!(noun.slot(4), Some(&Noun::from(Atom::from(4u8))));
+        assert_eq!(noun.slot(5), Some(&Noun::from(Atom::from(5u8))));
+        assert_eq!(noun.slot(6), Some(&Noun::from(Atom::from(6u8))));
+        assert_eq!(noun.slot(14), Some(&Noun::from(Atom::from(14u8))));
+        assert_eq!(noun.slot(15), Some(&Noun::from(Atom::from(15u8))));
+        assert_eq!(noun.slot(8), None);
+        assert_eq!(noun.slot(12), None);
+
+        // Axes that descend through a long chain of tails, including one wider than 64 bits.
+        {
+            let list = Noun::from(Cell::from([
+                0u8, 1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8, 11u8, 12u8, 13u8, 14u8,
+                15u8, 16u8, 17u8, 18u8, 19u8, 20u8, 21u8, 22u8, 23u8, 24u8, 25u8, 26u8, 27u8, 28u8,
+                29u8,
+            ]));
+            // The last element of a 30-element tuple is at axis 2^30 - 1, and the element before it
+            // is at axis 2^30 - 2.
+            assert_eq!(
+                list.slot_atom(&Atom::from((1u64 << 30) - 2)),
+                Some(&Noun::from(Atom::from(28u8)))
+            );
+            assert_eq!(list.slot_atom(&Atom::from((1u128 << 100) - 1)), None);
+        }
+    }
+
+    #[test]
+    fn edit() {
+        let noun = Rc::<Noun>::from(Cell::from([
+            Noun::from(Cell::from([4u8, 5u8])),
+            Noun::from(Cell::from([6u8, 14u8, 15u8])),
+        ]));
+        let value = Rc::<Noun>::from(Atom::from(99u8));
+
+        assert_eq!(noun.edit(0, value.clone()), None);
+        assert_eq!(noun.edit(8, value.clone()), None);
+        assert!(Rc::ptr_eq(&noun.edit(1, value.clone()).unwrap(), &value));
+
+        let edited = noun.edit(14, value.clone()).expect("edit");
+        assert_eq!(
+            *edited,
+            Noun::from(Cell::from([
+                Noun::from(Cell::from([4u8, 5u8])),
+                Noun::from(Cell::from([6u8, 99u8, 15u8])),
+            ]))
+        );
+        // Untouched branches are shared rather than copied.
+        if let (Noun::Cell(edited), Noun::Cell(noun)) = (&*edited, &*noun) {
+            assert!(Rc::ptr_eq(&edited.head(), &noun.head()));
+        } else {
+            panic!("unexpected atom");
+        }
+        assert_eq!(edited.slot(14), Some(&*value));
+    }
+
+    #[test]
+    fn mug() {
+        // Test vectors from the Urbit runtime's test suite and the `++mug` documentation.
+        {
+            let atom = |atom: Atom| Noun::from(atom).mug();
+            assert_eq!(atom(Atom::from(0u8)), 0x79ff_04e8);
+            assert_eq!(atom(Atom::from(1u8)), 0x715c_2a60);
+            assert_eq!(atom(Atom::from(2u8)), 0x718b_9468);
+            assert_eq!(atom(Atom::from(10_000u16)), 795_713_195);
+            assert_eq!(atom(Atom::from(10_001u16)), 420_521_697);
+            assert_eq!(atom(Atom::from("Hello, world!")), 0x4d44_1035);
+            assert_eq!(
+                atom(Atom::from("xxxxxxxxxxxxxxxxxxxxxxxxxxxx")),
+                0x64df_da5c
+            );
+        }
+
+        {
+            let cell = |cell: Cell| Noun::from(cell).mug();
+            assert_eq!(cell(Cell::from([0u8, 0u8])), 0x192f_5588);
+            assert_eq!(cell(Cell::from([1u8, 1u8])), 0x6b32_ec46);
+            assert_eq!(cell(Cell::from([2u8, 2u8])), 0x02ef_fe10);
+            assert_eq!(cell(Cell::from([1u8, 2u8, 3u8])), 0x3a81_1aec);
+        }
+    }
+
+    /// Jams a noun a single bit at a time, exactly as [`Jam`] did before it wrote whole words and
+    /// atoms at a time.
+    fn jam_bitwise(noun: &Noun) -> Atom {
+        fn encode_len(mut len: u64, bits: &mut AtomBuilder) {
+            let len_of_len = u64::BITS - len.leading_zeros();
+            for _ in 0..len_of_len {
+                bits.push_bit(false);
+            }
+            bits.push_bit(true);
+            if len_of_len != 0 {
+                while len != 1 {
+                    bits.push_bit((len & 1) != 0);
+                    len >>= 1;
+                }
+            }
+        }
+
+        fn encode_atom(atom: &Atom, bits: &mut AtomBuilder) {
+            bits.push_bit(false);
+            encode_len(atom.bit_len() as u64, bits);
+            for bit in atom.iter() {
+                bits.push_bit(bit);
+            }
+        }
+
+        fn encode<'a>(noun: &'a Noun, bits: &mut AtomBuilder, cache: &mut HashMap<&'a Noun, u64>) {
+            if let Some(idx) = cache.get(noun) {
+                if let Noun::Atom(atom) = noun {
+                    if atom.bit_len() as u64 <= u64::from(u64::BITS - idx.leading_zeros()) {
+                        encode_atom(atom, bits);
+                        return;
+                    }
+                }
+                let idx = Atom::from(*idx);
+                bits.push_bit(true);
+                bits.push_bit(true);
+                encode_len(idx.bit_len() as u64, bits);
+                for bit in idx.iter() {
+                    bits.push_bit(bit);
+                }
+                return;
+            }
+            cache.insert(noun, bits.pos() as u64);
+            match noun {
+                Noun::Atom(atom) => encode_atom(atom, bits),
+                Noun::Cell(cell) => {
+                    bits.push_bit(true);
+                    bits.push_bit(false);
+                    encode(cell.head_ref(), bits, cache);
+                    encode(cell.tail_ref(), bits, cache);
+                }
+            }
+        }
+
+        let mut bits = Atom::builder();
+        encode(noun, &mut bits, &mut HashMap::new());
+        bits.into_atom()
+    }
+
+    #[test]
+    fn order() {
+        let atom = |n: u16| Noun::from(Atom::from(n));
+        let cell = |head: u16, tail: u16| Noun::from(Cell::from([head, tail]));
+
+        let mut nouns = vec![
+            cell(1, 2),
+            atom(300),
+            cell(0, 5),
+            atom(2),
+            Noun::from(Cell::from([cell(0, 0), atom(0)])),
+            cell(1, 1),
+            atom(0),
+        ];
+        nouns.sort();
+        assert_eq!(
+            nouns,
+            [
+                atom(0),
+                atom(2),
+                atom(300),
+                cell(0, 5),
+                cell(1, 1),
+                cell(1, 2),
+                Noun::from(Cell::from([cell(0, 0), atom(0)])),
+            ]
+        );
+        for pair in nouns.windows(2) {
+            assert!(pair[0].dor(&pair[1]) && !pair[1].dor(&pair[0]));
+            assert!(pair[0].dor(&pair[0]));
+        }
+
+        // Cords sort alphabetically by `aor` but by length first by `dor`.
+        let cord = |cord: &str| Noun::from(Atom::from(cord));
+        let mut cords = ["b", "abc", "ab", "a", "ba"].map(cord);
+        cords.sort_by(|a, b| match (a.aor(b), b.aor(a)) {
+            (true, true) => std::cmp::Ordering::Equal,
+            (true, false) => std::cmp::Ordering::Less,
+            _ => std::cmp::Ordering::Greater,
+        });
+        assert_eq!(cords, ["a", "ab", "abc", "b", "ba"].map(cord));
+        assert!(cord("b").dor(&cord("ab")));
+        assert!(atom(7).aor(&cell(0, 0)) && !cell(0, 0).aor(&atom(7)));
+        assert!(cell(1, 2).aor(&cell(1, 3)) && cell(1, 3).aor(&cell(2, 0)));
+
+        // `(dor a b)`, `(aor a b)`, `(gor a b)` and `(mor a b)` in Hoon. Swapping `a` and `b` flips
+        // each result.
+        for (a, b, expected) in [
+            (atom(1), atom(2), [true, true, true, false]),
+            (atom(7), cell(1, 2), [true, true, true, true]),
+            (cell(3, 4), cell(5, 6), [true, true, true, false]),
+            (cord("foo"), cord("bar"), [true, false, false, false]),
+            (atom(0), cell(0, 0), [true, true, false, false]),
+            (cord("ab"), cord("b"), [false, true, false, false]),
+            (atom(300), cell(1, 1), [true, true, true, true]),
+        ] {
+            let order = |a: &Noun, b: &Noun| [a.dor(b), a.aor(b), a.gor(b), a.mor(b)];
+            assert_eq!(order(&a, &b), expected, "{} {}", a, b);
+            assert_eq!(order(&b, &a), expected.map(|x| !x), "{} {}", b, a);
+            assert!(a.gor(&a) && a.mor(&a));
+        }
+    }
+
+    #[test]
+    fn jam_cue_bulk() {
+        // A list of atoms of widths from 0 to 900 bits, with repeated atoms and cells so that
+        // backreferences land at unaligned positions, followed by a few large atoms.
+        let mut elems = Vec::new();
+        for i in 0..300usize {
+            let atom = (Atom::from(1u8) << (3 * i)) | Atom::from(i);
+            elems.push(Rc::<Noun>::from(atom.clone()));
+            if i % 7 == 0 {
+                elems.push(elems[i / 2].clone());
+                elems.push(Rc::<Noun>::from(Cell::from([atom.clone(), atom])));
+            }
+        }
+        elems.push(Rc::<Noun>::from(Atom::from(vec![0xa5; 10_000])));
+        elems.push(Rc::<Noun>::from(Atom::from(vec![0x5a; 10_001])));
+        elems.push(Rc::<Noun>::from(Atom::null()));
+        let noun = Noun::from(Cell::from(elems));
+
+        let jammed = (&noun).jam();
+        assert_eq!(jammed, jam_bitwise(&noun));
+        assert_eq!(Noun::cue(jammed).expect("cue"), noun);
+    }
+
+    #[test]
+    fn jam_cue_types() {
+        let nouns = [
+            Noun::from(Atom::from(0u8)),
+            Noun::from(Atom::from(581_949_002u32)),
+            Noun::from(Cell::from([10_000u16, 10_000u16])),
+            Noun::from(Cell::from([
+                Cell::from(["vary", "Origin"]),
+                Cell::from(["vary", "Accept-Encoding"]),
+            ])),
+        ];
+        for noun in nouns {
+            let jammed = (&noun).jam();
+            assert_eq!(noun.clone().jam(), jammed);
+            assert_eq!(Rc::new(noun.clone()).jam(), jammed);
+            assert_eq!(*Rc::<Noun>::cue(jammed.clone()).expect("cue"), noun);
+            match noun {
+                Noun::Atom(atom) => {
+                    assert_eq!((&atom).jam(), jammed);
+                    assert_eq!(atom.clone().jam(), jammed);
+                    assert_eq!(Atom::cue(jammed.clone()).expect("cue"), atom);
+                    assert!(matches!(
+                        Cell::cue(jammed),
+                        Err(serdes::Error::UnexpectedAtom)
+                    ));
+                }
+                Noun::Cell(cell) => {
+                    assert_eq!((&cell).jam(), jammed);
+                    assert_eq!(cell.clone().jam(), jammed);
+                    assert_eq!(Cell::cue(jammed.clone()).expect("cue"), cell);
+                    assert!(matches!(
+                        Atom::cue(jammed),
+                        Err(serdes::Error::UnexpectedCell)
+                    ));
+                }
+            }
+        }
+    }
 
     #[test]
     fn jam_cue_atom() {
@@ -772,4 +1680,264 @@ mod tests {
             assert_eq!(Noun::cue(jammed_cell).expect("cue"), cell);
         }
     }
+
+    #[test]
+    fn long_list() {
+        const LEN: u32 = 1_000_000;
+
+        fn list(len: u32) -> Rc<Noun> {
+            let mut list = Rc::new(Noun::null());
+            for i in (0..len).rev() {
+                list = Rc::<Noun>::from(Cell::from([Rc::<Noun>::from(Atom::from(i)), list]));
+            }
+            list
+        }
+
+        // A list nested in heads rather than tails.
+        fn nested(len: u32) -> Rc<Noun> {
+            let mut noun = Rc::new(Noun::null());
+            for _ in 0..len {
+                noun = Rc::<Noun>::from(Cell::from([noun, Rc::new(Noun::null())]));
+            }
+            noun
+        }
+
+        let noun = list(LEN);
+        let cued = Rc::<Noun>::cue(noun.clone().jam()).expect("cue");
+        assert_eq!(cued, noun);
+        assert_ne!(cued, list(LEN - 1));
+
+        let printed = cued.to_string();
+        let first = format!("[{} {} ", Atom::from(0u8), Atom::from(1u8));
+        let last = format!(" {} {}]", Atom::from(LEN - 1), Atom::null());
+        assert!(printed.starts_with(&first));
+        assert!(printed.ends_with(&last));
+        drop(cued);
+        drop(noun);
+
+        let noun = nested(LEN);
+        assert_eq!(noun, nested(LEN));
+        assert!(noun.to_string().starts_with("[[[["));
+    }
+
+    #[test]
+    fn cue_limits() {
+        fn cue(jammed: &Atom, limits: CueLimits) -> serdes::Result<Noun> {
+            Noun::cue_with_limits(jammed.clone(), &limits)
+        }
+
+        let unlimited = CueLimits::unlimited();
+
+        // [[1 2] 300 [1 2]], whose second [1 2] is a backreference.
+        let pair = Noun::from(Cell::from([1u8, 2u8]));
+        let noun = Noun::from(Cell::from([
+            pair.clone(),
+            Noun::from(Atom::from(300u16)),
+            pair,
+        ]));
+        let jammed = noun.clone().jam();
+        assert_eq!(cue(&jammed, unlimited.clone()).expect("cue"), noun);
+
+        let limits = CueLimits {
+            max_depth: 2,
+            max_atom_bits: 9,
+            max_cells: 3,
+            max_total_bits: jammed.bit_len(),
+        };
+        assert_eq!(cue(&jammed, limits.clone()).expect("cue"), noun);
+        assert!(matches!(
+            cue(
+                &jammed,
+                CueLimits {
+                    max_depth: 1,
+                    ..limits.clone()
+                }
+            ),
+            Err(serdes::Error::MaxDepthExceeded { .. })
+        ));
+        assert!(matches!(
+            cue(
+                &jammed,
+                CueLimits {
+                    max_atom_bits: 8,
+                    ..limits.clone()
+                }
+            ),
+            Err(serdes::Error::MaxAtomBitsExceeded { .. })
+        ));
+        assert!(matches!(
+            cue(
+                &jammed,
+                CueLimits {
+                    max_cells: 2,
+                    ..limits.clone()
+                }
+            ),
+            Err(serdes::Error::MaxCellsExceeded { .. })
+        ));
+        assert!(matches!(
+            cue(
+                &jammed,
+                CueLimits {
+                    max_total_bits: jammed.bit_len() - 1,
+                    ..limits
+                }
+            ),
+            Err(serdes::Error::MaxTotalBitsExceeded)
+        ));
+
+        // An atom that claims to be 2^61 - 1 bits long.
+        let mut bits = Atom::builder();
+        bits.push_bit(false);
+        bits.push_bits(0, 61);
+        bits.push_bit(true);
+        bits.push_bits(u64::MAX, 60);
+        let jammed = bits.into_atom();
+        // `Cue::cue()` decodes with the default limits.
+        for result in [
+            cue(&jammed, CueLimits::default()),
+            Noun::cue(jammed.clone()),
+            Rc::<Noun>::cue(jammed.clone()).map(|noun| (*noun).clone()),
+        ] {
+            assert!(matches!(
+                result,
+                Err(serdes::Error::MaxAtomBitsExceeded {
+                    pos: 0,
+                    entity: Entity::Atom
+                })
+            ));
+        }
+        // Without limits, decoding only fails once the input runs out.
+        assert!(matches!(
+            cue(&jammed, unlimited),
+            Err(serdes::Error::UnexpectedEof {
+                pos: 0,
+                entity: Entity::Atom
+            })
+        ));
+    }
+
+    #[test]
+    fn cue_errors() {
+        // Nothing to decode.
+        let err = Noun::cue(Atom::null()).unwrap_err();
+        assert!(matches!(
+            err,
+            serdes::Error::UnexpectedEof {
+                pos: 0,
+                entity: Entity::Tag
+            }
+        ));
+
+        // [0 19] with the last four bits of 19 missing.
+        let jammed = Cell::from([0u8, 19u8]).jam();
+        let truncated = jammed.end(0, 12);
+        let err = Noun::cue(truncated).unwrap_err();
+        assert!(matches!(
+            err,
+            serdes::Error::UnexpectedEof {
+                pos: 4,
+                entity: Entity::Atom
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "the jammed noun ended partway through the atom at bit 4"
+        );
+
+        // [0 19] with the tail missing.
+        let err = Noun::cue(jammed.end(0, 4)).unwrap_err();
+        assert!(matches!(
+            err,
+            serdes::Error::UnexpectedEof {
+                pos: 0,
+                entity: Entity::Cell
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "the jammed noun ended partway through the cell at bit 0"
+        );
+
+        // [0 <backreference to bit 1>].
+        let mut bits = Atom::builder();
+        bits.push_bits(0b01, 2);
+        bits.push_bits(0b10, 2);
+        bits.push_bits(0b11, 2);
+        bits.push_bits(0b110, 3);
+        let err = Noun::cue(bits.into_atom()).unwrap_err();
+        assert!(matches!(err, serdes::Error::CacheMiss { pos: 4, idx: 1 }));
+        assert_eq!(
+            err.to_string(),
+            "the backreference at bit 4 refers to bit 1, where no noun begins"
+        );
+
+        // A backreference whose length is at least 2^64.
+        let mut bits = Atom::builder();
+        bits.push_bits(0b11, 2);
+        bits.push_bits(0, 64);
+        bits.push_bits(0b10, 2);
+        let err = Noun::cue(bits.into_atom()).unwrap_err();
+        assert!(matches!(
+            err,
+            serdes::Error::InvalidLen {
+                pos: 0,
+                entity: Entity::Backref
+            }
+        ));
+    }
+
+    #[test]
+    fn noun_macro() {
+        assert_eq!(noun![0], Noun::null());
+        assert_eq!(noun![~], Noun::null());
+        assert_eq!(noun![1 2], Noun::from(Cell::from([1u8, 2u8])));
+        assert_eq!(noun![1 2 3], noun![1 [2 3]]);
+        assert_eq!(
+            noun![[1 2] 3],
+            Noun::from(Cell::from([
+                Rc::<Noun>::from(Cell::from([1u8, 2u8])),
+                Rc::<Noun>::from(Atom::from(3u8)),
+            ]))
+        );
+        assert_eq!(
+            noun![%x-cached "HIT"],
+            Noun::from(Cell::from(["x-cached", "HIT"]))
+        );
+        assert_eq!(
+            noun![%a-2 %type %.y %.n],
+            Noun::from(Cell::from([
+                Atom::from("a-2"),
+                Atom::from("type"),
+                Atom::null(),
+                Atom::from(1u8),
+            ]))
+        );
+        assert_eq!(
+            noun![0xffff_ffff_ffff_ffff_ffffu128 "é" ~],
+            Noun::from(Cell::from([
+                Atom::from(0xffff_ffff_ffff_ffff_ffffu128),
+                Atom::from("é"),
+                Atom::null(),
+            ]))
+        );
+
+        // Interpolated nouns are shared rather than copied.
+        let shared = Rc::<Noun>::from(Cell::from([4u8, 5u8]));
+        let atom = Atom::from(6u8);
+        let noun = noun![(Rc::clone(&shared)) [(&shared) atom] (Noun::null())];
+        assert_eq!(noun, noun![[4 5] [[4 5] 6] 0]);
+        let Noun::Cell(cell) = &noun else {
+            panic!("expected a cell");
+        };
+        let Noun::Cell(tail) = cell.tail_ref() else {
+            panic!("expected a cell");
+        };
+        let Noun::Cell(tail_head) = tail.head_ref() else {
+            panic!("expected a cell");
+        };
+        assert!(Rc::ptr_eq(&cell.head(), &shared));
+        assert!(Rc::ptr_eq(&tail_head.head(), &shared));
+        assert_eq!(noun![(Rc::clone(&shared))], *shared);
+    }
 }
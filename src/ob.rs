@@ -0,0 +1,424 @@
+//! Urbit's phonetic base: the `@p` and `@q` auras.
+//!
+//! Both auras spell an atom as a sequence of syllables, one per byte, alternating between 256
+//! prefix syllables and 256 suffix syllables. A byte on its own is spelled with a suffix (`~nec`),
+//! and a pair of bytes with a prefix followed by a suffix (`~marzod`).
+//!
+//! `@p` spells ship names. Atoms of up to 64 bits are first scrambled with [`fein`] so that
+//! adjacent planets get dissimilar names, then spelled as 16-bit words separated by `-`, with every
+//! fourth word separated by `--` instead. Galaxies and stars (atoms of up to 16 bits) are left
+//! unscrambled, as are comets (atoms wider than 64 bits).
+//!
+//! `@q` spells arbitrary atoms without scrambling, as the big-endian bytes of the atom paired into
+//! words separated by `-`.
+//!
+//! See [`Atom::to_patp`], [`Atom::from_patp`], [`Atom::to_patq`] and [`Atom::from_patq`].
+//!
+//! [`Atom::to_patp`]: crate::atom::Atom::to_patp
+//! [`Atom::from_patp`]: crate::atom::Atom::from_patp
+//! [`Atom::to_patq`]: crate::atom::Atom::to_patq
+//! [`Atom::from_patq`]: crate::atom::Atom::from_patq
+
+use crate::{atom::Atom, mug};
+use std::{
+    fmt::{self, Display, Formatter},
+    result,
+};
+
+/// The 256 prefix syllables, in order.
+const PREFIXES: &str = concat!(
+    "dozmarbinwansamlitsighidfidlissogdirwacsabwissib",
+    "rigsoldopmodfoglidhopdardorlorhodfolrintogsilmir",
+    "holpaslacrovlivdalsatlibtabhanticpidtorbolfosdot",
+    "losdilforpilramtirwintadbicdifrocwidbisdasmidlop",
+    "rilnardapmolsanlocnovsitnidtipsicropwitnatpanmin",
+    "ritpodmottamtolsavposnapnopsomfinfonbanmorworsip",
+    "ronnorbotwicsocwatdolmagpicdavbidbaltimtasmallig",
+    "sivtagpadsaldivdactansidfabtarmonranniswolmispal",
+    "lasdismaprabtobrollatlonnodnavfignomnibpagsopral",
+    "bilhaddocridmocpacravripfaltodtiltinhapmicfanpat",
+    "taclabmogsimsonpinlomrictapfirhasbosbatpochactid",
+    "havsaplindibhosdabbitbarracparloddosbortochilmac",
+    "tomdigfilfasmithobharmighinradmashalraglagfadtop",
+    "mophabnilnosmilfopfamdatnoldinhatnacrisfotribhoc",
+    "nimlarfitwalrapsarnalmoslandondanladdovrivbacpol",
+    "laptalpitnambonrostonfodponsovnocsorlavmatmipfip",
+);
+
+/// The 256 suffix syllables, in order.
+const SUFFIXES: &str = concat!(
+    "zodnecbudwessevpersutletfulpensytdurwepserwylsun",
+    "rypsyxdyrnuphebpeglupdepdysputlughecryttyvsydnex",
+    "lunmeplutseppesdelsulpedtemledtulmetwenbynhexfeb",
+    "pyldulhetmevruttylwydtepbesdexsefwycburderneppur",
+    "rysrebdennutsubpetrulsynregtydsupsemwynrecmegnet",
+    "secmulnymtevwebsummutnyxrextebfushepbenmuswyxsym",
+    "selrucdecwexsyrwetdylmynmesdetbetbeltuxtugmyrpel",
+    "syptermebsetdutdegtexsurfeltudnuxruxrenwytnubmed",
+    "lytdusnebrumtynseglyxpunresredfunrevrefmectedrus",
+    "bexlebduxrynnumpyxrygryxfeptyrtustyclegnemfermer",
+    "tenlusnussyltecmexpubrymtucfyllepdebbermughuttun",
+    "bylsudpemdevlurdefbusbeprunmelpexdytbyttyplevmyl",
+    "wedducfurfexnulluclennerlexrupnedlecrydlydfenwel",
+    "nydhusrelrudneshesfetdesretdunlernyrsebhulryllud",
+    "remlysfynwerrycsugnysnyllyndyndemluxfedsedbecmun",
+    "lyrtesmudnytbyrsenwegfyrmurtelreptegpecnelnevfes",
+);
+
+/// Seeds of the round functions of the Feistel cipher used by [`fein`] and [`fynd`].
+const RAKU: [u32; 4] = [0xb76d_5eed, 0xee28_1300, 0x85bc_ae01, 0x4b38_7af7];
+
+/// Left modulus of the Feistel cipher.
+const A: u64 = 0xffff;
+
+/// Right modulus of the Feistel cipher.
+const B: u64 = 0x1_0000;
+
+/// Errors that occur when parsing `@p` or `@q` strings.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The string didn't begin with `~`.
+    MissingSig,
+    /// The syllable at byte offset `pos` of the string wasn't a syllable of the right kind, or was
+    /// missing.
+    InvalidSyllable { pos: usize },
+    /// The string spelled an atom, but not in that atom's canonical form, such as `~doznec` rather
+    /// than `~nec` for `@q` or `~sampel--palnet` rather than `~sampel-palnet` for `@p`.
+    NonCanonical,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
+        match self {
+            Self::MissingSig => write!(f, "the name does not begin with `~`"),
+            Self::InvalidSyllable { pos } => write!(f, "invalid syllable at byte {}", pos),
+            Self::NonCanonical => write!(f, "the name is not in canonical form"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A specialized [`Result`] type for phonetic base operations that return [`ob::Error`] on error.
+///
+/// [`ob::Error`]: [`Error`]
+pub type Result<T> = result::Result<T, Error>;
+
+/// Scrambles a ship, the permutation `@p` applies to atoms of up to 64 bits (`++fein`).
+///
+/// Only the low 32 bits of ships of more than 16 bits are scrambled, so a moon shares the high 32
+/// bits of its unscrambled form. Galaxies and stars are unchanged.
+///
+/// # Examples
+/// ```
+/// # use noun::ob::{fein, fynd};
+/// assert_eq!(fein(0xff), 0xff);
+/// assert_eq!(fein(0x1_0000), 0x423e_60bf);
+/// assert_eq!(fynd(fein(1_624_961_343)), 1_624_961_343);
+/// ```
+pub fn fein(pyn: u64) -> u64 {
+    match pyn {
+        0x1_0000..=0xffff_ffff => 0x1_0000 + cycle(pyn - 0x1_0000, encrypt),
+        0x1_0000_0000.. => (pyn & 0xffff_ffff_0000_0000) | fein(pyn & 0xffff_ffff),
+        _ => pyn,
+    }
+}
+
+/// Unscrambles a ship, the inverse of [`fein`] (`++fynd`).
+pub fn fynd(cry: u64) -> u64 {
+    match cry {
+        0x1_0000..=0xffff_ffff => 0x1_0000 + cycle(cry - 0x1_0000, decrypt),
+        0x1_0000_0000.. => (cry & 0xffff_ffff_0000_0000) | fynd(cry & 0xffff_ffff),
+        _ => cry,
+    }
+}
+
+/// Applies `cipher` to `m`, applying it again if the result falls outside of the 32-bit domain of
+/// the permutation (cycle walking).
+fn cycle(m: u64, cipher: fn(u64) -> u64) -> u64 {
+    let c = cipher(m);
+    if c < 0xffff_ffff {
+        c
+    } else {
+        cipher(c)
+    }
+}
+
+/// The round function of the Feistel cipher: a MurmurHash3 of the low 16 bits of `arg`.
+fn round(j: usize, arg: u64) -> u64 {
+    u64::from(mug::murmur3(&[arg as u8, (arg >> 8) as u8], RAKU[j]))
+}
+
+/// Encrypts `m` with a four-round Feistel cipher over the mixed radix `A * B`.
+fn encrypt(m: u64) -> u64 {
+    let (mut ell, mut arr) = (m % A, m / A);
+    for j in 1..=RAKU.len() {
+        let modulus = if j % 2 == 1 { A } else { B };
+        let tmp = (ell + round(j - 1, arr)) % modulus;
+        (ell, arr) = (arr, tmp);
+    }
+    if arr == A {
+        A * arr + ell
+    } else {
+        A * ell + arr
+    }
+}
+
+/// Decrypts `m`, the inverse of [`encrypt`].
+fn decrypt(m: u64) -> u64 {
+    let (ahh, ale) = (m % A, m / A);
+    let (mut ell, mut arr) = if ale == A { (ahh, ale) } else { (ale, ahh) };
+    for j in (1..=RAKU.len()).rev() {
+        let modulus = if j % 2 == 1 { A } else { B };
+        let tmp = (arr + modulus - round(j - 1, ell) % modulus) % modulus;
+        (ell, arr) = (tmp, ell);
+    }
+    A * arr + ell
+}
+
+/// Returns the `n`th prefix syllable.
+fn prefix(n: u8) -> &'static str {
+    let i = usize::from(n) * 3;
+    &PREFIXES[i..i + 3]
+}
+
+/// Returns the `n`th suffix syllable.
+fn suffix(n: u8) -> &'static str {
+    let i = usize::from(n) * 3;
+    &SUFFIXES[i..i + 3]
+}
+
+/// Returns the index of `syllable` in `syllables`.
+fn index(syllables: &str, syllable: Option<&str>) -> Option<u8> {
+    let syllable = syllable?;
+    (0..=u8::MAX).find(|n| {
+        let i = usize::from(*n) * 3;
+        &syllables[i..i + 3] == syllable
+    })
+}
+
+/// Spells `atom` as `@p`.
+pub(crate) fn patp(atom: &Atom) -> String {
+    let scrambled;
+    let atom = match atom.as_u64() {
+        Some(pyn) => {
+            scrambled = Atom::from(fein(pyn));
+            &scrambled
+        }
+        None => atom,
+    };
+    let bytes = atom.as_bytes();
+    if bytes.len() <= 1 {
+        return format!("~{}", suffix(bytes.first().copied().unwrap_or(0)));
+    }
+
+    let words: Vec<_> = bytes.chunks(2).collect();
+    let mut name = String::from("~");
+    for (i, word) in words.iter().enumerate().rev() {
+        name.push_str(prefix(word.get(1).copied().unwrap_or(0)));
+        name.push_str(suffix(word[0]));
+        if i > 0 {
+            name.push_str(if i % 4 == 0 { "--" } else { "-" });
+        }
+    }
+    name
+}
+
+/// Parses `name` as `@p`.
+pub(crate) fn parse_patp(name: &str) -> Result<Atom> {
+    let atom = parse_syllables(name)?;
+    let atom = match atom.as_u64() {
+        Some(cry) => Atom::from(fynd(cry)),
+        None => atom,
+    };
+    if patp(&atom) == name {
+        Ok(atom)
+    } else {
+        Err(Error::NonCanonical)
+    }
+}
+
+/// Spells `atom` as `@q`.
+pub(crate) fn patq(atom: &Atom) -> String {
+    let mut bytes = atom.to_vec();
+    if bytes.is_empty() {
+        bytes.push(0);
+    }
+    bytes.reverse();
+    if bytes.len() == 1 {
+        return format!("~{}", suffix(bytes[0]));
+    }
+
+    // An odd number of bytes leaves the most significant byte on its own, which is spelled as a
+    // word with a zero high byte.
+    let mut name = String::from("~");
+    if bytes.len() % 2 == 1 {
+        bytes.insert(0, 0);
+    }
+    for (i, word) in bytes.chunks(2).enumerate() {
+        if i > 0 {
+            name.push('-');
+        }
+        name.push_str(prefix(word[0]));
+        name.push_str(suffix(word[1]));
+    }
+    name
+}
+
+/// Parses `name` as `@q`.
+pub(crate) fn parse_patq(name: &str) -> Result<Atom> {
+    let atom = parse_syllables(name)?;
+    if patq(&atom) == name {
+        Ok(atom)
+    } else {
+        Err(Error::NonCanonical)
+    }
+}
+
+/// Parses the syllables of `name` into an atom, without regard to whether the separators between
+/// them are canonical.
+fn parse_syllables(name: &str) -> Result<Atom> {
+    let Some(body) = name.strip_prefix('~') else {
+        return Err(Error::MissingSig);
+    };
+
+    let mut bytes = Vec::new();
+    let mut pos = 1;
+    for word in body.split('-') {
+        match word.len() {
+            0 => {}
+            3 if body.len() == 3 => {
+                bytes.push(index(SUFFIXES, Some(word)).ok_or(Error::InvalidSyllable { pos })?)
+            }
+            6 => {
+                bytes.push(index(PREFIXES, word.get(..3)).ok_or(Error::InvalidSyllable { pos })?);
+                bytes.push(
+                    index(SUFFIXES, word.get(3..))
+                        .ok_or(Error::InvalidSyllable { pos: pos + 3 })?,
+                );
+            }
+            _ => return Err(Error::InvalidSyllable { pos }),
+        }
+        pos += word.len() + 1;
+    }
+    if bytes.is_empty() {
+        return Err(Error::InvalidSyllable { pos: 1 });
+    }
+    bytes.reverse();
+    Ok(Atom::from(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn syllables() {
+        for syllables in [PREFIXES, SUFFIXES] {
+            assert_eq!(syllables.len(), 256 * 3);
+            for n in 0..=u8::MAX {
+                let i = usize::from(n) * 3;
+                assert_eq!(index(syllables, Some(&syllables[i..i + 3])), Some(n));
+            }
+        }
+    }
+
+    #[test]
+    fn fein_fynd() {
+        assert_eq!(fein(0), 0);
+        assert_eq!(fein(0xffff), 0xffff);
+        assert_eq!(fein(0x1_0000), 0x423e_60bf);
+        assert_eq!(fein(1_624_961_343), 0x046f_7f4f);
+        assert_eq!(fein(0xffff_ffff), 0xbba4_dcce);
+        assert_eq!(fein(0x1_0000_ffff), 0x1_0000_ffff);
+        assert_eq!(fein(0x1234_0001_0000), 0x1234_423e_60bf);
+
+        let mut x = 0x9e37_79b9_7f4a_7c15u64;
+        for _ in 0..10_000 {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            assert_eq!(fynd(fein(x)), x);
+            assert_eq!(fein(fynd(x)), x);
+        }
+    }
+
+    #[test]
+    fn patp() {
+        let ships: [(u128, &str); 11] = [
+            (0, "~zod"),
+            (1, "~nec"),
+            (255, "~fes"),
+            (256, "~marzod"),
+            (0xffff, "~fipfes"),
+            (0x1_0000, "~dapnep-ronmyl"),
+            (1_624_961_343, "~sampel-palnet"),
+            (0xffff_ffff, "~dostec-risfen"),
+            (0x1_0000_0000, "~doznec-dozzod-dozzod"),
+            (u64::MAX.into(), "~fipfes-fipfes-dostec-risfen"),
+            (
+                u128::MAX,
+                "~fipfes-fipfes-fipfes-fipfes--fipfes-fipfes-fipfes-fipfes",
+            ),
+        ];
+        for (ship, name) in ships {
+            assert_eq!(Atom::from(ship).to_patp(), name);
+            assert_eq!(Atom::from_patp(name), Ok(Atom::from(ship)));
+        }
+
+        let comet = Atom::from(0x1_0000_0000_0000_0000u128);
+        assert_eq!(comet.to_patp(), "~doznec--dozzod-dozzod-dozzod-dozzod");
+        assert_eq!(Atom::from_patp(&comet.to_patp()), Ok(comet));
+    }
+
+    #[test]
+    fn patq() {
+        let atoms: [(u128, &str); 7] = [
+            (0, "~zod"),
+            (1, "~nec"),
+            (255, "~fes"),
+            (256, "~marzod"),
+            (0x1_0000, "~doznec-dozzod"),
+            (0x046f_7f4f, "~sampel-palnet"),
+            (
+                u128::MAX,
+                "~fipfes-fipfes-fipfes-fipfes-fipfes-fipfes-fipfes-fipfes",
+            ),
+        ];
+        for (atom, name) in atoms {
+            assert_eq!(Atom::from(atom).to_patq(), name);
+            assert_eq!(Atom::from_patq(name), Ok(Atom::from(atom)));
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(Atom::from_patp("zod"), Err(Error::MissingSig));
+        assert_eq!(Atom::from_patp("~"), Err(Error::InvalidSyllable { pos: 1 }));
+        assert_eq!(
+            Atom::from_patp("~zad"),
+            Err(Error::InvalidSyllable { pos: 1 })
+        );
+        assert_eq!(
+            Atom::from_patp("~sampel-palnot"),
+            Err(Error::InvalidSyllable { pos: 11 })
+        );
+        assert_eq!(
+            Atom::from_patp("~zod-palnet"),
+            Err(Error::InvalidSyllable { pos: 1 })
+        );
+        assert_eq!(
+            Atom::from_patp("~sampel-palnetx"),
+            Err(Error::InvalidSyllable { pos: 8 })
+        );
+        assert_eq!(
+            Atom::from_patp("~sämpel"),
+            Err(Error::InvalidSyllable { pos: 1 })
+        );
+        assert_eq!(Atom::from_patp("~doznec"), Err(Error::NonCanonical));
+        assert_eq!(Atom::from_patp("~sampel--palnet"), Err(Error::NonCanonical));
+        assert_eq!(Atom::from_patp("~marzod-"), Err(Error::NonCanonical));
+        assert_eq!(Atom::from_patq("~doznec"), Err(Error::NonCanonical));
+        assert_eq!(Atom::from_patq("~dozzod-marzod"), Err(Error::NonCanonical));
+    }
+}
@@ -0,0 +1,550 @@
+//! Parsing of nouns written in Hoon's literal syntax.
+//!
+//! [`Noun`] implements [`FromStr`](std::str::FromStr) with the following syntax:
+//!
+//! | Syntax                        | Noun                                                          |
+//! |-------------------------------|---------------------------------------------------------------|
+//! | `[a b c]`                     | The cell `[a [b c]]`; cells associate to the right.           |
+//! | `~[a b c]`                    | The null-terminated list `[a b c ~]`.                         |
+//! | `~`                           | `0`.                                                          |
+//! | `1.000`, `0x1.0000`, `0b101`  | An unsigned number (`@ud`, `@ux`, `@ub`, `@uv` or `@uw`).     |
+//! | `%term`, `%$`, `%.y`, `%.n`   | A symbol (`@tas`), `0`, or the loobeans `0` and `1`.          |
+//! | `'cord'`                      | UTF-8 text (`@t`).                                            |
+//! | `"tape"`                      | A null-terminated list of the bytes of UTF-8 text.            |
+//! | `~sampel-palnet`              | A ship (`@p`).                                                |
+//! | `~2022.1.1`, `~h1.m30`        | An absolute date (`@da`) or relative duration (`@dr`).        |
+//!
+//! Elements of cells and lists are separated by whitespace, and `::` begins a comment that runs to
+//! the end of the line. Within cords and tapes, `\\`, `\'`, `\"` and `\` followed by two
+//! hexadecimal digits are escapes for a backslash, a quote, and an arbitrary byte.
+//!
+//! Everything [`Noun::pretty`] prints can be parsed back into the same noun.
+//!
+//! # Examples
+//!
+//! ```
+//! # use noun::{cell::Cell, Noun};
+//! let noun: Noun = "[%x-cached 'HIT']".parse().unwrap();
+//! assert_eq!(noun, Noun::from(Cell::from(["x-cached", "HIT"])));
+//!
+//! let err = "[1 2\n 0x12.34.56]".parse::<Noun>().unwrap_err();
+//! assert_eq!((err.line, err.column), (2, 2));
+//! ```
+
+use crate::{
+    atom::Atom,
+    cell::Cell,
+    noun::Noun,
+    ob,
+    print::{BASE32, BASE64},
+    time, Rc,
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    result,
+};
+
+/// An error that occurred when parsing a noun, at a 1-based line and column of the input.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Error {
+    /// The line of the error.
+    pub line: usize,
+    /// The column of the error, counted in characters.
+    pub column: usize,
+    /// The kind of error.
+    pub kind: ErrorKind,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.kind, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::InvalidShip(err) => Some(err),
+            ErrorKind::InvalidTime(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// The kinds of errors that occur when parsing a noun.
+#[derive(Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// The input ended partway through a noun.
+    UnexpectedEnd,
+    /// A character appeared where it isn't allowed.
+    UnexpectedChar(char),
+    /// A cell had fewer than two elements or a list had none.
+    TooFewElements,
+    /// A number had an invalid digit, a leading zero, or a group of digits of the wrong length.
+    InvalidNumber,
+    /// A cord or tape had an invalid escape sequence.
+    InvalidEscape,
+    /// A ship name was invalid.
+    InvalidShip(ob::Error),
+    /// An absolute date or relative duration was invalid.
+    InvalidTime(time::Error),
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
+        match self {
+            Self::UnexpectedEnd => write!(f, "unexpected end of input"),
+            Self::UnexpectedChar(c) => write!(f, "unexpected character {:?}", c),
+            Self::TooFewElements => write!(f, "too few elements"),
+            Self::InvalidNumber => write!(f, "invalid number"),
+            Self::InvalidEscape => write!(f, "invalid escape sequence"),
+            Self::InvalidShip(err) => write!(f, "invalid ship name: {}", err),
+            Self::InvalidTime(err) => write!(f, "invalid date or duration: {}", err),
+        }
+    }
+}
+
+/// A specialized [`Result`] type for parsing operations that return [`parse::Error`] on error.
+///
+/// [`parse::Error`]: [`Error`]
+pub type Result<T> = result::Result<T, Error>;
+
+/// Parses a noun written in Hoon's literal syntax.
+pub(crate) fn parse(text: &str) -> Result<Rc<Noun>> {
+    Parser { text, pos: 0 }.noun()
+}
+
+/// A cell or list whose closing bracket hasn't been reached yet.
+struct Frame {
+    elems: Vec<Rc<Noun>>,
+    list: bool,
+}
+
+/// A position in the text being parsed.
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    /// Creates an error of `kind` at byte offset `pos`.
+    fn error(&self, pos: usize, kind: ErrorKind) -> Error {
+        let before = &self.text[..pos];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Error {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            kind,
+        }
+    }
+
+    /// Creates an error for the character at the current position, or for the end of the input.
+    fn unexpected(&self) -> Error {
+        match self.peek() {
+            Some(c) => self.error(self.pos, ErrorKind::UnexpectedChar(c)),
+            None => self.error(self.pos, ErrorKind::UnexpectedEnd),
+        }
+    }
+
+    /// Returns the rest of the text.
+    fn rest(&self) -> &str {
+        &self.text[self.pos..]
+    }
+
+    /// Returns the next character.
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// Consumes and returns the next character.
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    /// Consumes whitespace and comments, returning whether there were any.
+    fn skip_space(&mut self) -> bool {
+        let start = self.pos;
+        loop {
+            let rest = self.rest();
+            if rest.starts_with("::") {
+                self.pos += rest.find('\n').unwrap_or(rest.len());
+            } else if rest.starts_with(char::is_whitespace) {
+                self.pos += rest.len() - rest.trim_start().len();
+            } else {
+                return self.pos > start;
+            }
+        }
+    }
+
+    /// Consumes and returns the characters up to the next whitespace or bracket.
+    fn token(&mut self) -> &str {
+        let start = self.pos;
+        let len = self
+            .rest()
+            .find(|c: char| c.is_whitespace() || c == '[' || c == ']')
+            .unwrap_or(self.rest().len());
+        self.pos += len;
+        &self.text[start..self.pos]
+    }
+
+    /// Parses a whole noun, which must span the whole text other than whitespace.
+    fn noun(&mut self) -> Result<Rc<Noun>> {
+        let mut frames: Vec<Frame> = Vec::new();
+        self.skip_space();
+        loop {
+            let noun = if self.rest().starts_with('[') {
+                self.pos += 1;
+                frames.push(Frame {
+                    elems: Vec::new(),
+                    list: false,
+                });
+                self.skip_space();
+                continue;
+            } else if self.rest().starts_with("~[") {
+                self.pos += 2;
+                frames.push(Frame {
+                    elems: Vec::new(),
+                    list: true,
+                });
+                self.skip_space();
+                continue;
+            } else if self.rest().starts_with(']') && !frames.is_empty() {
+                let frame = frames.pop().expect("frame");
+                let min_len = if frame.list { 1 } else { 2 };
+                if frame.elems.len() < min_len {
+                    return Err(self.error(self.pos, ErrorKind::TooFewElements));
+                }
+                self.pos += 1;
+                let mut elems = frame.elems.into_iter().rev();
+                let mut noun = if frame.list {
+                    Rc::new(Noun::null())
+                } else {
+                    elems.next().expect("element")
+                };
+                for elem in elems {
+                    noun = Rc::<Noun>::from(Cell::from([elem, noun]));
+                }
+                noun
+            } else {
+                self.leaf()?
+            };
+
+            // Elements must be separated by whitespace.
+            let spaced = self.skip_space();
+            match frames.last_mut() {
+                Some(frame) if spaced || self.rest().starts_with(']') => frame.elems.push(noun),
+                None if self.pos == self.text.len() => return Ok(noun),
+                _ => return Err(self.unexpected()),
+            }
+        }
+    }
+
+    /// Parses a noun other than a cell or list, which is an atom unless it's a nonempty tape.
+    fn leaf(&mut self) -> Result<Rc<Noun>> {
+        let start = self.pos;
+        let atom = match self.peek() {
+            Some('~') => self.sig()?,
+            Some('%') => self.term()?,
+            Some('\'') => Atom::from(self.quoted('\'')?),
+            Some('"') => {
+                let bytes = self.quoted('"')?;
+                return Ok(bytes
+                    .into_iter()
+                    .rev()
+                    .fold(Rc::new(Noun::null()), |tail, byte| {
+                        Rc::<Noun>::from(Cell::from([Rc::<Noun>::from(Atom::from(byte)), tail]))
+                    }));
+            }
+            Some('0'..='9') => {
+                let token = self.token();
+                number(token).ok_or_else(|| self.error(start, ErrorKind::InvalidNumber))?
+            }
+            _ => return Err(self.unexpected()),
+        };
+        Ok(Rc::<Noun>::from(atom))
+    }
+
+    /// Parses `~`, a ship, an absolute date, or a relative duration.
+    fn sig(&mut self) -> Result<Atom> {
+        let start = self.pos;
+        let token = self.token();
+        let mut chars = token.chars().skip(1);
+        let (first, second) = (chars.next(), chars.next());
+        let atom = match first {
+            None => return Ok(Atom::null()),
+            Some('0'..='9') => Atom::from_da(token).map_err(ErrorKind::InvalidTime),
+            Some('d' | 'h' | 'm' | 's') if second.is_some_and(|c| c.is_ascii_digit()) => {
+                Atom::from_dr(token).map_err(ErrorKind::InvalidTime)
+            }
+            Some('a'..='z') => Atom::from_patp(token).map_err(ErrorKind::InvalidShip),
+            Some(c) => return Err(self.error(start + 1, ErrorKind::UnexpectedChar(c))),
+        };
+        atom.map_err(|kind| {
+            let offset = match &kind {
+                ErrorKind::InvalidShip(ob::Error::InvalidSyllable { pos })
+                | ErrorKind::InvalidTime(time::Error::Syntax { pos })
+                | ErrorKind::InvalidTime(time::Error::OutOfRange { pos }) => *pos,
+                _ => 0,
+            };
+            self.error(start + offset, kind)
+        })
+    }
+
+    /// Parses a symbol, `%$`, or a loobean.
+    fn term(&mut self) -> Result<Atom> {
+        self.pos += 1;
+        let start = self.pos;
+        match self.peek() {
+            Some('$') => {
+                self.pos += 1;
+                return Ok(Atom::null());
+            }
+            Some('.') => {
+                self.pos += 1;
+                let atom = match self.peek() {
+                    Some('y') => Atom::null(),
+                    Some('n') => Atom::from(1u8),
+                    _ => return Err(self.unexpected()),
+                };
+                self.pos += 1;
+                return Ok(atom);
+            }
+            Some('a'..='z') => {}
+            _ => return Err(self.unexpected()),
+        }
+        while let Some('a'..='z' | '0'..='9' | '-') = self.peek() {
+            self.pos += 1;
+        }
+        Ok(Atom::from(&self.text[start..self.pos]))
+    }
+
+    /// Parses the bytes of text between two `quote`s, resolving escapes.
+    fn quoted(&mut self, quote: char) -> Result<Vec<u8>> {
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        loop {
+            let start = self.pos;
+            match self.next() {
+                None => return Err(self.unexpected()),
+                Some(c) if c == quote => return Ok(bytes),
+                Some('\\') => match self.next() {
+                    Some(c @ ('\\' | '\'' | '"')) => bytes.push(c as u8),
+                    _ => {
+                        let byte = self
+                            .text
+                            .get(start + 1..start + 3)
+                            .filter(|hex| hex.bytes().all(|byte| byte.is_ascii_hexdigit()))
+                            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                            .ok_or_else(|| self.error(start, ErrorKind::InvalidEscape))?;
+                        self.pos = start + 3;
+                        bytes.push(byte);
+                    }
+                },
+                Some(c) => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+            }
+        }
+    }
+}
+
+/// Parses an unsigned number: decimal with digits in groups of three, or one of the `0x`, `0b`,
+/// `0v` and `0w` power-of-two radixes with digits in groups of four or five.
+fn number(token: &str) -> Option<Atom> {
+    let (digits, bits, alphabet, size): (_, _, &[u8], _) = match token.get(..2) {
+        Some("0x") => (&token[2..], 4, b"0123456789abcdef", 4),
+        Some("0b") => (&token[2..], 1, b"01", 4),
+        Some("0v") => (&token[2..], 5, BASE32, 5),
+        Some("0w") => (&token[2..], 6, BASE64, 5),
+        _ => (token, 0, b"0123456789", 3),
+    };
+
+    // The first group has up to `size` digits and no leading zero, and the rest have exactly
+    // `size` digits.
+    let mut groups = digits.split('.');
+    let first = groups.next()?;
+    if first.is_empty() || first.len() > size || (first.len() > 1 && first.starts_with('0')) {
+        return None;
+    }
+    if groups.any(|group| group.len() != size) || (first == "0" && digits.len() > 1) {
+        return None;
+    }
+    let digits: Vec<u64> = digits
+        .bytes()
+        .filter(|byte| *byte != b'.')
+        .map(|byte| {
+            alphabet
+                .iter()
+                .position(|digit| *digit == byte)
+                .map(|d| d as u64)
+        })
+        .collect::<Option<_>>()?;
+
+    if bits == 0 {
+        let thousand = Atom::from(1000u16);
+        let mut atom = Atom::null();
+        for group in digits.rchunks(size).rev() {
+            let group = group
+                .iter()
+                .fold(0u16, |acc, digit| acc * 10 + *digit as u16);
+            atom = atom * &thousand + Atom::from(group);
+        }
+        Some(atom)
+    } else {
+        let mut builder = Atom::builder();
+        for digit in digits.into_iter().rev() {
+            builder.push_bits(digit, bits);
+        }
+        Some(builder.into_atom())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::print::Aura;
+
+    fn parse(text: &str) -> Noun {
+        text.parse().unwrap()
+    }
+
+    fn error(text: &str) -> (usize, usize, ErrorKind) {
+        let err = text.parse::<Noun>().unwrap_err();
+        (err.line, err.column, err.kind)
+    }
+
+    #[test]
+    fn atoms() {
+        let atoms: [(&str, Atom); 20] = [
+            ("0", Atom::null()),
+            ("~", Atom::null()),
+            ("1.000", Atom::from(1_000u16)),
+            (
+                "340.282.366.920.938.463.463.374.607.431.768.211.455",
+                Atom::from(u128::MAX),
+            ),
+            ("0x0", Atom::null()),
+            ("0xdead.beef", Atom::from(0xdead_beefu32)),
+            ("0b1.0110", Atom::from(0b1_0110u8)),
+            ("0v1.00000", Atom::from(1u64 << 25)),
+            ("0w~", Atom::from(63u8)),
+            ("%$", Atom::null()),
+            ("%.y", Atom::null()),
+            ("%.n", Atom::from(1u8)),
+            ("%x-cached", Atom::from("x-cached")),
+            ("''", Atom::null()),
+            ("'héllo'", Atom::from("héllo")),
+            ("'it\\'s\\0a'", Atom::from("it's\n")),
+            ("'a\\ff\\\\'", Atom::from(vec![b'a', 0xff, b'\\'])),
+            ("~zod", Atom::null()),
+            ("~sampel-palnet", Atom::from(1_624_961_343u32)),
+            (
+                "~2022.7.8..16.43.50",
+                Atom::from_da("~2022.7.8..16.43.50").unwrap(),
+            ),
+        ];
+        for (text, atom) in atoms {
+            assert_eq!(parse(text), Noun::from(atom), "{}", text);
+        }
+        assert_eq!(
+            parse("~h1.m30"),
+            Noun::from(Atom::from_dr("~h1.m30").unwrap())
+        );
+        assert_eq!(
+            parse(" \n 42 :: the answer\n"),
+            Noun::from(Atom::from(42u8))
+        );
+    }
+
+    #[test]
+    fn cells() {
+        assert_eq!(parse("[1 2]"), Noun::from(Cell::from([1u8, 2u8])));
+        assert_eq!(parse("[1 2 3]"), parse("[1 [2 3]]"));
+        assert_eq!(
+            parse("[[1 2] 3]"),
+            Noun::from(Cell::from([
+                Rc::<Noun>::from(Cell::from([1u8, 2u8])),
+                Rc::<Noun>::from(Atom::from(3u8)),
+            ]))
+        );
+        assert_eq!(parse("~[1 2 3]"), parse("[1 2 3 ~]"));
+        assert_eq!(parse("~[~]"), parse("[0 0]"));
+        assert_eq!(parse("\"\""), parse("~"));
+        assert_eq!(parse("\"hi\\\"\""), parse("~[104 105 34]"));
+        assert_eq!(
+            parse("[ %x-cached  :: a comment\n\t'HIT' ]"),
+            Noun::from(Cell::from(["x-cached", "HIT"]))
+        );
+
+        // Deeply nested cells and long lists.
+        let text = format!("{}0{}", "[0 ".repeat(100_000), "]".repeat(100_000));
+        assert_eq!(parse(&text), parse(&format!("[{}0]", "0 ".repeat(100_000))));
+    }
+
+    #[test]
+    fn round_trip() {
+        let texts = [
+            "[%poke ~[1 2 3] [[4 5] 6] 'text with spaces']",
+            "[~[0] 0xdead.beef ~[%a %b] ~2022.7.8..16.43.50 '\\0a' \"tape\"]",
+        ];
+        for text in texts {
+            let noun = parse(text);
+            assert_eq!(parse(&noun.pretty().to_string()), noun, "{}", text);
+            for aura in [Aura::Ud, Aura::Ux, Aura::Ub, Aura::Uv, Aura::Uw, Aura::P] {
+                let printed = noun.pretty().aura(aura).to_string();
+                assert_eq!(parse(&printed), noun, "{}", printed);
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(error(""), (1, 1, ErrorKind::UnexpectedEnd));
+        assert_eq!(error("[1 2"), (1, 5, ErrorKind::UnexpectedEnd));
+        assert_eq!(error("[1]"), (1, 3, ErrorKind::TooFewElements));
+        assert_eq!(error("~[]"), (1, 3, ErrorKind::TooFewElements));
+        assert_eq!(error("[1 2]]"), (1, 6, ErrorKind::UnexpectedChar(']')));
+        assert_eq!(error("[1[2 3]]"), (1, 3, ErrorKind::UnexpectedChar('[')));
+        assert_eq!(error("[1 2\n 0x12.34]"), (2, 2, ErrorKind::InvalidNumber));
+        assert_eq!(error("01"), (1, 1, ErrorKind::InvalidNumber));
+        assert_eq!(error("1.00"), (1, 1, ErrorKind::InvalidNumber));
+        assert_eq!(error("0xg"), (1, 1, ErrorKind::InvalidNumber));
+        assert_eq!(error("%Foo"), (1, 2, ErrorKind::UnexpectedChar('F')));
+        assert_eq!(error("%foo.bar"), (1, 5, ErrorKind::UnexpectedChar('.')));
+        assert_eq!(error("%.x"), (1, 3, ErrorKind::UnexpectedChar('x')));
+        assert_eq!(error("'é\\q'"), (1, 3, ErrorKind::InvalidEscape));
+        assert_eq!(error("\"abc"), (1, 5, ErrorKind::UnexpectedEnd));
+        assert_eq!(error("~!"), (1, 2, ErrorKind::UnexpectedChar('!')));
+        assert_eq!(
+            error("[1\n  ~zod-zod]"),
+            (
+                2,
+                4,
+                ErrorKind::InvalidShip(ob::Error::InvalidSyllable { pos: 1 })
+            )
+        );
+        assert_eq!(
+            error("~dozzod-marzod"),
+            (1, 1, ErrorKind::InvalidShip(ob::Error::NonCanonical))
+        );
+        assert_eq!(
+            error("~2022.13.1"),
+            (
+                1,
+                7,
+                ErrorKind::InvalidTime(time::Error::OutOfRange { pos: 6 })
+            )
+        );
+        assert_eq!(
+            "[1\n ~2022.13.1]".parse::<Noun>().unwrap_err().to_string(),
+            format!(
+                "invalid date or duration: {} at line 2, column 8",
+                time::Error::OutOfRange { pos: 6 }
+            )
+        );
+    }
+}
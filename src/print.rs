@@ -0,0 +1,410 @@
+//! Pretty-printing of nouns in Hoon's syntax.
+//!
+//! An atom has no inherent type, so [`Pretty`] renders each atom in an [`Aura`], either one chosen
+//! for the whole noun or one guessed per atom with [`Aura::infer`]. Cells are written with Hoon's
+//! sugar: right-nested cells as a flat tuple (`[a b c]` for `[a [b c]]`) and null-terminated lists
+//! as `~[a b c]`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use noun::{cell::Cell, print::Aura, Noun};
+//! let noun = Noun::from(Cell::from(["live", "exit", ""]));
+//! assert_eq!(noun.pretty().to_string(), "~[%live %exit]");
+//!
+//! let noun = Noun::from(Cell::from([1u32, 1_000u32, 0x1_0000u32]));
+//! assert_eq!(noun.pretty().to_string(), "[1 1.000 65.536]");
+//! assert_eq!(noun.pretty().aura(Aura::Ux).to_string(), "[0x1 0x3e8 0x1.0000]");
+//! ```
+
+use crate::{atom::Atom, cell::Cell, noun::Noun};
+use std::{
+    fmt::{self, Display, Formatter},
+    str,
+};
+
+/// Digits of `@uv`, in order.
+pub(crate) const BASE32: &[u8; 32] = b"0123456789abcdefghijklmnopqrstuv";
+
+/// Digits of `@uw`, in order.
+pub(crate) const BASE64: &[u8; 64] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ-~";
+
+/// Earliest absolute date inferred to be a `@da`, `~2000.1.1`.
+const MIN_DATE: u128 = 0x8000_000d_070b_5100 << 64;
+
+/// Latest absolute date inferred to be a `@da`, `~2100.1.1`.
+const MAX_DATE: u128 = 0x8000_000d_c324_6480 << 64;
+
+/// An atom's interpretation when printed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Aura {
+    /// Unsigned decimal, with digits in groups of three: `1.000.000`.
+    Ud,
+    /// Unsigned hexadecimal, with digits in groups of four: `0x1.0000`.
+    Ux,
+    /// Unsigned binary, with digits in groups of four: `0b1.0000`.
+    Ub,
+    /// Unsigned base32, with digits in groups of five: `0v1.00000`.
+    Uv,
+    /// Unsigned base64, with digits in groups of five: `0w1.00000`.
+    Uw,
+    /// UTF-8 text: `'cord'`.
+    T,
+    /// An ASCII symbol of lowercase letters, digits and `-` beginning with a letter: `%term`.
+    Tas,
+    /// A ship name: `~sampel-palnet`.
+    P,
+    /// An absolute date: `~2022.7.8..16.43.50`.
+    Da,
+}
+
+impl Aura {
+    /// Guesses the aura of `atom`.
+    ///
+    /// An atom of at least two bytes is a `@tas` if it's a valid symbol, or a `@t` if it's
+    /// printable UTF-8 text. A 128-bit atom is a `@da` if it's a date in the 21st century. Other
+    /// atoms are `@ud` if they fit in 32 bits and `@ux` otherwise.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::{atom::Atom, print::Aura};
+    /// assert_eq!(Aura::infer(&Atom::from(42u8)), Aura::Ud);
+    /// assert_eq!(Aura::infer(&Atom::from("poke")), Aura::Tas);
+    /// assert_eq!(Aura::infer(&Atom::from("Hello, world!")), Aura::T);
+    /// assert_eq!(Aura::infer(&Atom::from(u64::MAX)), Aura::Ux);
+    /// ```
+    pub fn infer(atom: &Atom) -> Self {
+        let bytes = atom.as_bytes();
+        if bytes.len() >= 2 {
+            if is_term(bytes) {
+                return Self::Tas;
+            }
+            if str::from_utf8(bytes).is_ok_and(|text| !text.chars().any(char::is_control)) {
+                return Self::T;
+            }
+        }
+        if atom
+            .as_u128()
+            .is_some_and(|time| (MIN_DATE..MAX_DATE).contains(&time))
+        {
+            return Self::Da;
+        }
+        if atom.bit_len() <= 32 {
+            Self::Ud
+        } else {
+            Self::Ux
+        }
+    }
+
+    /// Renders `atom` in this aura.
+    ///
+    /// An atom that isn't valid in this aura, such as a `@tas` that isn't a symbol or a `@da`
+    /// wider than 128 bits, is rendered as `@ux` instead. Bytes of a `@t` that aren't printable
+    /// UTF-8 are escaped as `\` followed by two hexadecimal digits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use noun::{atom::Atom, print::Aura};
+    /// assert_eq!(Aura::Ud.render(&Atom::from(1_234_567u32)), "1.234.567");
+    /// assert_eq!(Aura::Ub.render(&Atom::from(0b1_0110u8)), "0b1.0110");
+    /// assert_eq!(Aura::T.render(&Atom::from("it's\n")), "'it\\'s\\0a'");
+    /// assert_eq!(Aura::Tas.render(&Atom::from("Poke")), "0x656b.6f50");
+    /// ```
+    pub fn render(self, atom: &Atom) -> String {
+        match self {
+            Self::Ud => group(&decimal(atom), 3),
+            Self::Ux => format!("0x{}", group(&radix(atom, 4, b"0123456789abcdef"), 4)),
+            Self::Ub => format!("0b{}", group(&radix(atom, 1, b"01"), 4)),
+            Self::Uv => format!("0v{}", group(&radix(atom, 5, BASE32), 5)),
+            Self::Uw => format!("0w{}", group(&radix(atom, 6, BASE64), 5)),
+            Self::T => cord(atom.as_bytes()),
+            Self::Tas if atom.is_null() => String::from("%$"),
+            Self::Tas if is_term(atom.as_bytes()) => {
+                format!("%{}", str::from_utf8(atom.as_bytes()).expect("ASCII"))
+            }
+            Self::P => atom.to_patp(),
+            Self::Da => atom.to_da().unwrap_or_else(|| Self::Ux.render(atom)),
+            Self::Tas => Self::Ux.render(atom),
+        }
+    }
+}
+
+/// Returns whether `bytes` are a symbol: a lowercase letter followed by lowercase letters, digits
+/// and `-`.
+pub(crate) fn is_term(bytes: &[u8]) -> bool {
+    match bytes.split_first() {
+        Some((first, rest)) => {
+            first.is_ascii_lowercase()
+                && rest
+                    .iter()
+                    .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || *byte == b'-')
+        }
+        None => false,
+    }
+}
+
+/// Writes `bytes` as a quoted cord, escaping quotes, backslashes, and bytes that aren't printable
+/// UTF-8.
+fn cord(bytes: &[u8]) -> String {
+    let mut text = String::from("'");
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            match c {
+                '\'' | '\\' => {
+                    text.push('\\');
+                    text.push(c);
+                }
+                _ if c.is_control() => {
+                    let mut buf = [0; 4];
+                    for byte in c.encode_utf8(&mut buf).bytes() {
+                        text.push_str(&format!("\\{:02x}", byte));
+                    }
+                }
+                _ => text.push(c),
+            }
+        }
+        for byte in chunk.invalid() {
+            text.push_str(&format!("\\{:02x}", byte));
+        }
+    }
+    text.push('\'');
+    text
+}
+
+/// Returns the digits of `atom` in a power-of-two radix with `bits` bits per digit, most
+/// significant first.
+fn radix(atom: &Atom, bits: usize, alphabet: &[u8]) -> String {
+    let bytes = atom.as_bytes();
+    let len = atom.bit_len().div_ceil(bits).max(1);
+    (0..len)
+        .rev()
+        .map(|i| {
+            let digit = (0..bits)
+                .map(|j| i * bits + j)
+                .filter(|bit| {
+                    bytes
+                        .get(bit / 8)
+                        .is_some_and(|byte| byte >> (bit % 8) & 1 == 1)
+                })
+                .fold(0, |digit, bit| digit | 1 << (bit - i * bits));
+            char::from(alphabet[digit])
+        })
+        .collect()
+}
+
+/// Returns the decimal digits of `atom`, most significant first.
+fn decimal(atom: &Atom) -> String {
+    const BILLION: u64 = 1_000_000_000;
+
+    // Repeatedly divides little-endian 32-bit limbs by a billion, collecting the remainders.
+    let mut limbs: Vec<u32> = atom
+        .as_bytes()
+        .chunks(4)
+        .map(|chunk| {
+            let mut limb = [0; 4];
+            limb[..chunk.len()].copy_from_slice(chunk);
+            u32::from_le_bytes(limb)
+        })
+        .collect();
+    let mut chunks = Vec::new();
+    while !limbs.is_empty() {
+        let mut rem = 0;
+        for limb in limbs.iter_mut().rev() {
+            let cur = rem << 32 | u64::from(*limb);
+            *limb = (cur / BILLION) as u32;
+            rem = cur % BILLION;
+        }
+        chunks.push(rem);
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+    }
+
+    let mut digits = chunks.pop().unwrap_or(0).to_string();
+    for chunk in chunks.iter().rev() {
+        digits.push_str(&format!("{:09}", chunk));
+    }
+    digits
+}
+
+/// Separates `digits` into groups of `size` with `.`, counting from the least significant digit.
+fn group(digits: &str, size: usize) -> String {
+    let mut text = String::with_capacity(digits.len() + digits.len() / size);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(size) {
+            text.push('.');
+        }
+        text.push(digit);
+    }
+    text
+}
+
+/// A noun printed with atoms in a chosen or inferred [`Aura`], created by [`Noun::pretty`].
+pub struct Pretty<'a> {
+    noun: &'a Noun,
+    aura: Option<Aura>,
+}
+
+impl<'a> Pretty<'a> {
+    /// Prints `noun`, inferring the aura of each atom.
+    pub fn new(noun: &'a Noun) -> Self {
+        Self { noun, aura: None }
+    }
+
+    /// Prints every atom in `aura` instead of inferring the aura of each atom.
+    pub fn aura(mut self, aura: Aura) -> Self {
+        self.aura = Some(aura);
+        self
+    }
+
+    /// Renders `atom` in the chosen aura, or in its inferred aura if none was chosen.
+    fn render(&self, atom: &Atom) -> String {
+        self.aura.unwrap_or_else(|| Aura::infer(atom)).render(atom)
+    }
+}
+
+impl Display for Pretty<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        enum Token<'a> {
+            Noun(&'a Noun),
+            Text(&'static str),
+        }
+
+        /// Pushes the tokens of `cell` in reverse order, as a list if its right spine ends in `~`
+        /// and as a tuple otherwise.
+        fn push_cell<'a>(cell: &'a Cell, tokens: &mut Vec<Token<'a>>) {
+            let mut elems = vec![cell.head_ref()];
+            let mut tail = cell.tail_ref();
+            while let Noun::Cell(cell) = tail {
+                elems.push(cell.head_ref());
+                tail = cell.tail_ref();
+            }
+            let list = tail.is_null();
+            if !list {
+                elems.push(tail);
+            }
+
+            tokens.push(Token::Text("]"));
+            for (i, elem) in elems.into_iter().enumerate().rev() {
+                tokens.push(Token::Noun(elem));
+                if i > 0 {
+                    tokens.push(Token::Text(" "));
+                }
+            }
+            tokens.push(Token::Text(if list { "~[" } else { "[" }));
+        }
+
+        let mut tokens = vec![Token::Noun(self.noun)];
+        while let Some(token) = tokens.pop() {
+            match token {
+                Token::Noun(Noun::Atom(atom)) => f.write_str(&self.render(atom))?,
+                Token::Noun(Noun::Cell(cell)) => push_cell(cell, &mut tokens),
+                Token::Text(text) => f.write_str(text)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rc;
+
+    #[test]
+    fn render() {
+        let renders: [(Aura, Atom, &str); 20] = [
+            (Aura::Ud, Atom::null(), "0"),
+            (Aura::Ud, Atom::from(999u16), "999"),
+            (Aura::Ud, Atom::from(1_000u16), "1.000"),
+            (
+                Aura::Ud,
+                Atom::from(u128::MAX),
+                "340.282.366.920.938.463.463.374.607.431.768.211.455",
+            ),
+            (Aura::Ux, Atom::null(), "0x0"),
+            (Aura::Ux, Atom::from(0x100u16), "0x100"),
+            (Aura::Ux, Atom::from(0xdead_beefu32), "0xdead.beef"),
+            (Aura::Ux, Atom::from(0x1_0000u32), "0x1.0000"),
+            (Aura::Ub, Atom::from(5u8), "0b101"),
+            (Aura::Uv, Atom::from(32u8), "0v10"),
+            (Aura::Uv, Atom::from(1u64 << 25), "0v1.00000"),
+            (Aura::Uw, Atom::from(63u8), "0w~"),
+            (Aura::Uw, Atom::from(1u64 << 30), "0w1.00000"),
+            (Aura::T, Atom::null(), "''"),
+            (Aura::T, Atom::from("héllo"), "'héllo'"),
+            (Aura::T, Atom::from(vec![b'a', 0xff, b'\\']), "'a\\ff\\\\'"),
+            (Aura::Tas, Atom::null(), "%$"),
+            (Aura::Tas, Atom::from("a-2"), "%a-2"),
+            (Aura::P, Atom::from(256u16), "~marzod"),
+            (
+                Aura::Da,
+                Atom::from_da("~2022.7.8..16.43.50").unwrap(),
+                "~2022.7.8..16.43.50",
+            ),
+        ];
+        for (aura, atom, text) in renders {
+            assert_eq!(aura.render(&atom), text, "{:?}", aura);
+        }
+        assert_eq!(Aura::Tas.render(&Atom::from("2a")), "0x6132");
+        assert_eq!(
+            Aura::Da.render(&Atom::from(vec![1; 17])),
+            Aura::Ux.render(&Atom::from(vec![1; 17]))
+        );
+    }
+
+    #[test]
+    fn infer() {
+        assert_eq!(Aura::infer(&Atom::null()), Aura::Ud);
+        assert_eq!(Aura::infer(&Atom::from("a")), Aura::Ud);
+        assert_eq!(Aura::infer(&Atom::from(u32::MAX)), Aura::Ud);
+        assert_eq!(Aura::infer(&Atom::from(u32::MAX as u64 + 1)), Aura::Ux);
+        assert_eq!(Aura::infer(&Atom::from("sampel-palnet")), Aura::Tas);
+        assert_eq!(Aura::infer(&Atom::from("two words")), Aura::T);
+        assert_eq!(Aura::infer(&Atom::from("two\nlines")), Aura::Ux);
+        assert_eq!(Aura::infer(&Atom::from_da("~2022.7.8").unwrap()), Aura::Da);
+        assert_eq!(
+            Aura::infer(&Atom::from_da("~1999.12.31").unwrap()),
+            Aura::Ux
+        );
+    }
+
+    #[test]
+    fn pretty() {
+        let noun = Noun::from(Cell::from([
+            Rc::<Noun>::from(Atom::from("poke")),
+            Rc::<Noun>::from(Cell::from([1u8, 2u8, 3u8, 0u8])),
+            Rc::<Noun>::from(Cell::from([
+                Rc::<Noun>::from(Cell::from([4u8, 5u8])),
+                Rc::<Noun>::from(Atom::from(6u8)),
+            ])),
+            Rc::<Noun>::from(Atom::from("text with spaces")),
+        ]));
+        assert_eq!(
+            noun.pretty().to_string(),
+            "[%poke ~[1 2 3] [[4 5] 6] 'text with spaces']"
+        );
+        assert_eq!(
+            noun.pretty().aura(Aura::Ud).to_string(),
+            format!(
+                "[1.701.539.696 ~[1 2 3] [[4 5] 6] {}]",
+                Aura::Ud.render(&Atom::from("text with spaces"))
+            )
+        );
+        assert_eq!(Noun::null().pretty().to_string(), "0");
+        assert_eq!(
+            Noun::from(Cell::from([0u8, 0u8])).pretty().to_string(),
+            "~[0]"
+        );
+
+        // A list too long to print recursively.
+        let mut list = Rc::new(Noun::null());
+        for _ in 0..100_000 {
+            list = Rc::<Noun>::from(Cell::from([Rc::<Noun>::from(Atom::from(7u8)), list]));
+        }
+        let printed = list.pretty().to_string();
+        assert!(printed.starts_with("~[7 7 "));
+        assert!(printed.ends_with(" 7 7]"));
+    }
+}
@@ -0,0 +1,349 @@
+//! Hoon queues (`qeu`s): treaps of elements ordered by when they were added.
+
+use crate::{
+    convert::{self, FromNoun, ToNoun},
+    noun::Noun,
+    treap::{self, node, split},
+    Rc,
+};
+use std::{
+    fmt::{self, Debug, Formatter},
+    iter::FusedIterator,
+    marker::PhantomData,
+};
+
+/// A first-in, first-out queue with exactly the structure of a Hoon `qeu`.
+///
+/// A queue is a treap of elements balanced by `mor`, with the newest element leftmost and the
+/// oldest rightmost, exactly as Hoon's `++to` builds it. Elements are converted to and from nouns
+/// with [`ToNoun`] and [`FromNoun`].
+///
+/// # Examples
+///
+/// ```
+/// # use noun::qeu::Qeu;
+/// let mut qeu: Qeu<u64> = [1, 2].into_iter().collect();
+/// qeu.put(3);
+/// assert_eq!(qeu.top(), Some(1));
+/// assert_eq!(qeu.pop(), Some(1));
+/// assert_eq!(qeu.tap(), [2, 3]);
+/// ```
+pub struct Qeu<T> {
+    tree: Rc<Noun>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: FromNoun + ToNoun> Qeu<T> {
+    /// Creates an empty queue.
+    pub fn new() -> Self {
+        Self::from_tree(Rc::new(Noun::null()))
+    }
+
+    fn from_tree(tree: Rc<Noun>) -> Self {
+        Self {
+            tree,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns whether this queue has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_null()
+    }
+
+    /// Adds `elem` to the back of this queue.
+    pub fn put(&mut self, elem: T) {
+        self.tree = put(&self.tree, Rc::new(elem.to_noun()));
+    }
+
+    /// Removes the element at the front of this queue, returning it, or `None` if this queue is
+    /// empty.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let (front, tree) = get(&self.tree);
+        self.tree = tree;
+        Some(elem(&front))
+    }
+
+    /// Returns the element at the front of this queue, or `None` if this queue is empty.
+    pub fn top(&self) -> Option<T> {
+        self.iter().next()
+    }
+
+    /// Returns the elements of this queue from front to back.
+    pub fn tap(&self) -> Vec<T> {
+        self.iter().collect()
+    }
+
+    /// Returns an iterator over the elements of this queue from front to back.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            nodes: treap::Nodes::new(&self.tree),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the noun of this queue.
+    pub fn as_noun(&self) -> &Rc<Noun> {
+        &self.tree
+    }
+}
+
+/// Rotates the nodes of `tree` until every node comes before its subtrees' nodes in `mor` order.
+/// This is `bal` from Hoon's `++to`.
+fn bal(tree: &Rc<Noun>) -> Rc<Noun> {
+    if tree.is_null() {
+        return tree.clone();
+    }
+    let (n, l, r) = split(tree);
+    if !l.is_null() {
+        let (l_n, l_l, l_r) = split(&l);
+        if !n.mor(&l_n) {
+            return bal(&node(l_n, l_l, bal(&node(n, l_r, r))));
+        }
+    }
+    if !r.is_null() {
+        let (r_n, r_l, r_r) = split(&r);
+        if !n.mor(&r_n) {
+            return bal(&node(r_n, bal(&node(n, l, r_l)), r_r));
+        }
+    }
+    tree.clone()
+}
+
+/// Adds `n` to the left of `tree`. This is `put` from Hoon's `++to`.
+fn put(tree: &Rc<Noun>, n: Rc<Noun>) -> Rc<Noun> {
+    if tree.is_null() {
+        let null = Rc::new(Noun::null());
+        return node(n, null.clone(), null);
+    }
+    let (tree_n, l, r) = split(tree);
+    bal(&node(tree_n, put(&l, n), r))
+}
+
+/// Removes the rightmost node from nonempty `tree`, returning the node and the rest of `tree`. This
+/// is `get` from Hoon's `++to`.
+fn get(tree: &Rc<Noun>) -> (Rc<Noun>, Rc<Noun>) {
+    let (n, l, r) = split(tree);
+    if r.is_null() {
+        return (n, l);
+    }
+    let (front, rest) = get(&r);
+    if rest.is_null() {
+        return (front, node(n, l, rest));
+    }
+    let (rest_n, rest_l, rest_r) = split(&rest);
+    if n.mor(&rest_n) {
+        (front, node(n, l, rest))
+    } else {
+        (front, node(rest_n, node(n, l, rest_l), rest_r))
+    }
+}
+
+/// Converts a queue's node into an element.
+///
+/// # Panics
+///
+/// Panics if the node isn't a `T`. Every node of a queue is one, since [`Qeu::put`] stores a
+/// `T`'s noun and converting a noun into a queue converts each of its elements.
+fn elem<T: FromNoun>(node: &Noun) -> T {
+    T::from_noun(node).expect("queue element")
+}
+
+impl<T> Clone for Qeu<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tree: self.tree.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Debug + FromNoun + ToNoun> Debug for Qeu<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: FromNoun + ToNoun> Default for Qeu<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PartialEq for Qeu<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.tree == other.tree
+    }
+}
+
+impl<T> Eq for Qeu<T> {}
+
+impl<T: FromNoun + ToNoun> Extend<T> for Qeu<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.put(elem);
+        }
+    }
+}
+
+impl<T> From<Qeu<T>> for Noun {
+    fn from(qeu: Qeu<T>) -> Self {
+        Rc::try_unwrap(qeu.tree).unwrap_or_else(|tree| (*tree).clone())
+    }
+}
+
+impl<T> From<Qeu<T>> for Rc<Noun> {
+    fn from(qeu: Qeu<T>) -> Self {
+        qeu.tree
+    }
+}
+
+impl<T: FromNoun + ToNoun> FromIterator<T> for Qeu<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut qeu = Self::new();
+        qeu.extend(iter);
+        qeu
+    }
+}
+
+impl<'a, T: FromNoun + ToNoun> IntoIterator for &'a Qeu<T> {
+    type Item = T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T: FromNoun + ToNoun> TryFrom<Rc<Noun>> for Qeu<T> {
+    type Error = convert::Error;
+
+    /// Converts a noun into a queue, failing with [`convert::Error::InvalidTreap`] if the noun
+    /// isn't a well-formed treap balanced by `mor`, or with the error of the element that doesn't
+    /// convert.
+    fn try_from(tree: Rc<Noun>) -> Result<Self, Self::Error> {
+        if !treap::is_heap(&tree) {
+            return Err(convert::Error::InvalidTreap);
+        }
+        for node in treap::Nodes::new(&tree) {
+            T::from_noun(node)?;
+        }
+        Ok(Self::from_tree(tree))
+    }
+}
+
+impl<T: FromNoun + ToNoun> TryFrom<&Noun> for Qeu<T> {
+    type Error = convert::Error;
+
+    fn try_from(noun: &Noun) -> Result<Self, Self::Error> {
+        Self::try_from(Rc::new(noun.clone()))
+    }
+}
+
+impl<T> ToNoun for Qeu<T> {
+    fn to_noun(&self) -> Noun {
+        (*self.tree).clone()
+    }
+}
+
+impl<T: FromNoun + ToNoun> FromNoun for Qeu<T> {
+    fn from_noun(noun: &Noun) -> Result<Self, convert::Error> {
+        Self::try_from(noun)
+    }
+}
+
+/// An iterator over the elements of a [`Qeu`] from front to back, created by [`Qeu::iter`].
+pub struct Iter<'a, T> {
+    nodes: treap::Nodes<'a>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: FromNoun> Iterator for Iter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes.next().map(elem)
+    }
+}
+
+impl<T: FromNoun> FusedIterator for Iter<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noun;
+
+    #[test]
+    fn put_pop() {
+        let mut qeu: Qeu<u32> = Qeu::new();
+        assert!(qeu.is_empty());
+        assert_eq!(qeu.pop(), None);
+        assert_eq!(qeu.top(), None);
+        qeu.put(1);
+        assert_eq!(Noun::from(qeu.clone()), noun!([1 ~ ~]));
+
+        let mut qeu: Qeu<u32> = (0..200).collect();
+        assert_eq!(qeu.tap(), (0..200).collect::<Vec<_>>());
+        assert!(Qeu::<u32>::try_from(qeu.as_noun().clone()).is_ok());
+        for n in 0..100 {
+            assert_eq!(qeu.top(), Some(n));
+            assert_eq!(qeu.pop(), Some(n));
+            assert!(Qeu::<u32>::try_from(qeu.as_noun().clone()).is_ok());
+        }
+        qeu.extend(200..250);
+        assert_eq!(qeu.tap(), (100..250).collect::<Vec<_>>());
+        assert!(Qeu::<u32>::try_from(qeu.as_noun().clone()).is_ok());
+        while qeu.pop().is_some() {}
+        assert_eq!(qeu, Qeu::new());
+    }
+
+    #[test]
+    fn hoon() {
+        // =/  q  (~(gas to *(qeu @)) (gulf 1 8))
+        // (jam q)
+        let noun = treap::tests::cue("0x4f47.8cf4.7a16.4333.0cdc.34fc.3304.1761");
+        assert_eq!(noun, noun!([6 [8 ~ 7 ~ ~] 5 ~ 4 ~ 2 [3 ~ ~] 1 ~ ~]));
+        let mut qeu: Qeu<u32> = (1..9).collect();
+        assert_eq!(Noun::from(qeu.clone()), noun);
+        assert_eq!(Qeu::<u32>::try_from(&noun).expect("qeu"), qeu);
+
+        // (jam +:~(get to q))
+        let noun = treap::tests::cue("0x14f4.7a16.4333.0cdc.34fc.3304.1761");
+        assert_eq!(noun, noun!([6 [8 ~ 7 ~ ~] 5 ~ 4 ~ 2 [3 ~ ~] ~]));
+        assert_eq!(qeu.pop(), Some(1));
+        assert_eq!(Noun::from(qeu), noun);
+    }
+
+    #[test]
+    fn try_from() {
+        treap::tests::check_try_from(
+            (0..10).collect::<Qeu<u32>>(),
+            [noun!(1), noun!([1 ~]), noun!([1 [2 ~] ~])],
+        );
+        assert!(matches!(
+            Qeu::<u32>::try_from(&noun!([[1 2] ~ ~])),
+            Err(convert::Error::UnexpectedCell)
+        ));
+
+        let qeu: Qeu<u32> = (0..2).collect();
+        let Noun::Cell(tree) = &**qeu.as_noun() else {
+            panic!("expected a cell");
+        };
+        let [n, l, r] = tree.to_array::<3>().expect("treap");
+        let (sub_n, sub_l, sub_r) = split(if l.is_null() { &r } else { &l });
+        let rotated = node(sub_n, sub_l, node(n, sub_r, Rc::new(Noun::null())));
+        assert!(Qeu::<u32>::try_from(rotated).is_err());
+    }
+}
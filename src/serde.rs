@@ -0,0 +1,1162 @@
+//! [Serde] serialization of Rust values into nouns and deserialization of nouns into Rust values,
+//! enabled by the `serde` feature.
+//!
+//! Values map to nouns as follows:
+//!
+//! | Rust                               | Noun                                                       |
+//! |------------------------------------|------------------------------------------------------------|
+//! | `bool`                             | A loobean: `%.y` (`0`) for `true` and `%.n` (`1`) for `false`. |
+//! | Unsigned integers                  | An atom (`@ud`).                                           |
+//! | Signed integers                    | A signed atom (`@s`): `2n` for `n >= 0` and `-2n - 1` for `n < 0`. |
+//! | `f32`, `f64`                       | The IEEE 754 bits as an atom (`@rs`, `@rd`).               |
+//! | `char`, strings                    | A cord (`@t`).                                             |
+//! | Bytes                              | `[length bytes]`, so that trailing zero bytes survive (`octs`). |
+//! | `Option`                           | A unit: `~` for `None` and `[~ x]` for `Some(x)`.          |
+//! | `()`, unit structs                 | `~`.                                                       |
+//! | Newtype structs                    | The value they wrap.                                       |
+//! | Tuples, tuple structs, structs     | The fields as a tuple that nests to the right, `[a b c]`. One field is the field itself, and no fields are `~`. |
+//! | Sequences                          | A null-terminated list.                                    |
+//! | Maps                               | A [`HoonMap`], exactly as Hoon's `++by` builds it.          |
+//! | Unit variants                      | The variant's name as a symbol, `%name`.                   |
+//! | Other variants                     | `[%name data]`, where `data` is laid out like a newtype, tuple or struct. |
+//!
+//! Struct field names aren't stored, so fields are matched up by position. Variant names are
+//! stored as is, so `#[serde(rename_all = "kebab-case")]` makes them Hoon symbols.
+//!
+//! # Examples
+//!
+//! ```
+//! # use noun::{serdes::{Cue, Jam}, Noun};
+//! # use serde::{Deserialize, Serialize};
+//! #[derive(Debug, Deserialize, PartialEq, Serialize)]
+//! #[serde(rename_all = "kebab-case")]
+//! enum Request {
+//!     Scry { path: Vec<String> },
+//!     Ping,
+//! }
+//!
+//! let request = Request::Scry {
+//!     path: vec![String::from("gx"), String::from("pals")],
+//! };
+//! let noun = noun::to_noun(&request).unwrap();
+//! assert_eq!(noun, "[%scry ~['gx' 'pals']]".parse::<Noun>().unwrap());
+//!
+//! let cued = Noun::cue(noun.jam()).unwrap();
+//! assert_eq!(noun::from_noun::<Request>(&cued).unwrap(), request);
+//! ```
+//!
+//! [Serde]: https://serde.rs
+
+use crate::{atom::Atom, cell::Cell, convert, map::HoonMap, noun::Noun, treap, Rc};
+use ::serde::{
+    de::{self, value::BorrowedStrDeserializer, IntoDeserializer, Unexpected},
+    ser, Deserialize, Serialize,
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    iter, result,
+};
+
+/// Errors that occur when serializing to or deserializing from a noun.
+#[derive(Debug)]
+pub enum Error {
+    /// A [`Serialize`] or [`Deserialize`] implementation failed.
+    Custom(String),
+    /// The noun didn't have the shape of the type it was deserialized into.
+    Convert(convert::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
+        match self {
+            Self::Custom(msg) => write!(f, "{}", msg),
+            Self::Convert(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Custom(_) => None,
+            Self::Convert(err) => Some(err),
+        }
+    }
+}
+
+impl From<convert::Error> for Error {
+    fn from(err: convert::Error) -> Self {
+        Self::Convert(err)
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+/// A specialized [`Result`] type for serde operations that return [`serde::Error`] on error.
+///
+/// [`serde::Error`]: [`Error`]
+pub type Result<T> = result::Result<T, Error>;
+
+/// Serializes `value` into a noun.
+///
+/// See the [module docs](self) for how values map to nouns.
+pub fn to_noun<T: Serialize + ?Sized>(value: &T) -> Result<Noun> {
+    value.serialize(Serializer)
+}
+
+/// Deserializes a value from `noun`, borrowing strings and bytes from it where possible.
+///
+/// See the [module docs](self) for how values map to nouns.
+pub fn from_noun<'de, T: Deserialize<'de>>(noun: &'de Noun) -> Result<T> {
+    T::deserialize(Deserializer::new(noun))
+}
+
+/// Converts an `@s` atom's value into the `@ud` atom that stores it.
+fn zigzag(value: i128) -> Atom {
+    Atom::from(((value << 1) ^ (value >> 127)) as u128)
+}
+
+/// Converts an `@ud` atom that stores an `@s` into its value, the inverse of [`zigzag`].
+fn unzigzag(atom: &Atom) -> Option<i128> {
+    let stored = atom.as_u128()?;
+    Some((stored >> 1) as i128 ^ -((stored & 1) as i128))
+}
+
+/// A serializer that produces a [`Noun`], created by [`to_noun`].
+pub struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Noun;
+    type Error = Error;
+    type SerializeSeq = Compound;
+    type SerializeTuple = Compound;
+    type SerializeTupleStruct = Compound;
+    type SerializeTupleVariant = Compound;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = Compound;
+    type SerializeStructVariant = Compound;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, value: bool) -> Result<Noun> {
+        Ok(Noun::from(Atom::from(u8::from(!value))))
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<Noun> {
+        self.serialize_i128(i128::from(value))
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<Noun> {
+        self.serialize_i128(i128::from(value))
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<Noun> {
+        self.serialize_i128(i128::from(value))
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<Noun> {
+        self.serialize_i128(i128::from(value))
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<Noun> {
+        Ok(Noun::from(zigzag(value)))
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<Noun> {
+        Ok(Noun::from(Atom::from(value)))
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<Noun> {
+        Ok(Noun::from(Atom::from(value)))
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<Noun> {
+        Ok(Noun::from(Atom::from(value)))
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<Noun> {
+        Ok(Noun::from(Atom::from(value)))
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<Noun> {
+        Ok(Noun::from(Atom::from(value)))
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<Noun> {
+        Ok(Noun::from(Atom::from(value.to_bits())))
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<Noun> {
+        Ok(Noun::from(Atom::from(value.to_bits())))
+    }
+
+    fn serialize_char(self, value: char) -> Result<Noun> {
+        Ok(Noun::from(Atom::from(
+            value.encode_utf8(&mut [0; 4]) as &str
+        )))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<Noun> {
+        Ok(Noun::from(Atom::from(value)))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Noun> {
+        Ok(Noun::from(Cell::from([
+            Atom::from(value.len()),
+            Atom::from(value.to_vec()),
+        ])))
+    }
+
+    fn serialize_none(self) -> Result<Noun> {
+        Ok(Noun::null())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Noun> {
+        Ok(Noun::from(Cell::from([Noun::null(), to_noun(value)?])))
+    }
+
+    fn serialize_unit(self) -> Result<Noun> {
+        Ok(Noun::null())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Noun> {
+        Ok(Noun::null())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<Noun> {
+        Ok(Noun::from(Atom::from(variant)))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Noun> {
+        to_noun(value)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Noun> {
+        Ok(Noun::from(Cell::from([
+            Noun::from(Atom::from(variant)),
+            to_noun(value)?,
+        ])))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Compound> {
+        Ok(Compound::new(None, len.unwrap_or(0), true))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Compound> {
+        Ok(Compound::new(None, len, false))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Compound> {
+        Ok(Compound::new(None, len, false))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Compound> {
+        Ok(Compound::new(Some(variant), len, false))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeMap> {
+        Ok(SerializeMap {
+            map: HoonMap::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Compound> {
+        Ok(Compound::new(None, len, false))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Compound> {
+        Ok(Compound::new(Some(variant), len, false))
+    }
+}
+
+/// Serializes a sequence, tuple, struct, or variant with fields into a list or a tuple that nests
+/// to the right.
+pub struct Compound {
+    elems: Vec<Rc<Noun>>,
+    /// Whether the elements are a null-terminated list rather than a tuple.
+    list: bool,
+}
+
+impl Compound {
+    fn new(tag: Option<&str>, len: usize, list: bool) -> Self {
+        let mut elems = Vec::with_capacity(len + 2);
+        elems.extend(tag.map(|tag| Rc::<Noun>::from(Atom::from(tag))));
+        Self { elems, list }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.elems.push(Rc::new(to_noun(value)?));
+        Ok(())
+    }
+
+    fn end(mut self) -> Result<Noun> {
+        if self.list {
+            self.elems.push(Rc::new(Noun::null()));
+        }
+        Ok(match self.elems.len() {
+            0 => Noun::null(),
+            1 => {
+                let elem = self.elems.pop().expect("element");
+                Rc::try_unwrap(elem).unwrap_or_else(|elem| (*elem).clone())
+            }
+            _ => Noun::from(Cell::from(self.elems)),
+        })
+    }
+}
+
+impl ser::SerializeSeq for Compound {
+    type Ok = Noun;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Noun> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeTuple for Compound {
+    type Ok = Noun;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Noun> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound {
+    type Ok = Noun;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Noun> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound {
+    type Ok = Noun;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Noun> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeStruct for Compound {
+    type Ok = Noun;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Noun> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeStructVariant for Compound {
+    type Ok = Noun;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Noun> {
+        Compound::end(self)
+    }
+}
+
+/// Serializes a map into a treap.
+pub struct SerializeMap {
+    map: HoonMap,
+    /// The key whose value is serialized next.
+    key: Option<Rc<Noun>>,
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Noun;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
+        self.key = Some(Rc::new(to_noun(key)?));
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let key = self.key.take().ok_or_else(|| {
+            Error::Custom(String::from("serialize_value called before serialize_key"))
+        })?;
+        self.map.put(key, to_noun(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Noun> {
+        if self.key.is_some() {
+            return Err(Error::Custom(String::from(
+                "end called after serialize_key without serialize_value",
+            )));
+        }
+        Ok(Noun::from(self.map))
+    }
+}
+
+/// A deserializer that reads from a [`Noun`], created by [`from_noun`].
+#[derive(Clone, Copy)]
+pub struct Deserializer<'de> {
+    noun: &'de Noun,
+}
+
+impl<'de> Deserializer<'de> {
+    /// Creates a deserializer that reads from `noun`.
+    pub fn new(noun: &'de Noun) -> Self {
+        Self { noun }
+    }
+
+    fn atom(&self) -> Result<&'de Atom> {
+        match self.noun {
+            Noun::Atom(atom) => Ok(atom),
+            Noun::Cell(_) => Err(Error::Convert(convert::Error::UnexpectedCell)),
+        }
+    }
+
+    fn cell(&self) -> Result<&'de Cell> {
+        match self.noun {
+            Noun::Atom(_) => Err(Error::Convert(convert::Error::UnexpectedAtom)),
+            Noun::Cell(cell) => Ok(cell),
+        }
+    }
+
+    fn null(&self) -> Result<()> {
+        if self.noun.is_null() {
+            Ok(())
+        } else {
+            Err(Error::Convert(convert::Error::ExpectedNull))
+        }
+    }
+
+    fn str(&self) -> Result<&'de str> {
+        self.atom()?
+            .as_str()
+            .map_err(|_| Error::Convert(convert::Error::AtomToStr))
+    }
+
+    fn int(&self) -> Result<i128> {
+        unzigzag(self.atom()?).ok_or(Error::Convert(convert::Error::AtomToUint))
+    }
+
+    fn uint(&self) -> Result<u128> {
+        self.atom()?
+            .as_u128()
+            .ok_or(Error::Convert(convert::Error::AtomToUint))
+    }
+}
+
+/// Deserializes an integer of type `$int` from a stored `i128` or `u128`.
+macro_rules! deserialize_int {
+    ($method:ident, $visit:ident, $int:ty, $stored:ident) => {
+        fn $method<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            let value = <$int>::try_from(self.$stored()?)
+                .map_err(|_| Error::Convert(convert::Error::AtomToUint))?;
+            visitor.$visit(value)
+        }
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
+    type Error = Error;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    /// Deserializes an atom as a `u64` if it fits and as bytes otherwise, and a cell as a pair.
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.noun {
+            Noun::Atom(atom) => match atom.as_u64() {
+                Some(value) => visitor.visit_u64(value),
+                None => visitor.visit_borrowed_bytes(atom.as_bytes()),
+            },
+            Noun::Cell(_) => self.deserialize_tuple(2, visitor),
+        }
+    }
+
+    fn deserialize_bool<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.atom()?.as_u8() {
+            Some(0) => visitor.visit_bool(true),
+            Some(1) => visitor.visit_bool(false),
+            _ => Err(Error::Convert(convert::Error::InvalidAura)),
+        }
+    }
+
+    deserialize_int!(deserialize_i8, visit_i8, i8, int);
+    deserialize_int!(deserialize_i16, visit_i16, i16, int);
+    deserialize_int!(deserialize_i32, visit_i32, i32, int);
+    deserialize_int!(deserialize_i64, visit_i64, i64, int);
+    deserialize_int!(deserialize_i128, visit_i128, i128, int);
+    deserialize_int!(deserialize_u8, visit_u8, u8, uint);
+    deserialize_int!(deserialize_u16, visit_u16, u16, uint);
+    deserialize_int!(deserialize_u32, visit_u32, u32, uint);
+    deserialize_int!(deserialize_u64, visit_u64, u64, uint);
+    deserialize_int!(deserialize_u128, visit_u128, u128, uint);
+
+    fn deserialize_f32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let bits = self
+            .atom()?
+            .as_u32()
+            .ok_or(Error::Convert(convert::Error::AtomToUint))?;
+        visitor.visit_f32(f32::from_bits(bits))
+    }
+
+    fn deserialize_f64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let bits = self
+            .atom()?
+            .as_u64()
+            .ok_or(Error::Convert(convert::Error::AtomToUint))?;
+        visitor.visit_f64(f64::from_bits(bits))
+    }
+
+    fn deserialize_char<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let text = self.str()?;
+        let mut chars = text.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => visitor.visit_char(c),
+            _ => Err(de::Error::invalid_value(
+                Unexpected::Str(text),
+                &"a single character",
+            )),
+        }
+    }
+
+    fn deserialize_str<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_borrowed_str(self.str()?)
+    }
+
+    fn deserialize_string<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let cell = self.cell()?;
+        let len = Self::new(cell.head_ref()).uint()?;
+        let bytes = Self::new(cell.tail_ref()).atom()?.as_bytes();
+        match usize::try_from(len) {
+            Ok(len) if len == bytes.len() => visitor.visit_borrowed_bytes(bytes),
+            Ok(len) if len > bytes.len() => {
+                let mut padded = bytes.to_vec();
+                padded.resize(len, 0);
+                visitor.visit_byte_buf(padded)
+            }
+            _ => Err(de::Error::invalid_length(
+                bytes.len(),
+                &"at most the length",
+            )),
+        }
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        match self.noun {
+            Noun::Atom(atom) if atom.is_null() => visitor.visit_none(),
+            Noun::Cell(cell) if cell.head_ref().is_null() => {
+                visitor.visit_some(Self::new(cell.tail_ref()))
+            }
+            _ => Err(Error::Convert(convert::Error::ExpectedNull)),
+        }
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.null()?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(ListAccess { rest: self.noun })
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        if len == 0 {
+            self.null()?;
+        }
+        visitor.visit_seq(TupleAccess {
+            rest: self.noun,
+            remaining: len,
+        })
+    }
+
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if !treap::apt(self.noun, treap::Order::MAP) {
+            return Err(Error::Convert(convert::Error::InvalidTreap));
+        }
+        visitor.visit_map(MapAccess {
+            nodes: treap::Nodes::new(self.noun),
+            value: None,
+        })
+    }
+
+    fn deserialize_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_enum(EnumAccess { noun: self.noun })
+    }
+
+    fn deserialize_identifier<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+}
+
+/// Reads the elements of a null-terminated list.
+struct ListAccess<'de> {
+    rest: &'de Noun,
+}
+
+impl<'de> de::SeqAccess<'de> for ListAccess<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>> {
+        match self.rest {
+            Noun::Atom(atom) if atom.is_null() => Ok(None),
+            Noun::Atom(_) => Err(Error::Convert(convert::Error::ExpectedNull)),
+            Noun::Cell(cell) => {
+                self.rest = cell.tail_ref();
+                seed.deserialize(Deserializer::new(cell.head_ref()))
+                    .map(Some)
+            }
+        }
+    }
+}
+
+/// Reads the elements of a tuple that nests to the right.
+struct TupleAccess<'de> {
+    rest: &'de Noun,
+    remaining: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for TupleAccess<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>> {
+        let elem = match self.remaining {
+            0 => return Ok(None),
+            1 => self.rest,
+            _ => {
+                let cell = Deserializer::new(self.rest).cell()?;
+                self.rest = cell.tail_ref();
+                cell.head_ref()
+            }
+        };
+        self.remaining -= 1;
+        seed.deserialize(Deserializer::new(elem)).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.remaining)
+    }
+}
+
+/// Reads the `[key value]` nodes of a treap.
+struct MapAccess<'de> {
+    nodes: treap::Nodes<'de>,
+    /// The value of the last key read.
+    value: Option<&'de Noun>,
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match self.nodes.next() {
+            Some(node) => {
+                let node = Deserializer::new(node).cell()?;
+                self.value = Some(node.tail_ref());
+                seed.deserialize(Deserializer::new(node.head_ref()))
+                    .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let value = self.value.take().ok_or_else(|| {
+            Error::Custom(String::from("next_value_seed called before next_key_seed"))
+        })?;
+        seed.deserialize(Deserializer::new(value))
+    }
+}
+
+/// Reads an enum variant's tag, which is either the whole noun or its head.
+struct EnumAccess<'de> {
+    noun: &'de Noun,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumAccess<'de> {
+    type Error = Error;
+    type Variant = VariantAccess<'de>;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantAccess<'de>)> {
+        let (tag, data) = match self.noun {
+            Noun::Atom(_) => (Deserializer::new(self.noun), None),
+            Noun::Cell(cell) => (Deserializer::new(cell.head_ref()), Some(cell.tail_ref())),
+        };
+        let variant = seed.deserialize(BorrowedStrDeserializer::<Error>::new(tag.str()?))?;
+        Ok((variant, VariantAccess { data }))
+    }
+}
+
+/// Reads the data of an enum variant, which is `None` for a bare tag.
+struct VariantAccess<'de> {
+    data: Option<&'de Noun>,
+}
+
+impl<'de> VariantAccess<'de> {
+    /// Reads `len` fields of a tuple or struct variant.
+    fn fields<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        match self.data {
+            Some(data) => {
+                de::Deserializer::deserialize_tuple(Deserializer::new(data), len, visitor)
+            }
+            None if len == 0 => visitor.visit_seq(de::value::SeqDeserializer::<_, Error>::new(
+                iter::empty::<()>(),
+            )),
+            None => Err(Error::Convert(convert::Error::UnexpectedAtom)),
+        }
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess<'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        match self.data {
+            Some(_) => Err(Error::Convert(convert::Error::UnexpectedCell)),
+            None => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        match self.data {
+            Some(data) => seed.deserialize(Deserializer::new(data)),
+            None => Err(Error::Convert(convert::Error::UnexpectedAtom)),
+        }
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        self.fields(len, visitor)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.fields(fields.len(), visitor)
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for &'de Noun {
+    type Deserializer = Deserializer<'de>;
+
+    fn into_deserializer(self) -> Deserializer<'de> {
+        Deserializer::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serdes::{Cue, Jam};
+    use ::serde::{Deserialize, Serialize};
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        fmt::Debug,
+    };
+
+    /// Asserts that `value` serializes to the noun written as `text`, survives a jam and cue, and
+    /// deserializes back to itself.
+    fn round_trip<T>(value: T, text: &str)
+    where
+        T: Debug + for<'de> Deserialize<'de> + PartialEq + Serialize,
+    {
+        let noun = to_noun(&value).expect("to noun");
+        assert_eq!(noun, text.parse::<Noun>().expect("noun"), "{}", text);
+        let cued = Noun::cue((&noun).jam()).expect("cue");
+        assert_eq!(from_noun::<T>(&cued).expect("from noun"), value, "{}", text);
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Unit;
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Newtype(u32);
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Ship {
+        name: String,
+        life: u32,
+        sponsor: Option<Box<Ship>>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    #[serde(rename_all = "kebab-case")]
+    enum Event {
+        Boot,
+        Poke(String),
+        Move(i8, i8),
+        SetName { name: String, public: bool },
+        NoFields {},
+    }
+
+    #[test]
+    fn primitives() {
+        round_trip(true, "%.y");
+        round_trip(false, "%.n");
+        round_trip(0u8, "0");
+        round_trip(u64::MAX, "18.446.744.073.709.551.615");
+        round_trip(u128::MAX, "0xffff.ffff.ffff.ffff.ffff.ffff.ffff.ffff");
+        round_trip(0i32, "0");
+        round_trip(1i32, "2");
+        round_trip(-1i32, "1");
+        round_trip(-2i64, "3");
+        round_trip(i8::MIN, "255");
+        round_trip(i128::MIN, "0xffff.ffff.ffff.ffff.ffff.ffff.ffff.ffff");
+        round_trip(1.5f32, "0x3fc0.0000");
+        round_trip(-0.0f64, "0x8000.0000.0000.0000");
+        round_trip('a', "'a'");
+        round_trip('é', "'é'");
+        round_trip(String::from("hello"), "'hello'");
+        round_trip((), "~");
+        round_trip(Unit, "~");
+        round_trip(Newtype(7), "7");
+    }
+
+    #[test]
+    fn compounds() {
+        round_trip(None::<u8>, "~");
+        round_trip(Some(5u8), "[~ 5]");
+        round_trip(Some(None::<u8>), "[~ ~]");
+        round_trip((1u8, 2u8), "[1 2]");
+        round_trip((1u8, (2u8, 3u8), 4u8), "[1 [2 3] 4]");
+        round_trip(Vec::<u8>::new(), "~");
+        round_trip(vec![1u8, 2, 3], "~[1 2 3]");
+        round_trip(vec![vec![true], vec![]], "~[~[%.y] ~]");
+        round_trip(
+            Ship {
+                name: String::from("zod"),
+                life: 1,
+                sponsor: None,
+            },
+            "['zod' 1 ~]",
+        );
+        round_trip(
+            Ship {
+                name: String::from("nec"),
+                life: 2,
+                sponsor: Some(Box::new(Ship {
+                    name: String::from("zod"),
+                    life: 1,
+                    sponsor: None,
+                })),
+            },
+            "['nec' 2 ~ 'zod' 1 ~]",
+        );
+    }
+
+    #[test]
+    fn bytes() {
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Bytes(#[serde(with = "serde_bytes_like")] Vec<u8>);
+
+        /// Serializes a `Vec<u8>` as bytes rather than a sequence.
+        mod serde_bytes_like {
+            use ::serde::{de, Deserializer, Serializer};
+            use std::fmt::{self, Formatter};
+
+            pub fn serialize<S: Serializer>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error> {
+                s.serialize_bytes(bytes)
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+                struct Visitor;
+
+                impl de::Visitor<'_> for Visitor {
+                    type Value = Vec<u8>;
+
+                    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                        write!(f, "bytes")
+                    }
+
+                    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Vec<u8>, E> {
+                        Ok(bytes.to_vec())
+                    }
+
+                    fn visit_byte_buf<E: de::Error>(self, bytes: Vec<u8>) -> Result<Vec<u8>, E> {
+                        Ok(bytes)
+                    }
+                }
+
+                d.deserialize_bytes(Visitor)
+            }
+        }
+
+        round_trip(Bytes(vec![]), "[0 0]");
+        round_trip(Bytes(vec![1, 2]), "[2 0x201]");
+        round_trip(Bytes(vec![1, 0, 0]), "[3 1]");
+
+        let noun: Noun = "[1 0x201]".parse().expect("noun");
+        assert!(from_noun::<Bytes>(&noun).is_err());
+    }
+
+    #[test]
+    fn enums() {
+        round_trip(Event::Boot, "%boot");
+        round_trip(Event::Poke(String::from("hi")), "[%poke 'hi']");
+        round_trip(Event::Move(-1, 1), "[%move 1 2]");
+        round_trip(
+            Event::SetName {
+                name: String::from("zod"),
+                public: false,
+            },
+            "[%set-name 'zod' %.n]",
+        );
+        round_trip(Event::NoFields {}, "%no-fields");
+
+        for text in ["%reboot", "[%boot ~]", "%poke", "[%move 1]", "[1 2]"] {
+            let noun: Noun = text.parse().expect("noun");
+            assert!(from_noun::<Event>(&noun).is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn maps() {
+        round_trip(BTreeMap::<u8, u8>::new(), "~");
+        round_trip(BTreeMap::from([(1u8, 2u8)]), "[[1 2] ~ ~]");
+
+        let map: BTreeMap<String, u32> = (0..100).map(|i| (format!("key-{}", i), i * i)).collect();
+        let noun = to_noun(&map).expect("to noun");
+        let hoon_map: HoonMap = [(3u32, "c"), (1, "a"), (2, "b")]
+            .into_iter()
+            .map(|(key, value)| (Atom::from(key), Atom::from(value)))
+            .collect();
+        assert_eq!(
+            to_noun(&BTreeMap::from([(1u32, "a"), (2, "b"), (3, "c")])).expect("to noun"),
+            Noun::from(hoon_map)
+        );
+        let cued = Noun::cue((&noun).jam()).expect("cue");
+        assert_eq!(
+            from_noun::<BTreeMap<String, u32>>(&cued).expect("from noun"),
+            map
+        );
+
+        let set: BTreeSet<u8> = from_noun(&"~[3 1 2]".parse().expect("noun")).expect("set");
+        assert_eq!(set, BTreeSet::from([1, 2, 3]));
+
+        let noun: Noun = "[1 2]".parse().expect("noun");
+        assert!(from_noun::<BTreeMap<u8, u8>>(&noun).is_err());
+    }
+
+    #[test]
+    fn borrowed() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Borrowed<'a> {
+            name: &'a str,
+            tags: Vec<&'a str>,
+        }
+
+        let noun: Noun = "['zod' 'a' 'b' ~]".parse().expect("noun");
+        assert_eq!(
+            from_noun::<Borrowed>(&noun).expect("from noun"),
+            Borrowed {
+                name: "zod",
+                tags: vec!["a", "b"],
+            }
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let noun: Noun = "[1 2]".parse().expect("noun");
+        assert!(matches!(
+            from_noun::<u8>(&noun),
+            Err(Error::Convert(convert::Error::UnexpectedCell))
+        ));
+        let noun = Noun::from(Atom::from(256u16));
+        assert!(matches!(
+            from_noun::<u8>(&noun),
+            Err(Error::Convert(convert::Error::AtomToUint))
+        ));
+        let noun = Noun::from(Atom::from(2u8));
+        assert!(from_noun::<bool>(&noun).is_err());
+        let noun = Noun::from(Atom::from(vec![0xff]));
+        assert!(matches!(
+            from_noun::<String>(&noun),
+            Err(Error::Convert(convert::Error::AtomToStr))
+        ));
+        let noun: Noun = "'ab'".parse().expect("noun");
+        assert!(matches!(from_noun::<char>(&noun), Err(Error::Custom(_))));
+        let noun: Noun = "[1 2 3]".parse().expect("noun");
+        assert!(from_noun::<Vec<u8>>(&noun).is_err());
+    }
+
+    #[test]
+    fn map_out_of_order() {
+        use ::serde::ser::{SerializeMap, Serializer as _};
+
+        let mut map = Serializer.serialize_map(None).expect("map");
+        assert!(matches!(map.serialize_value(&1u8), Err(Error::Custom(_))));
+        let mut map = Serializer.serialize_map(None).expect("map");
+        map.serialize_key(&1u8).expect("key");
+        assert!(matches!(map.end(), Err(Error::Custom(_))));
+
+        /// Reads a value from a map before its key.
+        #[derive(Debug)]
+        struct ValueFirst;
+
+        impl<'de> Deserialize<'de> for ValueFirst {
+            fn deserialize<D: ::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> std::result::Result<Self, D::Error> {
+                struct Visitor;
+
+                impl<'de> de::Visitor<'de> for Visitor {
+                    type Value = ValueFirst;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        f.write_str("a map")
+                    }
+
+                    fn visit_map<A: de::MapAccess<'de>>(
+                        self,
+                        mut map: A,
+                    ) -> std::result::Result<ValueFirst, A::Error> {
+                        map.next_value::<u8>()?;
+                        Ok(ValueFirst)
+                    }
+                }
+
+                deserializer.deserialize_map(Visitor)
+            }
+        }
+
+        let noun = to_noun(&BTreeMap::from([(1u8, 2u8)])).expect("to noun");
+        assert!(matches!(
+            from_noun::<ValueFirst>(&noun),
+            Err(Error::Custom(_))
+        ));
+    }
+}
@@ -62,51 +62,208 @@
 //! [Cue] is a bitwise decoding of a jammed noun. It's simply the inverse of the jam encoding
 //! described above.
 //!
+//! # Streaming
+//!
+//! [`Jam`] and [`Cue`] operate on jammed nouns held in memory as atoms. To serialize a noun
+//! directly to an [`io::Write`] or deserialize one directly from an [`io::Read`], use
+//! [`jam_to_writer()`] and [`cue_from_reader()`], which encode and decode the little-endian bytes
+//! of a jammed noun a chunk at a time.
+//!
 //! [Jam]: https://developers.urbit.org/reference/hoon/stdlib/2p#jam
 //! [Cue]: https://developers.urbit.org/reference/hoon/stdlib/2p#cue
 
-use crate::{atom::Atom, marker::Nounish};
+use crate::{
+    atom::{self, Atom, Builder as AtomBuilder, Iter as AtomIter},
+    marker::Nounish,
+    noun::{self, Noun},
+    Rc,
+};
 use std::{
+    convert::Infallible,
     fmt::{self, Display, Formatter},
+    io::{self, Read, Write},
     result,
 };
 
+/// The kind of entity in a jammed noun that was being decoded when an error occurred.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Entity {
+    /// The tag of an entity, before the kind of entity is known.
+    Tag,
+    /// An atom.
+    Atom,
+    /// A backreference.
+    Backref,
+    /// A cell.
+    Cell,
+}
+
+impl Display for Entity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
+        match self {
+            Self::Tag => write!(f, "tag"),
+            Self::Atom => write!(f, "atom"),
+            Self::Backref => write!(f, "backreference"),
+            Self::Cell => write!(f, "cell"),
+        }
+    }
+}
+
 /// Errors that occur when serializing/deserializing.
+///
+/// Errors that occur partway through decoding a jammed noun carry `pos`, the bit offset of the
+/// start of the entity that was being decoded.
 #[derive(Debug)]
 pub enum Error {
-    /// Building up an atom with [`atom::Builder`](crate::atom::Builder) failed.
-    AtomBuilding,
-    /// A key lookup in the cache failed.
-    CacheMiss,
-    /// A corrupt backreference was encountered.
-    InvalidBackref,
+    /// A backreference referred to an index at which no noun was decoded.
+    CacheMiss { pos: u64, idx: u64 },
+    /// A backreference's index was too large to refer to any part of a jammed noun.
+    InvalidBackref { pos: u64, idx: Atom },
+    /// Reading a jammed noun failed.
+    Io(io::Error),
     /// A corrupt length encoding was encountered.
-    InvalidLen,
-    /// A corrupt tag was encountered.
-    InvalidTag,
+    InvalidLen { pos: u64, entity: Entity },
+    /// A decoded atom was longer than [`CueLimits::max_atom_bits`].
+    MaxAtomBitsExceeded { pos: u64, entity: Entity },
+    /// More cells were decoded than [`CueLimits::max_cells`].
+    MaxCellsExceeded { pos: u64 },
+    /// Cells were nested more deeply than [`CueLimits::max_depth`].
+    MaxDepthExceeded { pos: u64 },
+    /// A jammed noun was longer than [`CueLimits::max_total_bits`].
+    MaxTotalBitsExceeded,
+    /// An atom was decoded when a cell was expected.
+    UnexpectedAtom,
+    /// A cell was decoded when an atom was expected.
+    UnexpectedCell,
+    /// A jammed noun ended partway through an entity.
+    UnexpectedEof { pos: u64, entity: Entity },
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
         match self {
-            Self::AtomBuilding => write!(f, "building an atom a bit at a time failed"),
-            Self::CacheMiss => write!(
+            Self::CacheMiss { pos, idx } => write!(
+                f,
+                "the backreference at bit {} refers to bit {}, where no noun begins",
+                pos, idx
+            ),
+            Self::InvalidBackref { pos, idx } => write!(
+                f,
+                "the backreference at bit {} refers to an out-of-range index {}",
+                pos, idx
+            ),
+            Self::Io(err) => write!(f, "failed to read the jammed noun: {}", err),
+            Self::InvalidLen { pos, entity } => {
+                write!(f, "the {} at bit {} has an invalid length", entity, pos)
+            }
+            Self::MaxAtomBitsExceeded { pos, entity } => write!(
+                f,
+                "the {} at bit {} exceeds the maximum atom length",
+                entity, pos
+            ),
+            Self::MaxCellsExceeded { pos } => write!(
+                f,
+                "the cell at bit {} exceeds the maximum number of cells",
+                pos
+            ),
+            Self::MaxDepthExceeded { pos } => write!(
+                f,
+                "the cell at bit {} exceeds the maximum nesting depth",
+                pos
+            ),
+            Self::MaxTotalBitsExceeded => write!(f, "the jammed noun exceeds the maximum length"),
+            Self::UnexpectedAtom => write!(f, "decoded an atom when a cell was expected"),
+            Self::UnexpectedCell => write!(f, "decoded a cell when an atom was expected"),
+            Self::UnexpectedEof { pos, entity } => write!(
                 f,
-                "a key that was expected to be in the cache was missing from the cache"
+                "the jammed noun ended partway through the {} at bit {}",
+                entity, pos
             ),
-            Self::InvalidBackref => write!(f, "encountered an invalid backreference"),
-            Self::InvalidLen => write!(f, "encountered an invalid length"),
-            Self::InvalidTag => write!(f, "encountered an invalid tag"),
         }
     }
 }
 
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
 /// A specialized [`Result`] type for serialization/deserialization operations that return
 /// [`serdes::Error`] on error.
 ///
 /// [`serdes::Error`]: [`Error`]
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Bounds on the resources that deserializing a single jammed noun may consume.
+///
+/// Decoding fails with the corresponding [`Error`] variant as soon as any limit is exceeded, so a
+/// small malicious input can't trigger a huge allocation or an unbounded amount of work.
+///
+/// [`Cue::cue()`] decodes with the [default](Self::default()) limits, which allow jammed nouns of
+/// up to 512 MiB with up to 2^26 cells. That's smaller than the largest nouns a trusted source like
+/// a pier snapshot can hold, so trusted input can be decoded with [`Self::unlimited()`] through
+/// [`Noun::cue_with_limits()`](crate::Noun::cue_with_limits()) instead.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, cell::Cell, serdes::{CueLimits, Error, Jam}, Noun};
+/// let jammed = Cell::from([1u8, 2u8, 3u8]).jam();
+/// let limits = CueLimits {
+///     max_depth: 1,
+///     ..CueLimits::default()
+/// };
+/// assert!(matches!(
+///     Noun::cue_with_limits(jammed, &limits),
+///     Err(Error::MaxDepthExceeded { pos: 6 })
+/// ));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CueLimits {
+    /// Maximum number of cells that any decoded entity may be nested within.
+    pub max_depth: usize,
+    /// Maximum length in bits of any decoded atom.
+    pub max_atom_bits: usize,
+    /// Maximum number of cells decoded, not counting cells referred to by backreferences.
+    pub max_cells: usize,
+    /// Maximum length in bits of the jammed noun.
+    pub max_total_bits: usize,
+}
+
+impl CueLimits {
+    /// Returns limits that never stop decoding, for input from a trusted source such as the
+    /// runtime or a pier snapshot.
+    pub const fn unlimited() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            max_atom_bits: usize::MAX,
+            max_cells: usize::MAX,
+            max_total_bits: usize::MAX,
+        }
+    }
+}
+
+impl Default for CueLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 1 << 24,
+            max_atom_bits: u32::MAX as usize,
+            max_cells: 1 << 26,
+            max_total_bits: u32::MAX as usize,
+        }
+    }
+}
+
 /// Serialize a noun type into a bitstream.
 #[doc(alias("serialize", "serialization"))]
 pub trait Jam: Nounish {
@@ -120,6 +277,466 @@ pub trait Jam: Nounish {
 pub trait Cue: Nounish + Sized {
     /// Deserializes ("cues") a jammed noun (a bitstream represented as an atom), returning the
     /// resulting noun type.
+    ///
+    /// Decoding is bounded by the [default](CueLimits::default()) limits, so untrusted input can be
+    /// cued this way. To decode with other limits, or with [none](CueLimits::unlimited()) for
+    /// trusted input, use [`Noun::cue_with_limits()`](crate::Noun::cue_with_limits()).
     #[doc(alias("deserialize", "deserialization"))]
     fn cue(jammed_noun: Atom) -> Result<Self>;
 }
+
+/// Serializes ("jams") a noun to `writer` as the little-endian bytes of the jammed noun.
+///
+/// The bytes written are the same as the bytes of the atom that [`Jam::jam()`] returns, but they're
+/// written a chunk at a time rather than built up in memory. `writer` is flushed once the whole
+/// noun has been written.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, serdes::{self, Jam}, Noun};
+/// let noun = Noun::from(Cell::from([0u8, 19u8]));
+/// let mut bytes = Vec::new();
+/// serdes::jam_to_writer(&noun, &mut bytes).unwrap();
+/// assert_eq!(bytes, noun.jam().as_bytes());
+/// ```
+pub fn jam_to_writer(noun: &Noun, writer: impl Write) -> io::Result<()> {
+    let mut bits = BitWriter::new(writer);
+    noun::encode(vec![noun], &mut bits)?;
+    bits.finish()
+}
+
+/// Serializes ("jams") a noun into the little-endian bytes of the jammed noun.
+///
+/// See [`jam_to_writer()`].
+pub fn jam_to_bytes(noun: &Noun) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    jam_to_writer(noun, &mut bytes).expect("write to vector");
+    bytes
+}
+
+/// Deserializes ("cues") a noun from the little-endian bytes of a jammed noun read from `reader`.
+///
+/// Unlike [`Cue::cue()`], decoding is [unlimited](CueLimits::unlimited()), so only cue trusted
+/// input this way. To decode untrusted input, use [`cue_from_reader_with_limits()`].
+///
+/// The input is read and decoded a chunk at a time, so only the noun being built and a bounded
+/// amount of input are held in memory. Because `reader` is read a chunk at a time, bytes past the
+/// end of the jammed noun may be consumed.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{cell::Cell, serdes::{self, Jam}, Noun};
+/// let noun = Noun::from(Cell::from([0u8, 19u8]));
+/// let bytes = serdes::jam_to_bytes(&noun);
+/// assert_eq!(serdes::cue_from_reader(&bytes[..]).unwrap(), noun);
+/// ```
+pub fn cue_from_reader(reader: impl Read) -> Result<Noun> {
+    cue_from_reader_with_limits(reader, &CueLimits::unlimited())
+}
+
+/// Deserializes ("cues") a noun from the little-endian bytes of a jammed noun read from `reader`,
+/// bounded by `limits`.
+///
+/// See [`cue_from_reader()`].
+pub fn cue_from_reader_with_limits(reader: impl Read, limits: &CueLimits) -> Result<Noun> {
+    let mut bits = BitReader::new(reader, limits.max_total_bits);
+    let noun = noun::decode(&mut bits, limits)?;
+    // The cache used during decoding has been dropped by the time `decode()` returns, which
+    // guarantees that the top level noun has exactly one reference and makes it safe to move out
+    // of the Rc.
+    Ok(Rc::try_unwrap(noun).unwrap())
+}
+
+/// Deserializes ("cues") a noun from the little-endian bytes of a jammed noun.
+///
+/// See [`cue_from_reader()`].
+pub fn cue_from_bytes(bytes: &[u8]) -> Result<Noun> {
+    cue_from_reader(bytes)
+}
+
+/// Number of bytes read or written at a time when streaming a jammed noun.
+const CHUNK_LEN: usize = 64 * 1024;
+
+/// A destination for the bits of a jammed noun.
+pub(crate) trait BitSink {
+    /// Error that occurs when writing bits.
+    type Error;
+
+    /// Returns the number of bits written so far.
+    fn pos(&self) -> u64;
+
+    /// Writes the low `n` bits of `bits`, from least significant to most significant.
+    fn push_bits(&mut self, bits: u64, n: usize) -> result::Result<(), Self::Error>;
+
+    /// Writes the bits of `atom`, from least significant to most significant.
+    fn push_atom(&mut self, atom: &Atom) -> result::Result<(), Self::Error>;
+}
+
+impl BitSink for AtomBuilder {
+    type Error = Infallible;
+
+    fn pos(&self) -> u64 {
+        AtomBuilder::pos(self) as u64
+    }
+
+    fn push_bits(&mut self, bits: u64, n: usize) -> result::Result<(), Self::Error> {
+        AtomBuilder::push_bits(self, bits, n);
+        Ok(())
+    }
+
+    fn push_atom(&mut self, atom: &Atom) -> result::Result<(), Self::Error> {
+        AtomBuilder::push_atom(self, atom);
+        Ok(())
+    }
+}
+
+/// Writes the bits of a jammed noun to a writer a chunk at a time.
+struct BitWriter<W> {
+    writer: W,
+    /// Bits that haven't been written to `writer` yet.
+    bits: AtomBuilder,
+    /// Number of bits written to `writer`.
+    written: u64,
+}
+
+impl<W: Write> BitWriter<W> {
+    fn new(writer: W) -> Self {
+        Self {
+            writer,
+            bits: Atom::builder(),
+            written: 0,
+        }
+    }
+
+    /// Writes the complete bytes of `bits` to `writer` if there's at least a chunk of them.
+    fn write_chunk(&mut self) -> io::Result<()> {
+        if self.bits.pos() >= CHUNK_LEN * 8 {
+            let bytes = self.bits.drain_bytes();
+            self.writer.write_all(&bytes)?;
+            self.written += bytes.len() as u64 * 8;
+        }
+        Ok(())
+    }
+
+    /// Writes the remaining bits to `writer` and flushes it.
+    fn finish(mut self) -> io::Result<()> {
+        // A jammed noun always ends with a high bit, so converting the remaining bits to an atom
+        // doesn't discard any of them.
+        self.writer.write_all(self.bits.into_atom().as_bytes())?;
+        self.writer.flush()
+    }
+}
+
+impl<W: Write> BitSink for BitWriter<W> {
+    type Error = io::Error;
+
+    fn pos(&self) -> u64 {
+        self.written + self.bits.pos() as u64
+    }
+
+    fn push_bits(&mut self, bits: u64, n: usize) -> io::Result<()> {
+        self.bits.push_bits(bits, n);
+        self.write_chunk()
+    }
+
+    fn push_atom(&mut self, atom: &Atom) -> io::Result<()> {
+        self.bits.push_atom(atom);
+        self.write_chunk()
+    }
+}
+
+/// A source of the bits of a jammed noun.
+pub(crate) trait BitSource {
+    /// Returns the number of bits read so far.
+    fn pos(&self) -> u64;
+
+    /// Reads the next bit, returning `None` at the end of the input.
+    fn next_bit(&mut self) -> Result<Option<bool>>;
+
+    /// Reads the next `n` bits (at most 64) as the low `n` bits of a `u64`, returning `None`
+    /// without advancing if fewer than `n` bits remain.
+    fn next_bits(&mut self, n: usize) -> Result<Option<u64>>;
+
+    /// Reads the next `n` bits as an atom, returning `None` without advancing if fewer than `n`
+    /// bits remain.
+    fn next_atom(&mut self, n: usize) -> Result<Option<Atom>>;
+}
+
+impl BitSource for AtomIter<'_> {
+    fn pos(&self) -> u64 {
+        AtomIter::pos(self) as u64
+    }
+
+    fn next_bit(&mut self) -> Result<Option<bool>> {
+        Ok(self.next())
+    }
+
+    fn next_bits(&mut self, n: usize) -> Result<Option<u64>> {
+        Ok(AtomIter::next_bits(self, n))
+    }
+
+    fn next_atom(&mut self, n: usize) -> Result<Option<Atom>> {
+        Ok(AtomIter::next_atom(self, n))
+    }
+}
+
+/// Reads the bits of a jammed noun from a reader a chunk at a time.
+struct BitReader<R> {
+    reader: R,
+    /// Bytes read from `reader`, the first of which contains the next unread bit.
+    buf: Vec<u8>,
+    /// Offset of the next unread bit in `buf`.
+    bit_idx: usize,
+    /// Number of bits discarded from the start of `buf`.
+    discarded: u64,
+    /// Maximum number of bits that may be read.
+    max_bits: usize,
+    /// Whether `reader` has reached the end of its input.
+    eof: bool,
+}
+
+impl<R: Read> BitReader<R> {
+    fn new(reader: R, max_bits: usize) -> Self {
+        Self {
+            reader,
+            buf: Vec::new(),
+            bit_idx: 0,
+            discarded: 0,
+            max_bits,
+            eof: false,
+        }
+    }
+
+    /// Reads from `reader` until at least `n` unread bits are buffered, returning `false` if the
+    /// input ends first.
+    fn fill(&mut self, n: usize) -> Result<bool> {
+        if self.discarded + (self.bit_idx + n) as u64 > self.max_bits as u64 {
+            return Err(Error::MaxTotalBitsExceeded);
+        }
+        if self.buf.len() * 8 >= self.bit_idx + n {
+            return Ok(true);
+        }
+
+        let consumed = self.bit_idx / 8;
+        self.buf.drain(..consumed);
+        self.bit_idx -= consumed * 8;
+        self.discarded += consumed as u64 * 8;
+
+        while self.buf.len() * 8 < self.bit_idx + n {
+            if self.eof {
+                return Ok(false);
+            }
+            let len = self.buf.len();
+            self.buf.resize(len + CHUNK_LEN, 0);
+            let read = loop {
+                match self.reader.read(&mut self.buf[len..]) {
+                    Ok(read) => break read,
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                    Err(err) => {
+                        self.buf.truncate(len);
+                        return Err(Error::Io(err));
+                    }
+                }
+            };
+            self.buf.truncate(len + read);
+            self.eof = read == 0;
+        }
+        Ok(true)
+    }
+}
+
+impl<R: Read> BitSource for BitReader<R> {
+    fn pos(&self) -> u64 {
+        self.discarded + self.bit_idx as u64
+    }
+
+    fn next_bit(&mut self) -> Result<Option<bool>> {
+        if !self.fill(1)? {
+            return Ok(None);
+        }
+        let bit = self.buf[self.bit_idx / 8] & (1 << (self.bit_idx % 8)) != 0;
+        self.bit_idx += 1;
+        Ok(Some(bit))
+    }
+
+    fn next_bits(&mut self, n: usize) -> Result<Option<u64>> {
+        assert!(n <= 64, "cannot read more than 64 bits at a time");
+        if !self.fill(n)? {
+            return Ok(None);
+        }
+        // A 64-bit read at an unaligned position spans at most nine bytes.
+        let mut word = 0u128;
+        for (i, byte) in self.buf[self.bit_idx / 8..].iter().take(9).enumerate() {
+            word |= u128::from(*byte) << (8 * i);
+        }
+        let bits = (word >> (self.bit_idx % 8)) as u64;
+        self.bit_idx += n;
+        Ok(Some(if n == 64 { bits } else { bits & ((1 << n) - 1) }))
+    }
+
+    fn next_atom(&mut self, n: usize) -> Result<Option<Atom>> {
+        if !self.fill(n)? {
+            return Ok(None);
+        }
+        let atom = atom::bits_to_atom(&self.buf, self.bit_idx, n);
+        self.bit_idx += n;
+        Ok(Some(atom))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::Cell;
+
+    /// A reader that returns at most `n` bytes per read, interrupting every other read.
+    struct Trickle<'a> {
+        bytes: &'a [u8],
+        n: usize,
+        interrupt: bool,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            let n = self.n.min(buf.len()).min(self.bytes.len());
+            buf[..n].copy_from_slice(&self.bytes[..n]);
+            self.bytes = &self.bytes[n..];
+            Ok(n)
+        }
+    }
+
+    /// A writer that fails after accepting `n` bytes.
+    struct Failing(usize);
+
+    impl Write for Failing {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.0 < buf.len() {
+                return Err(io::ErrorKind::BrokenPipe.into());
+            }
+            self.0 -= buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// A list of atoms of varying widths, some repeated, whose encoding spans several chunks.
+    fn noun() -> Noun {
+        let mut elems = Vec::new();
+        for i in 0..4000usize {
+            elems.push(Rc::<Noun>::from(
+                (Atom::from(1u8) << (i % 300)) | Atom::from(i),
+            ));
+            if i % 5 == 0 {
+                elems.push(elems[i / 2].clone());
+            }
+        }
+        elems.push(Rc::<Noun>::from(Atom::from(vec![0xa5; 3 * CHUNK_LEN])));
+        let mut list = Rc::new(Noun::null());
+        for elem in elems.into_iter().rev() {
+            list = Rc::<Noun>::from(Cell::from([elem, list]));
+        }
+        Rc::try_unwrap(list).unwrap()
+    }
+
+    #[test]
+    fn jam_to_writer() {
+        let noun = noun();
+        let jammed = noun.clone().jam();
+        assert!(jammed.as_bytes().len() > 4 * CHUNK_LEN);
+
+        let bytes = super::jam_to_bytes(&noun);
+        assert_eq!(bytes, jammed.as_bytes());
+
+        let atom = Noun::from(Atom::from(19u8));
+        assert_eq!(super::jam_to_bytes(&atom), atom.clone().jam().as_bytes());
+        assert_eq!(super::jam_to_bytes(&Noun::null()), [0b10]);
+
+        let err = super::jam_to_writer(&noun, Failing(2 * CHUNK_LEN)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    }
+
+    #[test]
+    fn cue_from_reader() {
+        let noun = noun();
+        let bytes = super::jam_to_bytes(&noun);
+        assert_eq!(super::cue_from_bytes(&bytes).expect("cue"), noun);
+
+        let trickle = Trickle {
+            bytes: &bytes,
+            n: 1000,
+            interrupt: false,
+        };
+        assert_eq!(super::cue_from_reader(trickle).expect("cue"), noun);
+
+        // Trailing bytes after the jammed noun are ignored.
+        let atom = Noun::from(Atom::from(19u8));
+        let mut bytes = super::jam_to_bytes(&atom);
+        bytes.extend([0xff; 10]);
+        assert_eq!(super::cue_from_bytes(&bytes).expect("cue"), atom);
+    }
+
+    #[test]
+    fn cue_from_reader_errors() {
+        let bytes = super::jam_to_bytes(&noun());
+
+        let err = super::cue_from_bytes(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnexpectedEof {
+                entity: Entity::Atom,
+                ..
+            }
+        ));
+
+        let err = super::cue_from_bytes(&[]).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnexpectedEof {
+                pos: 0,
+                entity: Entity::Tag
+            }
+        ));
+
+        let limits = CueLimits {
+            max_total_bits: bytes.len() * 8 - 8,
+            ..CueLimits::default()
+        };
+        let err = super::cue_from_reader_with_limits(&bytes[..], &limits).unwrap_err();
+        assert!(matches!(err, Error::MaxTotalBitsExceeded));
+
+        struct Broken;
+
+        impl Read for Broken {
+            fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::ConnectionReset.into())
+            }
+        }
+
+        let err = super::cue_from_reader(Broken).unwrap_err();
+        assert!(matches!(&err, Error::Io(err) if err.kind() == io::ErrorKind::ConnectionReset));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn cue_from_reader_unlimited() {
+        let noun = Noun::from(Cell::from([1u8, 2u8, 3u8]));
+        let bytes = super::jam_to_bytes(&noun);
+        let limits = CueLimits {
+            max_cells: 1,
+            ..CueLimits::default()
+        };
+        let err = super::cue_from_reader_with_limits(&bytes[..], &limits).unwrap_err();
+        assert!(matches!(err, Error::MaxCellsExceeded { .. }));
+        assert_eq!(super::cue_from_reader(&bytes[..]).expect("cue"), noun);
+        assert_eq!(super::cue_from_bytes(&bytes).expect("cue"), noun);
+    }
+}
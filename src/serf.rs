@@ -0,0 +1,832 @@
+//! Messages of the protocol between the Urbit runtime ("king") and its worker process ("serf").
+//!
+//! The king sends the serf [`Writ`]s and the serf sends the king [`Plea`]s, each as a noun in a
+//! [newt] frame. Both convert to and from nouns with [`From`] and [`TryFrom`]:
+//!
+//! ```
+//! # use noun::{serf::{Live, Writ}, Noun};
+//! let writ = Writ::Live(Live::Save { event: 42 });
+//! let noun = Noun::from(writ.clone());
+//! assert_eq!(Writ::try_from(&noun).unwrap(), writ);
+//! ```
+//!
+//! [`MockSerf`] stands in for a serf process so that a king can be tested without the runtime.
+//!
+//! The shapes of the messages follow the `writ` and `plea` types in the runtime's [`lord.c`].
+//!
+//! [`lord.c`]: https://github.com/urbit/vere/blob/develop/pkg/vere/lord.c
+
+use crate::{
+    atom::Atom,
+    cell::Cell,
+    convert,
+    newt::{self, NewtReader, NewtWriter},
+    noun::Noun,
+    serdes::{self, CueLimits},
+    Rc,
+};
+use std::{
+    io::{self, PipeReader, PipeWriter},
+    thread::{self, JoinHandle},
+};
+
+/// A message from the king to the serf.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Writ {
+    /// `[%live ...]`: a command that doesn't change the serf's state.
+    Live(Live),
+    /// `[%peek mil=@ sam=*]`: a scry request, which the serf answers with [`Plea::Peek`].
+    Peek {
+        /// Milliseconds the request may run before it's interrupted, or `0` for no limit.
+        timeout: u32,
+        /// The request.
+        request: Rc<Noun>,
+    },
+    /// `[%play eve=@ lit=(list *)]`: events to replay, the first of which is event number
+    /// `event`, which the serf answers with [`Plea::Play`].
+    Play {
+        /// The number of the first event.
+        event: u64,
+        /// The events.
+        events: Vec<Rc<Noun>>,
+    },
+    /// `[%work mil=@ job=*]`: a new event, which the serf answers with [`Plea::Work`].
+    Work {
+        /// Milliseconds the event may run before it's interrupted, or `0` for no limit.
+        timeout: u32,
+        /// The event, a `(pair @da ovum)`.
+        job: Rc<Noun>,
+    },
+}
+
+/// A command that doesn't change the serf's state, which the serf answers with [`Plea::Live`]
+/// (other than [`Live::Exit`], which it doesn't answer).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Live {
+    /// `[%cram eve=@]`: writes a portable snapshot at event number `event`.
+    Cram {
+        /// The current event number.
+        event: u64,
+    },
+    /// `[%exit cod=@]`: exits with status `code`.
+    Exit {
+        /// The exit status.
+        code: u8,
+    },
+    /// `[%save eve=@]`: writes a snapshot at event number `event`.
+    Save {
+        /// The current event number.
+        event: u64,
+    },
+    /// `[%meld ~]`: deduplicates the serf's memory.
+    Meld,
+    /// `[%pack ~]`: defragments the serf's memory.
+    Pack,
+}
+
+/// A message from the serf to the king.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Plea {
+    /// `[%live ~]`: the answer to a [`Writ::Live`].
+    Live,
+    /// `[%ripe [pro=@ hon=@ nok=@] eve=@ mug=@]`: the serf is ready, which it sends once it starts.
+    Ripe {
+        /// The protocol version.
+        protocol: u32,
+        /// The Hoon kelvin version.
+        hoon: u32,
+        /// The Nock kelvin version.
+        nock: u32,
+        /// The number of the last event the serf processed.
+        event: u64,
+        /// The mug of the serf's state.
+        mug: u32,
+    },
+    /// `[%slog pri=@ tank]`: a message to print.
+    Slog {
+        /// The priority of the message, where `0` is the lowest.
+        priority: u32,
+        /// The message, a `tank`.
+        tank: Rc<Noun>,
+    },
+    /// `[%flog cord]`: a message to write to the king's log.
+    Flog(Atom),
+    /// `[%peek ...]`: the answer to a [`Writ::Peek`].
+    Peek(PeekReply),
+    /// `[%play ...]`: the answer to a [`Writ::Play`].
+    Play(PlayReply),
+    /// `[%work ...]`: the answer to a [`Writ::Work`].
+    Work(WorkReply),
+}
+
+/// The outcome of a [`Writ::Peek`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PeekReply {
+    /// `[%done dat=(unit (cask))]`: the result of the request, if any.
+    Done(Option<Rc<Noun>>),
+    /// `[%bail dud=goof]`: the request crashed.
+    Bail(Rc<Noun>),
+}
+
+/// The outcome of a [`Writ::Play`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PlayReply {
+    /// `[%done mug=@]`: every event was replayed.
+    Done {
+        /// The mug of the serf's state.
+        mug: u32,
+    },
+    /// `[%bail eve=@ mug=@ dud=goof]`: an event crashed, and none after it were replayed.
+    Bail {
+        /// The number of the last event that was replayed.
+        event: u64,
+        /// The mug of the serf's state.
+        mug: u32,
+        /// The crash.
+        goof: Rc<Noun>,
+    },
+}
+
+/// The outcome of a [`Writ::Work`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WorkReply {
+    /// `[%done eve=@ mug=@ fec=(list ovum)]`: the event was processed.
+    Done {
+        /// The number of the event.
+        event: u64,
+        /// The mug of the serf's state.
+        mug: u32,
+        /// The effects of the event.
+        effects: Vec<Rc<Noun>>,
+    },
+    /// `[%swap eve=@ mug=@ job=(pair @da ovum) fec=(list ovum)]`: the event crashed, and a
+    /// replacement event was processed in its place.
+    Swap {
+        /// The number of the event.
+        event: u64,
+        /// The mug of the serf's state.
+        mug: u32,
+        /// The replacement event.
+        job: Rc<Noun>,
+        /// The effects of the replacement event.
+        effects: Vec<Rc<Noun>>,
+    },
+    /// `[%bail lud=(list goof)]`: the event crashed and wasn't processed.
+    Bail(Vec<Rc<Noun>>),
+}
+
+impl TryFrom<&Noun> for Writ {
+    type Error = convert::Error;
+
+    fn try_from(noun: &Noun) -> Result<Self, Self::Error> {
+        let (tag, data) = tagged(noun)?;
+        match tag {
+            "live" => {
+                let (tag, data) = tagged(data)?;
+                let live = match tag {
+                    "cram" => Live::Cram { event: uint(data)? },
+                    "exit" => Live::Exit { code: uint(data)? },
+                    "save" => Live::Save { event: uint(data)? },
+                    "meld" => null(data).map(|()| Live::Meld)?,
+                    "pack" => null(data).map(|()| Live::Pack)?,
+                    _ => return Err(convert::Error::InvalidTag),
+                };
+                Ok(Self::Live(live))
+            }
+            "peek" => {
+                let data = cell(data)?;
+                Ok(Self::Peek {
+                    timeout: uint(data.head_ref())?,
+                    request: data.tail(),
+                })
+            }
+            "play" => {
+                let data = cell(data)?;
+                Ok(Self::Play {
+                    event: uint(data.head_ref())?,
+                    events: list(data.tail_ref())?,
+                })
+            }
+            "work" => {
+                let data = cell(data)?;
+                Ok(Self::Work {
+                    timeout: uint(data.head_ref())?,
+                    job: data.tail(),
+                })
+            }
+            _ => Err(convert::Error::InvalidTag),
+        }
+    }
+}
+
+impl From<Writ> for Noun {
+    fn from(writ: Writ) -> Self {
+        match &writ {
+            Writ::Live(live) => {
+                let live = match live {
+                    Live::Cram { event } => pair("cram", atom(*event)),
+                    Live::Exit { code } => pair("exit", atom(*code)),
+                    Live::Save { event } => pair("save", atom(*event)),
+                    Live::Meld => pair("meld", atom(0u8)),
+                    Live::Pack => pair("pack", atom(0u8)),
+                };
+                Self::from(Cell::from([atom("live"), live]))
+            }
+            Writ::Peek { timeout, request } => {
+                Self::from(Cell::from([atom("peek"), atom(*timeout), request.clone()]))
+            }
+            Writ::Play { event, events } => {
+                Self::from(Cell::from([atom("play"), atom(*event), to_list(events)]))
+            }
+            Writ::Work { timeout, job } => {
+                Self::from(Cell::from([atom("work"), atom(*timeout), job.clone()]))
+            }
+        }
+    }
+}
+
+impl TryFrom<&Noun> for Plea {
+    type Error = convert::Error;
+
+    fn try_from(noun: &Noun) -> Result<Self, Self::Error> {
+        let (tag, data) = tagged(noun)?;
+        match tag {
+            "live" => null(data).map(|()| Self::Live),
+            "ripe" => {
+                let [version, event, mug] = tuple(data)?;
+                let [protocol, hoon, nock] = tuple(&version)?;
+                Ok(Self::Ripe {
+                    protocol: uint(&protocol)?,
+                    hoon: uint(&hoon)?,
+                    nock: uint(&nock)?,
+                    event: uint(&event)?,
+                    mug: uint(&mug)?,
+                })
+            }
+            "slog" => {
+                let data = cell(data)?;
+                Ok(Self::Slog {
+                    priority: uint(data.head_ref())?,
+                    tank: data.tail(),
+                })
+            }
+            "flog" => match data {
+                Noun::Atom(cord) => Ok(Self::Flog(cord.clone())),
+                Noun::Cell(_) => Err(convert::Error::UnexpectedCell),
+            },
+            "peek" => {
+                let (tag, data) = tagged(data)?;
+                let reply = match tag {
+                    "done" => PeekReply::Done(unit(data)?),
+                    "bail" => PeekReply::Bail(tail(noun, 2)?),
+                    _ => return Err(convert::Error::InvalidTag),
+                };
+                Ok(Self::Peek(reply))
+            }
+            "play" => {
+                let (tag, data) = tagged(data)?;
+                let reply = match tag {
+                    "done" => PlayReply::Done { mug: uint(data)? },
+                    "bail" => {
+                        let [event, mug, goof] = tuple(data)?;
+                        PlayReply::Bail {
+                            event: uint(&event)?,
+                            mug: uint(&mug)?,
+                            goof,
+                        }
+                    }
+                    _ => return Err(convert::Error::InvalidTag),
+                };
+                Ok(Self::Play(reply))
+            }
+            "work" => {
+                let (tag, data) = tagged(data)?;
+                let reply = match tag {
+                    "done" => {
+                        let [event, mug, effects] = tuple(data)?;
+                        WorkReply::Done {
+                            event: uint(&event)?,
+                            mug: uint(&mug)?,
+                            effects: list(&effects)?,
+                        }
+                    }
+                    "swap" => {
+                        let [event, mug, job, effects] = tuple(data)?;
+                        WorkReply::Swap {
+                            event: uint(&event)?,
+                            mug: uint(&mug)?,
+                            job,
+                            effects: list(&effects)?,
+                        }
+                    }
+                    "bail" => WorkReply::Bail(list(data)?),
+                    _ => return Err(convert::Error::InvalidTag),
+                };
+                Ok(Self::Work(reply))
+            }
+            _ => Err(convert::Error::InvalidTag),
+        }
+    }
+}
+
+impl From<Plea> for Noun {
+    fn from(plea: Plea) -> Self {
+        match &plea {
+            Plea::Live => Self::from(Cell::from([atom("live"), atom(0u8)])),
+            Plea::Ripe {
+                protocol,
+                hoon,
+                nock,
+                event,
+                mug,
+            } => Self::from(Cell::from([
+                atom("ripe"),
+                Rc::<Noun>::from(Cell::from([atom(*protocol), atom(*hoon), atom(*nock)])),
+                atom(*event),
+                atom(*mug),
+            ])),
+            Plea::Slog { priority, tank } => {
+                Self::from(Cell::from([atom("slog"), atom(*priority), tank.clone()]))
+            }
+            Plea::Flog(cord) => Self::from(Cell::from([atom("flog"), atom(cord.clone())])),
+            Plea::Peek(reply) => {
+                let reply = match reply {
+                    PeekReply::Done(None) => pair("done", atom(0u8)),
+                    PeekReply::Done(Some(data)) => pair(
+                        "done",
+                        Rc::<Noun>::from(Cell::from([atom(0u8), data.clone()])),
+                    ),
+                    PeekReply::Bail(goof) => pair("bail", goof.clone()),
+                };
+                Self::from(Cell::from([atom("peek"), reply]))
+            }
+            Plea::Play(reply) => {
+                let reply = match reply {
+                    PlayReply::Done { mug } => pair("done", atom(*mug)),
+                    PlayReply::Bail { event, mug, goof } => Rc::<Noun>::from(Cell::from([
+                        atom("bail"),
+                        atom(*event),
+                        atom(*mug),
+                        goof.clone(),
+                    ])),
+                };
+                Self::from(Cell::from([atom("play"), reply]))
+            }
+            Plea::Work(reply) => {
+                let reply = match reply {
+                    WorkReply::Done {
+                        event,
+                        mug,
+                        effects,
+                    } => Rc::<Noun>::from(Cell::from([
+                        atom("done"),
+                        atom(*event),
+                        atom(*mug),
+                        to_list(effects),
+                    ])),
+                    WorkReply::Swap {
+                        event,
+                        mug,
+                        job,
+                        effects,
+                    } => Rc::<Noun>::from(Cell::from([
+                        atom("swap"),
+                        atom(*event),
+                        atom(*mug),
+                        job.clone(),
+                        to_list(effects),
+                    ])),
+                    WorkReply::Bail(goofs) => pair("bail", to_list(goofs)),
+                };
+                Self::from(Cell::from([atom("work"), reply]))
+            }
+        }
+    }
+}
+
+/// Returns the cell `noun`.
+fn cell(noun: &Noun) -> Result<&Cell, convert::Error> {
+    match noun {
+        Noun::Atom(_) => Err(convert::Error::UnexpectedAtom),
+        Noun::Cell(cell) => Ok(cell),
+    }
+}
+
+/// Splits `noun`, which must be of the form `[tag data]` where `tag` is a cord, into its tag and
+/// data.
+fn tagged(noun: &Noun) -> Result<(&str, &Noun), convert::Error> {
+    let noun = cell(noun)?;
+    Ok((<&str>::try_from(noun.head_ref())?, noun.tail_ref()))
+}
+
+/// Unpacks `noun`, which must be of the form `[a1 a2 ... aN]`, into an array.
+fn tuple<const N: usize>(noun: &Noun) -> Result<[Rc<Noun>; N], convert::Error> {
+    cell(noun)?
+        .to_array::<N>()
+        .ok_or(convert::Error::UnexpectedAtom)
+}
+
+/// Returns the `n`th tail of `noun`, which is shared with `noun`.
+fn tail(noun: &Noun, n: usize) -> Result<Rc<Noun>, convert::Error> {
+    let mut tail = cell(noun)?.tail();
+    for _ in 1..n {
+        tail = cell(&tail)?.tail();
+    }
+    Ok(tail)
+}
+
+/// Converts `noun`, which must be an atom, into an unsigned integer.
+fn uint<T: TryFrom<u64>>(noun: &Noun) -> Result<T, convert::Error> {
+    match noun {
+        Noun::Atom(atom) => atom
+            .as_u64()
+            .and_then(|uint| T::try_from(uint).ok())
+            .ok_or(convert::Error::AtomToUint),
+        Noun::Cell(_) => Err(convert::Error::UnexpectedCell),
+    }
+}
+
+/// Checks that `noun` is `~`.
+fn null(noun: &Noun) -> Result<(), convert::Error> {
+    match noun {
+        Noun::Atom(atom) if atom.is_null() => Ok(()),
+        Noun::Atom(_) => Err(convert::Error::ExpectedNull),
+        Noun::Cell(_) => Err(convert::Error::UnexpectedCell),
+    }
+}
+
+/// Converts `noun`, which must be a `unit`, into an [`Option`].
+fn unit(noun: &Noun) -> Result<Option<Rc<Noun>>, convert::Error> {
+    match noun {
+        Noun::Atom(_) => null(noun).map(|()| None),
+        Noun::Cell(cell) => null(cell.head_ref()).map(|()| Some(cell.tail())),
+    }
+}
+
+/// Converts `noun`, which must be a null-terminated list, into a vector.
+fn list(noun: &Noun) -> Result<Vec<Rc<Noun>>, convert::Error> {
+    let mut elems = Vec::new();
+    let mut noun = noun;
+    while let Noun::Cell(cell) = noun {
+        elems.push(cell.head());
+        noun = cell.tail_ref();
+    }
+    null(noun).map(|()| elems)
+}
+
+/// Converts `value` into an atom.
+fn atom(value: impl Into<Atom>) -> Rc<Noun> {
+    Rc::<Noun>::from(value.into())
+}
+
+/// Creates the cell `[tag data]`.
+fn pair(tag: &str, data: Rc<Noun>) -> Rc<Noun> {
+    Rc::<Noun>::from(Cell::from([atom(tag), data]))
+}
+
+/// Converts `elems` into a null-terminated list.
+fn to_list(elems: &[Rc<Noun>]) -> Rc<Noun> {
+    let mut list = atom(0u8);
+    for elem in elems.iter().rev() {
+        list = Rc::<Noun>::from(Cell::from([elem.clone(), list]));
+    }
+    list
+}
+
+/// A stand-in for a serf process that answers writs with canned pleas, for testing a king
+/// without the Urbit runtime.
+///
+/// The mock serf runs on its own thread and talks to the king over a pair of pipes, like a serf
+/// process does over its standard input and output. It sends its greeting as soon as it starts,
+/// then answers the first writ it reads with the first list of replies, the second writ with the
+/// second list, and so on. It stops once it has answered every writ it has replies for, when it
+/// reads a [`Live::Exit`], or when the king closes its end of the pipe.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::serf::{Live, MockSerf, Plea, PlayReply, Writ};
+/// let ripe = Plea::Ripe { protocol: 1, hoon: 139, nock: 4, event: 0, mug: 0 };
+/// let done = Plea::Play(PlayReply::Done { mug: 0x1234 });
+/// let replies = [vec![done.clone()], vec![]];
+/// let (serf, mut reader, mut writer) = MockSerf::spawn(&[ripe.clone()], &replies);
+///
+/// let plea = |noun| Plea::try_from(&noun).unwrap();
+/// assert_eq!(plea(reader.read().unwrap().unwrap()), ripe);
+/// let play = Writ::Play { event: 1, events: vec![] };
+/// writer.write(&play.clone().into()).unwrap();
+/// assert_eq!(plea(reader.read().unwrap().unwrap()), done);
+/// writer.write(&Writ::Live(Live::Exit { code: 0 }).into()).unwrap();
+/// assert!(reader.read().unwrap().is_none());
+///
+/// let writs = serf.join().unwrap();
+/// assert_eq!(writs.len(), 2);
+/// assert_eq!(Writ::try_from(&writs[0]).unwrap(), play);
+/// ```
+pub struct MockSerf {
+    /// The serf's thread, which returns the jammed writs it read.
+    thread: JoinHandle<newt::Result<Vec<Vec<u8>>>>,
+}
+
+impl MockSerf {
+    /// Starts a mock serf that sends `greeting` and then answers the `n`th writ it reads with
+    /// `replies[n]`, returning the serf along with the king's ends of the pipes to it.
+    pub fn spawn(
+        greeting: &[Plea],
+        replies: &[Vec<Plea>],
+    ) -> (Self, NewtReader<PipeReader>, NewtWriter<PipeWriter>) {
+        // Nouns can't be sent to another thread unless the `thread-safe` feature is enabled, so
+        // the pleas cross over as frames.
+        let frames = |pleas: &[Plea]| {
+            let mut writer = NewtWriter::new(Vec::new());
+            for plea in pleas {
+                writer.write(&plea.clone().into()).expect("write to vector");
+            }
+            writer.into_inner()
+        };
+        let greeting = frames(greeting);
+        let replies: Vec<_> = replies.iter().map(|pleas| frames(pleas)).collect();
+
+        let (king_reader, serf_writer) = io::pipe().expect("pipe");
+        let (serf_reader, king_writer) = io::pipe().expect("pipe");
+        let thread = thread::spawn(move || {
+            let mut reader = NewtReader::new(serf_reader);
+            let mut writer = serf_writer;
+            io::Write::write_all(&mut writer, &greeting)?;
+            let mut writs = Vec::new();
+            for reply in replies {
+                let Some(writ) = reader.read()? else {
+                    break;
+                };
+                writs.push(serdes::jam_to_bytes(&writ));
+                if let Ok(Writ::Live(Live::Exit { .. })) = Writ::try_from(&writ) {
+                    break;
+                }
+                io::Write::write_all(&mut writer, &reply)?;
+            }
+            Ok(writs)
+        });
+
+        (
+            Self { thread },
+            NewtReader::new(king_reader),
+            NewtWriter::new(king_writer),
+        )
+    }
+
+    /// Waits for the serf to stop, returning the writs it read.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the serf's thread panicked.
+    pub fn join(self) -> newt::Result<Vec<Noun>> {
+        let writs = self.thread.join().expect("mock serf panicked")?;
+        writs
+            .iter()
+            .map(|writ| {
+                serdes::cue_from_reader_with_limits(&writ[..], &CueLimits::unlimited())
+                    .map_err(newt::Error::Cue)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noun(value: &str) -> Rc<Noun> {
+        atom(value)
+    }
+
+    #[test]
+    fn writ() {
+        let writs = [
+            Writ::Live(Live::Cram { event: 7 }),
+            Writ::Live(Live::Exit { code: 2 }),
+            Writ::Live(Live::Save { event: u64::MAX }),
+            Writ::Live(Live::Meld),
+            Writ::Live(Live::Pack),
+            Writ::Peek {
+                timeout: 0,
+                request: Rc::<Noun>::from(Cell::from(["gang", "path"])),
+            },
+            Writ::Play {
+                event: 1,
+                events: vec![noun("a"), noun("b")],
+            },
+            Writ::Play {
+                event: 1,
+                events: vec![],
+            },
+            Writ::Work {
+                timeout: 1000,
+                job: noun("job"),
+            },
+        ];
+        for writ in writs {
+            assert_eq!(
+                Writ::try_from(&Noun::from(writ.clone())).expect("writ"),
+                writ
+            );
+        }
+
+        // [%live %exit 2]
+        assert_eq!(
+            Noun::from(Writ::Live(Live::Exit { code: 2 })),
+            Noun::from(Cell::from([atom("live"), atom("exit"), atom(2u8)]))
+        );
+        // [%play 1 [%a %b ~]]
+        assert_eq!(
+            Noun::from(Writ::Play {
+                event: 1,
+                events: vec![noun("a"), noun("b")],
+            }),
+            Noun::from(Cell::from([
+                atom("play"),
+                atom(1u8),
+                atom("a"),
+                atom("b"),
+                atom(0u8),
+            ]))
+        );
+    }
+
+    #[test]
+    fn plea() {
+        let pleas = [
+            Plea::Live,
+            Plea::Ripe {
+                protocol: 1,
+                hoon: 139,
+                nock: 4,
+                event: 12,
+                mug: 0x7fff_ffff,
+            },
+            Plea::Slog {
+                priority: 3,
+                tank: noun("tank"),
+            },
+            Plea::Flog(Atom::from("log")),
+            Plea::Peek(PeekReply::Done(None)),
+            Plea::Peek(PeekReply::Done(Some(noun("data")))),
+            Plea::Peek(PeekReply::Bail(noun("goof"))),
+            Plea::Play(PlayReply::Done { mug: 1 }),
+            Plea::Play(PlayReply::Bail {
+                event: 3,
+                mug: 1,
+                goof: noun("goof"),
+            }),
+            Plea::Work(WorkReply::Done {
+                event: 4,
+                mug: 2,
+                effects: vec![noun("effect")],
+            }),
+            Plea::Work(WorkReply::Swap {
+                event: 4,
+                mug: 2,
+                job: noun("job"),
+                effects: vec![],
+            }),
+            Plea::Work(WorkReply::Bail(vec![noun("goof"), noun("goof")])),
+        ];
+        for plea in pleas {
+            assert_eq!(
+                Plea::try_from(&Noun::from(plea.clone())).expect("plea"),
+                plea
+            );
+        }
+
+        // [%ripe [1 139 4] 12 5]
+        assert_eq!(
+            Noun::from(Plea::Ripe {
+                protocol: 1,
+                hoon: 139,
+                nock: 4,
+                event: 12,
+                mug: 5,
+            }),
+            Noun::from(Cell::from([
+                atom("ripe"),
+                Rc::<Noun>::from(Cell::from([1u8, 139u8, 4u8])),
+                atom(12u8),
+                atom(5u8),
+            ]))
+        );
+        // [%peek %done ~ %data]
+        assert_eq!(
+            Noun::from(Plea::Peek(PeekReply::Done(Some(noun("data"))))),
+            Noun::from(Cell::from([
+                atom("peek"),
+                atom("done"),
+                atom(0u8),
+                atom("data"),
+            ]))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let writ = |nouns: Vec<Rc<Noun>>| Writ::try_from(&Noun::from(Cell::from(nouns)));
+        let plea = |nouns: Vec<Rc<Noun>>| Plea::try_from(&Noun::from(Cell::from(nouns)));
+
+        assert!(matches!(
+            Writ::try_from(&Noun::null()),
+            Err(convert::Error::UnexpectedAtom)
+        ));
+        assert!(matches!(
+            writ(vec![atom("fly"), atom(0u8)]),
+            Err(convert::Error::InvalidTag)
+        ));
+        assert!(matches!(
+            writ(vec![atom("live"), atom("exit"), atom(256u16)]),
+            Err(convert::Error::AtomToUint)
+        ));
+        assert!(matches!(
+            writ(vec![atom("live"), atom("meld"), atom(1u8)]),
+            Err(convert::Error::ExpectedNull)
+        ));
+        assert!(matches!(
+            writ(vec![atom("play"), atom(1u8), atom("a"), atom(1u8)]),
+            Err(convert::Error::ExpectedNull)
+        ));
+        assert!(matches!(
+            plea(vec![atom("ripe"), atom(1u8), atom(2u8)]),
+            Err(convert::Error::UnexpectedAtom)
+        ));
+        assert!(matches!(
+            plea(vec![atom("work"), atom("lost"), atom(0u8)]),
+            Err(convert::Error::InvalidTag)
+        ));
+        assert!(matches!(
+            plea(vec![atom("flog"), atom("a"), atom("b")]),
+            Err(convert::Error::UnexpectedCell)
+        ));
+    }
+
+    #[test]
+    fn mock_serf() {
+        let ripe = Plea::Ripe {
+            protocol: 1,
+            hoon: 139,
+            nock: 4,
+            event: 0,
+            mug: 0,
+        };
+        let replies = vec![
+            vec![Plea::Play(PlayReply::Done { mug: 9 })],
+            vec![
+                Plea::Slog {
+                    priority: 0,
+                    tank: noun("working"),
+                },
+                Plea::Work(WorkReply::Done {
+                    event: 2,
+                    mug: 10,
+                    effects: vec![noun("effect")],
+                }),
+            ],
+            vec![Plea::Live],
+        ];
+        let (serf, mut reader, mut writer) = MockSerf::spawn(std::slice::from_ref(&ripe), &replies);
+        let mut read = || Plea::try_from(&reader.read().expect("read").expect("plea")).unwrap();
+
+        assert_eq!(read(), ripe);
+        let writs = vec![
+            Writ::Play {
+                event: 1,
+                events: vec![noun("boot")],
+            },
+            Writ::Work {
+                timeout: 0,
+                job: noun("job"),
+            },
+            Writ::Live(Live::Save { event: 2 }),
+        ];
+        for (writ, reply) in writs.iter().zip(&replies) {
+            writer.write(&writ.clone().into()).expect("write");
+            for plea in reply {
+                assert_eq!(&read(), plea);
+            }
+        }
+        // The serf stops once it runs out of replies.
+        assert!(reader.read().expect("read").is_none());
+        drop(writer);
+
+        let received: Vec<_> = serf
+            .join()
+            .expect("join")
+            .iter()
+            .map(|writ| Writ::try_from(writ).expect("writ"))
+            .collect();
+        assert_eq!(received, writs);
+    }
+
+    #[test]
+    fn mock_serf_closed() {
+        let (serf, mut reader, writer) = MockSerf::spawn(&[], &[vec![Plea::Live]]);
+        drop(writer);
+        assert!(reader.read().expect("read").is_none());
+        assert!(serf.join().expect("join").is_empty());
+    }
+}
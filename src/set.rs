@@ -0,0 +1,276 @@
+//! Hoon sets: treaps of elements.
+
+use crate::{
+    convert::{self, FromNoun, ToNoun},
+    noun::Noun,
+    treap::{self, Order},
+    Rc,
+};
+use std::{
+    fmt::{self, Debug, Formatter},
+    iter::FusedIterator,
+};
+
+/// A set of nouns with exactly the structure of a Hoon `set`.
+///
+/// A set is a treap of elements ordered by `gor` and balanced by `mor`, so its noun is identical to
+/// the one that Hoon's `++in` would build from the same elements, no matter the order in which they
+/// were added. Like [`HoonMap`](crate::map::HoonMap)s, sets are persistent.
+///
+/// # Examples
+///
+/// ```
+/// # use noun::{atom::Atom, set::HoonSet, Noun};
+/// let mut set = HoonSet::new();
+/// set.put(Atom::from("zod"));
+/// assert!(set.has(&Noun::from(Atom::from("zod"))));
+///
+/// let other: HoonSet = [Atom::from("zod"), Atom::from("nec")].into_iter().collect();
+/// assert_eq!(other.diff(&set), HoonSet::from_iter([Atom::from("nec")]));
+/// ```
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct HoonSet {
+    tree: Rc<Noun>,
+}
+
+impl HoonSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self {
+            tree: Rc::new(Noun::null()),
+        }
+    }
+
+    /// Returns whether this set has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_null()
+    }
+
+    /// Returns whether this set has `elem`.
+    pub fn has(&self, elem: &Noun) -> bool {
+        treap::get(&self.tree, elem, Order::SET).is_some()
+    }
+
+    /// Adds `elem` to this set.
+    pub fn put(&mut self, elem: impl Into<Rc<Noun>>) {
+        self.tree = treap::put(&self.tree, elem.into(), Order::SET);
+    }
+
+    /// Removes `elem` from this set if this set has `elem`.
+    pub fn del(&mut self, elem: &Noun) {
+        self.tree = treap::del(&self.tree, elem, Order::SET);
+    }
+
+    /// Returns an iterator over the elements of this set in the order Hoon's `tap` lists them.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            nodes: treap::Nodes::new(&self.tree),
+        }
+    }
+
+    /// Returns the set of the elements of this set and `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            tree: treap::uni(&self.tree, &other.tree, Order::SET),
+        }
+    }
+
+    /// Returns the set of the elements of this set that `other` doesn't have.
+    pub fn diff(&self, other: &Self) -> Self {
+        Self {
+            tree: treap::dif(&self.tree, &other.tree, Order::SET),
+        }
+    }
+
+    /// Returns the noun of this set.
+    pub fn as_noun(&self) -> &Rc<Noun> {
+        &self.tree
+    }
+}
+
+impl Debug for HoonSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl Default for HoonSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Into<Rc<Noun>>> Extend<T> for HoonSet {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.put(elem);
+        }
+    }
+}
+
+impl From<HoonSet> for Noun {
+    fn from(set: HoonSet) -> Self {
+        Rc::try_unwrap(set.tree).unwrap_or_else(|tree| (*tree).clone())
+    }
+}
+
+impl From<HoonSet> for Rc<Noun> {
+    fn from(set: HoonSet) -> Self {
+        set.tree
+    }
+}
+
+impl<T: Into<Rc<Noun>>> FromIterator<T> for HoonSet {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a> IntoIterator for &'a HoonSet {
+    type Item = &'a Noun;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl TryFrom<Rc<Noun>> for HoonSet {
+    type Error = convert::Error;
+
+    /// Converts a noun into a set, failing with [`convert::Error::InvalidTreap`] if the noun isn't
+    /// a well-formed treap.
+    fn try_from(tree: Rc<Noun>) -> Result<Self, Self::Error> {
+        if treap::apt(&tree, Order::SET) {
+            Ok(Self { tree })
+        } else {
+            Err(convert::Error::InvalidTreap)
+        }
+    }
+}
+
+impl TryFrom<&Noun> for HoonSet {
+    type Error = convert::Error;
+
+    fn try_from(noun: &Noun) -> Result<Self, Self::Error> {
+        Self::try_from(Rc::new(noun.clone()))
+    }
+}
+
+impl ToNoun for HoonSet {
+    fn to_noun(&self) -> Noun {
+        (*self.tree).clone()
+    }
+}
+
+impl FromNoun for HoonSet {
+    fn from_noun(noun: &Noun) -> Result<Self, convert::Error> {
+        Self::try_from(noun)
+    }
+}
+
+/// An iterator over the elements of a [`HoonSet`], created by [`HoonSet::iter`].
+#[derive(Clone)]
+pub struct Iter<'a> {
+    nodes: treap::Nodes<'a>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a Noun;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes.next()
+    }
+}
+
+impl FusedIterator for Iter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{atom::Atom, noun};
+
+    /// Returns a set of the atoms in `elems`.
+    fn atoms(elems: impl IntoIterator<Item = u32>) -> HoonSet {
+        elems.into_iter().map(Atom::from).collect()
+    }
+
+    #[test]
+    fn put_has_del() {
+        let mut set = HoonSet::new();
+        assert!(set.is_empty());
+        set.put(Atom::from(1u8));
+        assert_eq!(Noun::from(set.clone()), noun!([1 ~ ~]));
+
+        let mut set = atoms(0..100);
+        assert_eq!(set, atoms((0..100).rev()));
+        assert!(HoonSet::try_from(set.as_noun().clone()).is_ok());
+        for n in 0..100u32 {
+            assert!(set.has(&Noun::from(Atom::from(n))));
+        }
+        assert!(!set.has(&Noun::from(Atom::from(100u8))));
+        let mut elems: Vec<_> = set
+            .iter()
+            .map(|elem| match elem {
+                Noun::Atom(elem) => elem.as_u32().expect("u32"),
+                Noun::Cell(_) => panic!("expected an atom"),
+            })
+            .collect();
+        elems.sort_unstable();
+        assert_eq!(elems, (0..100).collect::<Vec<_>>());
+
+        for n in (0..100u32).filter(|n| n % 3 != 0) {
+            set.del(&Noun::from(Atom::from(n)));
+        }
+        assert_eq!(set, atoms((0..100).step_by(3)));
+        assert!(HoonSet::try_from(set.as_noun().clone()).is_ok());
+    }
+
+    #[test]
+    fn union_diff() {
+        let fives = atoms((0..80).step_by(5));
+        let sevens = atoms((0..80).step_by(7));
+        let union = fives.union(&sevens);
+        assert_eq!(union, atoms((0..80).filter(|n| n % 5 == 0 || n % 7 == 0)));
+        assert_eq!(union, sevens.union(&fives));
+        assert!(HoonSet::try_from(union.as_noun().clone()).is_ok());
+
+        let diff = union.diff(&fives);
+        assert_eq!(diff, atoms((0..80).filter(|n| n % 5 != 0 && n % 7 == 0)));
+        assert!(HoonSet::try_from(diff.as_noun().clone()).is_ok());
+        assert_eq!(fives.diff(&atoms(0..80)), HoonSet::new());
+    }
+
+    #[test]
+    fn hoon() {
+        // (jam (silt (gulf 1 10)))
+        let noun = treap::tests::cue("0x5.0b8f.430b.8f19.920b.2166.1aa6.e17e.1982.0b44.1761");
+        assert_eq!(
+            noun,
+            noun!([6 [10 [8 ~ 7 [5 ~ ~] ~] ~] 4 [2 [9 ~ 1 ~ ~] 3 ~ ~] ~])
+        );
+        assert_eq!(Noun::from(atoms(1..11)), noun);
+        assert_eq!(Noun::from(atoms((1..11).rev())), noun);
+
+        // (jam (silt `(list @tas)`~[%foo %bar %baz %qux %quux %corge]))
+        let noun = treap::tests::cue(
+            "0xb0.10f7.b7b3.3c06.7d30.b13c.06a9.f0ea.eae3.f017.c3ab.8bc0.5e4c.2c4f.0172.b3b9.37b1.9e01",
+        );
+        assert_eq!(
+            noun,
+            noun!([%corge [%bar [%qux [%quux ~ ~] ~] ~] %baz ~ %foo ~ ~])
+        );
+        let terms = ["foo", "bar", "baz", "qux", "quux", "corge"];
+        let set: HoonSet = terms.into_iter().map(Atom::from).collect();
+        assert_eq!(Noun::from(set.clone()), noun);
+        assert_eq!(HoonSet::try_from(&noun).expect("set"), set);
+    }
+
+    #[test]
+    fn try_from() {
+        assert!(HoonSet::try_from(&noun!([[1 2] ~ ~])).is_ok());
+        treap::tests::check_try_from(atoms(0..10), [noun!(1), noun!([1 ~]), noun!([1 [1 ~ ~] ~])]);
+    }
+}
@@ -0,0 +1,689 @@
+//! Urbit's absolute dates (`@da`) and relative durations (`@dr`).
+//!
+//! Both auras count time in units of 2^-64 seconds: the high 64 bits of the atom are whole
+//! seconds and the low 64 bits are the fraction of a second. A `@dr` is a span of time, and a `@da`
+//! is the span of time since the Urbit epoch, the start of the year 292,277,024,401 BC in the
+//! proleptic Gregorian calendar. The Unix epoch is `~1970.1.1`.
+//!
+//! A `@da` is written as a date, then the time of day if it isn't midnight, then the fraction of a
+//! second if it isn't zero, as groups of four hexadecimal digits (`~2022.7.8..16.43.50..1a2b`).
+//! Years before 1 AD end with `-` (`~44-.3.15`). A `@dr` is written as the days, hours, minutes and
+//! seconds it contains, skipping those that are zero, then the fraction of a second
+//! (`~d1.h2.m30..8000`).
+//!
+//! See [`Atom::to_da`], [`Atom::from_da`], [`Atom::to_dr`], [`Atom::from_dr`],
+//! [`Atom::to_system_time`], [`Atom::from_system_time`], [`Atom::to_duration`] and
+//! [`Atom::from_duration`].
+//!
+//! [`Atom::to_da`]: crate::atom::Atom::to_da
+//! [`Atom::from_da`]: crate::atom::Atom::from_da
+//! [`Atom::to_dr`]: crate::atom::Atom::to_dr
+//! [`Atom::from_dr`]: crate::atom::Atom::from_dr
+//! [`Atom::to_system_time`]: crate::atom::Atom::to_system_time
+//! [`Atom::from_system_time`]: crate::atom::Atom::from_system_time
+//! [`Atom::to_duration`]: crate::atom::Atom::to_duration
+//! [`Atom::from_duration`]: crate::atom::Atom::from_duration
+
+use std::{
+    fmt::{self, Display, Formatter},
+    result,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Seconds from the Urbit epoch to the Unix epoch (`~1970.1.1`).
+const UNIX_EPOCH_SECS: u64 = 0x8000_000c_ce9e_0d80;
+
+/// Years from the Urbit epoch to 1 AD.
+const AD_YEAR: u64 = 292_277_024_400;
+
+/// Days in a 400-year era.
+const ERA_DAYS: u64 = 146_097;
+
+/// Days in a century that doesn't begin an era.
+const CENTURY_DAYS: u64 = 36_524;
+
+/// Seconds in a day.
+const DAY_SECS: u64 = 86_400;
+
+/// Seconds in an hour.
+const HOUR_SECS: u64 = 3_600;
+
+/// Seconds in a minute.
+const MINUTE_SECS: u64 = 60;
+
+/// Days in each month of a common year.
+const MONTH_DAYS: [u64; 12] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+/// Days in each month of a leap year.
+const LEAP_MONTH_DAYS: [u64; 12] = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+
+/// Errors that occur when parsing `@da` or `@dr` strings.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The string didn't begin with `~`.
+    MissingSig,
+    /// The string didn't have the expected syntax at byte offset `pos`.
+    Syntax { pos: usize },
+    /// The field at byte offset `pos` of the string was out of range, such as month `13`, or made
+    /// the time too large to fit in 128 bits.
+    OutOfRange { pos: usize },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> result::Result<(), fmt::Error> {
+        match self {
+            Self::MissingSig => write!(f, "the time does not begin with `~`"),
+            Self::Syntax { pos } => write!(f, "unexpected character at byte {}", pos),
+            Self::OutOfRange { pos } => write!(f, "the field at byte {} is out of range", pos),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A specialized [`Result`] type for time operations that return [`time::Error`] on error.
+///
+/// [`time::Error`]: [`Error`]
+pub type Result<T> = result::Result<T, Error>;
+
+/// A span of time broken into days, hours, minutes, seconds, and the fraction of a second as up
+/// to four 16-bit groups, most significant first, with trailing zero groups omitted (`tarp`).
+#[derive(Debug, Default, Eq, PartialEq)]
+struct Tarp {
+    day: u64,
+    hour: u64,
+    minute: u64,
+    second: u64,
+    fraction: Vec<u16>,
+}
+
+/// A calendar date (`date`).
+#[derive(Debug, Eq, PartialEq)]
+struct Date {
+    /// Whether the year is AD rather than BC.
+    ad: bool,
+    year: u64,
+    month: u64,
+    /// The day of the month in `tarp.day`.
+    tarp: Tarp,
+}
+
+/// Breaks a span of time into a [`Tarp`] (`++yell`).
+fn yell(time: u128) -> Tarp {
+    let mut sec = (time >> 64) as u64;
+    let mut raw = time as u64;
+    let mut fraction = Vec::new();
+    let mut muc = 4;
+    while raw != 0 && muc != 0 {
+        muc -= 1;
+        fraction.push((raw >> (16 * muc)) as u16);
+        raw &= (1u64 << (16 * muc)).wrapping_sub(1);
+    }
+    let day = sec / DAY_SECS;
+    sec %= DAY_SECS;
+    let hour = sec / HOUR_SECS;
+    sec %= HOUR_SECS;
+    Tarp {
+        day,
+        hour,
+        minute: sec / MINUTE_SECS,
+        second: sec % MINUTE_SECS,
+        fraction,
+    }
+}
+
+/// Joins a [`Tarp`] into a span of time, returning `None` if it doesn't fit in 128 bits
+/// (`++yule`).
+fn yule(tarp: &Tarp) -> Option<u128> {
+    let sec = u128::from(tarp.day) * u128::from(DAY_SECS)
+        + u128::from(tarp.hour) * u128::from(HOUR_SECS)
+        + u128::from(tarp.minute) * u128::from(MINUTE_SECS)
+        + u128::from(tarp.second);
+    let sec = u64::try_from(sec).ok()?;
+    let fraction = tarp
+        .fraction
+        .iter()
+        .zip((0..4).rev())
+        .fold(0, |fraction, (group, muc)| {
+            fraction | u64::from(*group) << (16 * muc)
+        });
+    Some(u128::from(sec) << 64 | u128::from(fraction))
+}
+
+/// Returns whether `year`, counted from the Urbit epoch, is a leap year (`++yelp`).
+fn yelp(year: u64) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+/// Converts days since the Urbit epoch into a year since the Urbit epoch, a month, and a day of
+/// the month (`++yall`).
+fn yall(mut day: u64) -> (u64, u64, u64) {
+    let era = day / ERA_DAYS;
+    day %= ERA_DAYS;
+    // The first century of an era has a leap day in its first year, and the others don't.
+    let (cet, mut lep) = if day < CENTURY_DAYS + 1 {
+        (0, true)
+    } else {
+        day -= CENTURY_DAYS + 1;
+        let cet = 1 + day / CENTURY_DAYS;
+        day %= CENTURY_DAYS;
+        (cet, false)
+    };
+
+    let mut year = 400 * era + 100 * cet;
+    loop {
+        let dis = if lep { 366 } else { 365 };
+        if day < dis {
+            break;
+        }
+        year += 1;
+        day -= dis;
+        lep = year.is_multiple_of(4);
+    }
+
+    let months = if lep { LEAP_MONTH_DAYS } else { MONTH_DAYS };
+    let mut month = 0;
+    while day >= months[month] {
+        day -= months[month];
+        month += 1;
+    }
+    (year, month as u64 + 1, day + 1)
+}
+
+/// Converts a year since the Urbit epoch, a month, and a day of the month into days since the
+/// Urbit epoch (`++yawn`).
+fn yawn(year: u64, month: u64, day: u64) -> u128 {
+    let months = if yelp(year) {
+        LEAP_MONTH_DAYS
+    } else {
+        MONTH_DAYS
+    };
+    let year = u128::from(year);
+    // Leap years before `year`, counting the leap year that begins the epoch.
+    let leaps = year.div_ceil(4) - year.div_ceil(100) + year.div_ceil(400);
+    let before_month: u64 = months[..(month - 1) as usize].iter().sum();
+    365 * year + leaps + u128::from(before_month + day - 1)
+}
+
+/// Converts an absolute date into a calendar date (`++yore`).
+fn yore(time: u128) -> Date {
+    let mut tarp = yell(time);
+    let (year, month, day) = yall(tarp.day);
+    tarp.day = day;
+    let (ad, year) = if year > AD_YEAR {
+        (true, year - AD_YEAR)
+    } else {
+        (false, AD_YEAR + 1 - year)
+    };
+    Date {
+        ad,
+        year,
+        month,
+        tarp,
+    }
+}
+
+/// Writes the fraction of a second of `tarp`, if it isn't zero.
+fn push_fraction(text: &mut String, tarp: &Tarp) {
+    if !tarp.fraction.is_empty() {
+        text.push('.');
+        for group in &tarp.fraction {
+            text.push_str(&format!(".{:04x}", group));
+        }
+    }
+}
+
+/// Spells an absolute date as `@da`.
+pub(crate) fn da(time: u128) -> String {
+    let date = yore(time);
+    let tarp = &date.tarp;
+    let mut text = format!(
+        "~{}{}.{}.{}",
+        date.year,
+        if date.ad { "" } else { "-" },
+        date.month,
+        tarp.day
+    );
+    if tarp.hour != 0 || tarp.minute != 0 || tarp.second != 0 || !tarp.fraction.is_empty() {
+        text.push_str(&format!(
+            "..{:02}.{:02}.{:02}",
+            tarp.hour, tarp.minute, tarp.second
+        ));
+    }
+    push_fraction(&mut text, tarp);
+    text
+}
+
+/// Spells a relative duration as `@dr`.
+pub(crate) fn dr(time: u128) -> String {
+    let tarp = yell(time);
+    let mut text = String::from("~");
+    let units = [
+        ('d', tarp.day),
+        ('h', tarp.hour),
+        ('m', tarp.minute),
+        ('s', tarp.second),
+    ];
+    for (unit, value) in units.iter().filter(|(_, value)| *value != 0) {
+        if text.len() > 1 {
+            text.push('.');
+        }
+        text.push_str(&format!("{}{}", unit, value));
+    }
+    if text.len() == 1 {
+        text.push_str("s0");
+    }
+    push_fraction(&mut text, &tarp);
+    text
+}
+
+/// A position in a string being parsed.
+struct Cursor<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// Parses `text`, which must begin with `~`.
+    fn new(text: &'a str) -> Result<Self> {
+        if text.starts_with('~') {
+            Ok(Self { text, pos: 1 })
+        } else {
+            Err(Error::MissingSig)
+        }
+    }
+
+    /// Returns whether the rest of the string begins with `prefix`, consuming it if so.
+    fn eat(&mut self, prefix: &str) -> bool {
+        let matches = self.text[self.pos..].starts_with(prefix);
+        if matches {
+            self.pos += prefix.len();
+        }
+        matches
+    }
+
+    /// Consumes `prefix`, which must begin the rest of the string.
+    fn expect(&mut self, prefix: &str) -> Result<()> {
+        if self.eat(prefix) {
+            Ok(())
+        } else {
+            Err(Error::Syntax { pos: self.pos })
+        }
+    }
+
+    /// Consumes a number of between `min_len` and `max_len` digits in `radix`, returning it along
+    /// with its position.
+    fn number(&mut self, radix: u32, min_len: usize, max_len: usize) -> Result<(u64, usize)> {
+        let start = self.pos;
+        let len = self.text[start..]
+            .bytes()
+            .take(max_len)
+            .take_while(|byte| byte.is_ascii_digit() || radix == 16 && (b'a'..=b'f').contains(byte))
+            .count();
+        if len < min_len {
+            return Err(Error::Syntax { pos: start + len });
+        }
+        self.pos += len;
+        let number = u64::from_str_radix(&self.text[start..self.pos], radix)
+            .map_err(|_| Error::OutOfRange { pos: start })?;
+        Ok((number, start))
+    }
+
+    /// Consumes the fraction of a second, if any, into `tarp`.
+    fn fraction(&mut self, tarp: &mut Tarp) -> Result<()> {
+        if self.eat("..") {
+            loop {
+                let (group, _) = self.number(16, 4, 4)?;
+                tarp.fraction.push(group as u16);
+                if tarp.fraction.len() == 4 || !self.eat(".") {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the whole string has been consumed.
+    fn end(&self) -> Result<()> {
+        if self.pos == self.text.len() {
+            Ok(())
+        } else {
+            Err(Error::Syntax { pos: self.pos })
+        }
+    }
+}
+
+/// Returns `value` if it's in `range`, or an error at `pos` otherwise.
+fn in_range(value: u64, range: std::ops::RangeInclusive<u64>, pos: usize) -> Result<u64> {
+    if range.contains(&value) {
+        Ok(value)
+    } else {
+        Err(Error::OutOfRange { pos })
+    }
+}
+
+/// Parses `text` as `@da` (`++year`).
+pub(crate) fn parse_da(text: &str) -> Result<u128> {
+    let mut cursor = Cursor::new(text)?;
+    let (year, year_pos) = cursor.number(10, 1, 20)?;
+    let ad = !cursor.eat("-");
+    // There's no year 0 in either era.
+    let year = match year {
+        0 => None,
+        _ if ad => AD_YEAR.checked_add(year),
+        _ => (AD_YEAR + 1).checked_sub(year),
+    }
+    .ok_or(Error::OutOfRange { pos: year_pos })?;
+
+    cursor.expect(".")?;
+    let (month, pos) = cursor.number(10, 1, 2)?;
+    let month = in_range(month, 1..=12, pos)?;
+    cursor.expect(".")?;
+    let (day, pos) = cursor.number(10, 1, 2)?;
+    let months = if yelp(year) {
+        LEAP_MONTH_DAYS
+    } else {
+        MONTH_DAYS
+    };
+    let day = in_range(day, 1..=months[month as usize - 1], pos)?;
+
+    let mut tarp = Tarp::default();
+    if cursor.eat("..") {
+        let (hour, pos) = cursor.number(10, 2, 2)?;
+        tarp.hour = in_range(hour, 0..=23, pos)?;
+        cursor.expect(".")?;
+        let (minute, pos) = cursor.number(10, 2, 2)?;
+        tarp.minute = in_range(minute, 0..=59, pos)?;
+        cursor.expect(".")?;
+        let (second, pos) = cursor.number(10, 2, 2)?;
+        tarp.second = in_range(second, 0..=59, pos)?;
+        cursor.fraction(&mut tarp)?;
+    }
+    cursor.end()?;
+
+    tarp.day =
+        u64::try_from(yawn(year, month, day)).map_err(|_| Error::OutOfRange { pos: year_pos })?;
+    yule(&tarp).ok_or(Error::OutOfRange { pos: year_pos })
+}
+
+/// Parses `text` as `@dr`.
+pub(crate) fn parse_dr(text: &str) -> Result<u128> {
+    let mut cursor = Cursor::new(text)?;
+    let mut tarp = Tarp::default();
+    {
+        // Units must appear in order, each at most once.
+        let mut units = [
+            ('d', &mut tarp.day),
+            ('h', &mut tarp.hour),
+            ('m', &mut tarp.minute),
+            ('s', &mut tarp.second),
+        ]
+        .into_iter();
+        loop {
+            let pos = cursor.pos;
+            let rest = &cursor.text[pos..];
+            let Some((_, value)) = units.find(|(unit, _)| rest.starts_with(*unit)) else {
+                return Err(Error::Syntax { pos });
+            };
+            cursor.pos += 1;
+            *value = cursor.number(10, 1, 20)?.0;
+            if cursor.text[cursor.pos..].starts_with("..") || !cursor.eat(".") {
+                break;
+            }
+        }
+    }
+    cursor.fraction(&mut tarp)?;
+    cursor.end()?;
+    yule(&tarp).ok_or(Error::OutOfRange { pos: 1 })
+}
+
+/// Converts a fraction of a second in nanoseconds into units of 2^-64 seconds, rounding up so that
+/// [`nanos`] recovers it exactly.
+fn fraction(nanos: u32) -> u64 {
+    ((u128::from(nanos) << 64).div_ceil(1_000_000_000)) as u64
+}
+
+/// Converts a fraction of a second in units of 2^-64 seconds into nanoseconds, rounding down.
+fn nanos(fraction: u64) -> u32 {
+    ((u128::from(fraction) * 1_000_000_000) >> 64) as u32
+}
+
+/// Converts a duration into a `@dr`.
+pub(crate) fn from_duration(duration: Duration) -> u128 {
+    u128::from(duration.as_secs()) << 64 | u128::from(fraction(duration.subsec_nanos()))
+}
+
+/// Converts a `@dr` into a duration.
+pub(crate) fn to_duration(time: u128) -> Duration {
+    Duration::new((time >> 64) as u64, nanos(time as u64))
+}
+
+/// Converts a system time into a `@da`, returning `None` if it precedes the Urbit epoch.
+pub(crate) fn from_system_time(time: SystemTime) -> Option<u128> {
+    let epoch = u128::from(UNIX_EPOCH_SECS) << 64;
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since) => epoch.checked_add(from_duration(since)),
+        Err(err) => epoch.checked_sub(from_duration(err.duration())),
+    }
+}
+
+/// Converts a `@da` into a system time, returning `None` if it can't be represented.
+pub(crate) fn to_system_time(time: u128) -> Option<SystemTime> {
+    let epoch = u128::from(UNIX_EPOCH_SECS) << 64;
+    if time >= epoch {
+        UNIX_EPOCH.checked_add(to_duration(time - epoch))
+    } else {
+        // Round away from the Unix epoch, as rounding `time` down would.
+        let before = epoch - time;
+        let mut duration = to_duration(before);
+        if from_duration(duration) != before {
+            duration += Duration::from_nanos(1);
+        }
+        UNIX_EPOCH.checked_sub(duration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::atom::Atom;
+
+    #[test]
+    fn yore_year() {
+        let unix = u128::from(UNIX_EPOCH_SECS) << 64;
+        assert_eq!(
+            yore(unix),
+            Date {
+                ad: true,
+                year: 1970,
+                month: 1,
+                tarp: Tarp {
+                    day: 1,
+                    ..Tarp::default()
+                },
+            }
+        );
+        assert_eq!(da(0), "~292277024401-.1.1");
+        assert_eq!(parse_da("~292277024401-.1.1"), Ok(0));
+    }
+
+    #[test]
+    fn yall_yawn() {
+        // Days around the start of the epoch, 1 AD, a century that isn't a leap year, and the end
+        // of the range of 64-bit seconds.
+        let starts = [
+            0,
+            yawn(AD_YEAR, 1, 1) - 400,
+            yawn(AD_YEAR + 1900, 1, 1) - 400,
+            u128::from(u64::MAX / DAY_SECS - 800),
+        ];
+        for start in starts {
+            let start = start as u64;
+            for day in start..start + 800 {
+                let (year, month, day_of_month) = yall(day);
+                assert_eq!(yawn(year, month, day_of_month), u128::from(day));
+            }
+        }
+    }
+
+    #[test]
+    fn da_text() {
+        let dates: [(&str, u64, u64); 8] = [
+            ("~1970.1.1", UNIX_EPOCH_SECS, 0),
+            ("~2000.1.1", UNIX_EPOCH_SECS + 946_684_800, 0),
+            ("~2000.2.29", UNIX_EPOCH_SECS + 951_782_400, 0),
+            ("~1900.3.1", UNIX_EPOCH_SECS - 2_203_891_200, 0),
+            (
+                "~2022.7.8..16.43.50..1a2b",
+                UNIX_EPOCH_SECS + 1_657_298_630,
+                0x1a2b << 48,
+            ),
+            (
+                "~2022.7.8..00.00.00..0000.0000.0000.0001",
+                UNIX_EPOCH_SECS + 1_657_238_400,
+                1,
+            ),
+            ("~1.1.1", UNIX_EPOCH_SECS - 62_135_596_800, 0),
+            ("~1-.12.31..23.59.59", UNIX_EPOCH_SECS - 62_135_596_801, 0),
+        ];
+        for (text, secs, fraction) in dates {
+            let time = u128::from(secs) << 64 | u128::from(fraction);
+            assert_eq!(super::da(time), text);
+            assert_eq!(parse_da(text), Ok(time));
+            assert_eq!(Atom::from(time).to_da().as_deref(), Some(text));
+            assert_eq!(Atom::from_da(text), Ok(Atom::from(time)));
+        }
+        assert_eq!(
+            super::da(u128::MAX),
+            "~292277024853.11.8..07.00.15..ffff.ffff.ffff.ffff"
+        );
+        assert_eq!(
+            parse_da("~292277024853.11.8..07.00.15..ffff.ffff.ffff.ffff"),
+            Ok(u128::MAX)
+        );
+        assert_eq!(Atom::from(vec![1; 17]).to_da(), None);
+        // Trailing zero groups are allowed but not written.
+        assert_eq!(parse_da("~1970.1.1..00.00.00..0000"), parse_da("~1970.1.1"));
+    }
+
+    #[test]
+    fn da_errors() {
+        assert_eq!(parse_da("1970.1.1"), Err(Error::MissingSig));
+        assert_eq!(parse_da("~"), Err(Error::Syntax { pos: 1 }));
+        assert_eq!(parse_da("~1970"), Err(Error::Syntax { pos: 5 }));
+        assert_eq!(parse_da("~1970.1.1."), Err(Error::Syntax { pos: 9 }));
+        assert_eq!(
+            parse_da("~1970.1.1..1.00.00"),
+            Err(Error::Syntax { pos: 12 })
+        );
+        assert_eq!(
+            parse_da("~1970.1.1..01.00.00..1"),
+            Err(Error::Syntax { pos: 22 })
+        );
+        assert_eq!(
+            parse_da("~1970.1.1..01.00.00..0001.0002.0003.0004.0005"),
+            Err(Error::Syntax { pos: 40 })
+        );
+        assert_eq!(parse_da("~0.1.1"), Err(Error::OutOfRange { pos: 1 }));
+        assert_eq!(parse_da("~0-.1.1"), Err(Error::OutOfRange { pos: 1 }));
+        assert_eq!(parse_da("~1970.13.1"), Err(Error::OutOfRange { pos: 6 }));
+        assert_eq!(parse_da("~2023.2.29"), Err(Error::OutOfRange { pos: 8 }));
+        assert_eq!(parse_da("~1900.2.29"), Err(Error::OutOfRange { pos: 8 }));
+        assert_eq!(
+            parse_da("~1970.1.1..24.00.00"),
+            Err(Error::OutOfRange { pos: 11 })
+        );
+        assert_eq!(
+            parse_da("~1970.1.1..00.60.00"),
+            Err(Error::OutOfRange { pos: 14 })
+        );
+        assert_eq!(
+            parse_da("~292277024402-.1.1"),
+            Err(Error::OutOfRange { pos: 1 })
+        );
+        assert_eq!(
+            parse_da("~292277024854.1.1"),
+            Err(Error::OutOfRange { pos: 1 })
+        );
+        assert_eq!(
+            parse_da("~99999999999999999999.1.1"),
+            Err(Error::OutOfRange { pos: 1 })
+        );
+    }
+
+    #[test]
+    fn dr_text() {
+        let durations: [(&str, u64, u64); 7] = [
+            ("~s0", 0, 0),
+            ("~s1", 1, 0),
+            ("~s0..8000", 0, 1 << 63),
+            ("~m1", 60, 0),
+            ("~h1.s1", 3_601, 0),
+            ("~d1", 86_400, 0),
+            ("~d1.h2.m3.s4..0001", 93_784, 1 << 48),
+        ];
+        for (text, secs, fraction) in durations {
+            let time = u128::from(secs) << 64 | u128::from(fraction);
+            assert_eq!(super::dr(time), text);
+            assert_eq!(parse_dr(text), Ok(time));
+            assert_eq!(Atom::from(time).to_dr().as_deref(), Some(text));
+            assert_eq!(Atom::from_dr(text), Ok(Atom::from(time)));
+        }
+        // Units may exceed their usual range.
+        assert_eq!(parse_dr("~h25.s90"), parse_dr("~d1.h1.m1.s30"));
+
+        assert_eq!(parse_dr("s1"), Err(Error::MissingSig));
+        assert_eq!(parse_dr("~"), Err(Error::Syntax { pos: 1 }));
+        assert_eq!(parse_dr("~s1.m1"), Err(Error::Syntax { pos: 4 }));
+        assert_eq!(parse_dr("~h1.h1"), Err(Error::Syntax { pos: 4 }));
+        assert_eq!(parse_dr("~x1"), Err(Error::Syntax { pos: 1 }));
+        assert_eq!(parse_dr("~s"), Err(Error::Syntax { pos: 2 }));
+        assert_eq!(parse_dr("~s1."), Err(Error::Syntax { pos: 4 }));
+        assert_eq!(
+            parse_dr("~d213503982334602"),
+            Err(Error::OutOfRange { pos: 1 })
+        );
+    }
+
+    #[test]
+    fn duration() {
+        let durations = [
+            Duration::ZERO,
+            Duration::from_nanos(1),
+            Duration::from_millis(500),
+            Duration::new(1, 999_999_999),
+            Duration::MAX,
+        ];
+        for duration in durations {
+            assert_eq!(to_duration(from_duration(duration)), duration);
+            assert_eq!(Atom::from_duration(duration).to_duration(), Some(duration));
+        }
+        assert_eq!(from_duration(Duration::from_millis(500)), 1 << 63);
+        assert_eq!(to_duration(1 << 63), Duration::from_millis(500));
+        assert_eq!(to_duration(1), Duration::ZERO);
+        assert_eq!(Atom::from(vec![1; 17]).to_duration(), None);
+    }
+
+    #[test]
+    fn system_time() {
+        let times = [
+            UNIX_EPOCH,
+            UNIX_EPOCH + Duration::new(1_657_298_630, 123_456_789),
+            UNIX_EPOCH - Duration::new(2_203_891_200, 1),
+        ];
+        for time in times {
+            assert_eq!(to_system_time(from_system_time(time).unwrap()), Some(time));
+            let atom = Atom::from_system_time(time).expect("system time");
+            assert_eq!(atom.to_system_time(), Some(time));
+        }
+        assert_eq!(
+            Atom::from_system_time(UNIX_EPOCH).map(|atom| atom.to_da()),
+            Some(Some(String::from("~1970.1.1")))
+        );
+        let time = Atom::from_da("~2022.7.8..16.43.50..8000").unwrap();
+        assert_eq!(
+            time.to_system_time(),
+            Some(UNIX_EPOCH + Duration::new(1_657_298_630, 500_000_000))
+        );
+    }
+}
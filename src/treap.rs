@@ -0,0 +1,558 @@
+//! Treaps balanced by `mor`, the structure of Hoon's `map`, `set`, `mop`, and `qeu`.
+//!
+//! A treap is `~` or a cell `[n l r]` of a node `n` and the treaps `l` and `r`. A map's nodes are
+//! `[key value]` cells, and a set's nodes are its elements. The nodes of maps and sets are ordered
+//! by `gor`, and the nodes of mops by a comparator, as described by an [`Order`]. A queue's nodes
+//! are ordered by when they were added.
+
+use crate::{cell::Cell, noun::Noun, Rc};
+
+/// Returns the node and subtrees of a nonempty treap, or `None` if `tree` is malformed.
+pub(crate) fn parts(tree: &Noun) -> Option<(&Noun, &Noun, &Noun)> {
+    let Noun::Cell(tree) = tree else {
+        return None;
+    };
+    let Noun::Cell(subtrees) = tree.tail_ref() else {
+        return None;
+    };
+    Some((tree.head_ref(), subtrees.head_ref(), subtrees.tail_ref()))
+}
+
+/// Returns shared pointers to the node and subtrees of a nonempty treap.
+///
+/// # Panics
+///
+/// Panics if `tree` is malformed.
+pub(crate) fn split(tree: &Noun) -> (Rc<Noun>, Rc<Noun>, Rc<Noun>) {
+    match tree {
+        Noun::Cell(tree) => match tree.tail_ref() {
+            Noun::Cell(subtrees) => (tree.head(), subtrees.head(), subtrees.tail()),
+            Noun::Atom(_) => panic!("malformed treap"),
+        },
+        Noun::Atom(_) => panic!("malformed treap"),
+    }
+}
+
+/// Creates a treap from a node and subtrees.
+pub(crate) fn node(n: Rc<Noun>, l: Rc<Noun>, r: Rc<Noun>) -> Rc<Noun> {
+    Rc::<Noun>::from(Cell::from([n, l, r]))
+}
+
+/// How the nodes of a treap are ordered.
+#[derive(Clone, Copy)]
+pub(crate) struct Order {
+    /// Returns the part of a node that's ordered: a map node's key or a set node itself.
+    key: fn(&Noun) -> &Noun,
+    /// Returns whether a key belongs to the left of another.
+    before: fn(&Noun, &Noun) -> bool,
+}
+
+impl Order {
+    /// The order of a map's `[key value]` nodes: by key, using `gor`.
+    pub(crate) const MAP: Self = Self::new(map_key, Noun::gor);
+
+    /// The order of a set's nodes: by node, using `gor`.
+    pub(crate) const SET: Self = Self::new(set_key, Noun::gor);
+
+    /// Creates an order from a function that returns the key of a node and a function that
+    /// returns whether a key belongs to the left of another.
+    pub(crate) const fn new(key: fn(&Noun) -> &Noun, before: fn(&Noun, &Noun) -> bool) -> Self {
+        Self { key, before }
+    }
+
+    /// Returns the key of `node`.
+    pub(crate) fn key<'a>(&self, node: &'a Noun) -> &'a Noun {
+        (self.key)(node)
+    }
+
+    /// Returns whether key `a` belongs to the left of key `b`.
+    fn before(&self, a: &Noun, b: &Noun) -> bool {
+        (self.before)(a, b)
+    }
+}
+
+/// Returns the key of a map's node, the head of its `[key value]` cell.
+pub(crate) fn map_key(node: &Noun) -> &Noun {
+    match node {
+        Noun::Cell(node) => node.head_ref(),
+        Noun::Atom(_) => node,
+    }
+}
+
+/// Returns the key of a set's node, the node itself.
+fn set_key(node: &Noun) -> &Noun {
+    node
+}
+
+/// Inserts `n` into `tree`, replacing the node with the same key. This is `put` from Hoon's `++by`,
+/// `++in`, and `++on`, so the result has exactly the shape Hoon would build. If `n` is already in
+/// `tree`, `tree` itself is returned.
+///
+/// # Panics
+///
+/// Panics if `tree` is malformed.
+pub(crate) fn put(tree: &Rc<Noun>, n: Rc<Noun>, order: Order) -> Rc<Noun> {
+    if tree.is_null() {
+        let null = Rc::new(Noun::null());
+        return node(n, null.clone(), null);
+    }
+    let (tree_n, l, r) = split(tree);
+    if order.key(&n) == order.key(&tree_n) {
+        return if n == tree_n {
+            tree.clone()
+        } else {
+            node(n, l, r)
+        };
+    }
+    if order.before(order.key(&n), order.key(&tree_n)) {
+        let d = put(&l, n, order);
+        if Rc::ptr_eq(&d, &l) {
+            return tree.clone();
+        }
+        let (d_n, d_l, d_r) = split(&d);
+        if Noun::mor(order.key(&tree_n), order.key(&d_n)) {
+            node(tree_n, d, r)
+        } else {
+            node(d_n, d_l, node(tree_n, d_r, r))
+        }
+    } else {
+        let d = put(&r, n, order);
+        if Rc::ptr_eq(&d, &r) {
+            return tree.clone();
+        }
+        let (d_n, d_l, d_r) = split(&d);
+        if Noun::mor(order.key(&tree_n), order.key(&d_n)) {
+            node(tree_n, l, d)
+        } else {
+            node(d_n, node(tree_n, l, d_l), d_r)
+        }
+    }
+}
+
+/// Returns the node of `tree` whose key is `k`, or `None` if there's no such node or `tree` is
+/// malformed.
+pub(crate) fn get<'a>(mut tree: &'a Noun, k: &Noun, order: Order) -> Option<&'a Noun> {
+    while let Some((n, l, r)) = parts(tree) {
+        if k == order.key(n) {
+            return Some(n);
+        }
+        tree = if order.before(k, order.key(n)) { l } else { r };
+    }
+    None
+}
+
+/// Removes the node whose key is `k` from `tree`. This is `del` from Hoon's `++by`, `++in`, and
+/// `++on`. If there's no such node, `tree` itself is returned.
+///
+/// # Panics
+///
+/// Panics if `tree` is malformed.
+pub(crate) fn del(tree: &Rc<Noun>, k: &Noun, order: Order) -> Rc<Noun> {
+    if tree.is_null() {
+        return tree.clone();
+    }
+    let (n, l, r) = split(tree);
+    if k == order.key(&n) {
+        merge(&l, &r, order)
+    } else if order.before(k, order.key(&n)) {
+        let d = del(&l, k, order);
+        if Rc::ptr_eq(&d, &l) {
+            tree.clone()
+        } else {
+            node(n, d, r)
+        }
+    } else {
+        let d = del(&r, k, order);
+        if Rc::ptr_eq(&d, &r) {
+            tree.clone()
+        } else {
+            node(n, l, d)
+        }
+    }
+}
+
+/// Merges treaps `l` and `r`, every node of which comes after every node of `l`, into one treap.
+pub(crate) fn merge(l: &Rc<Noun>, r: &Rc<Noun>, order: Order) -> Rc<Noun> {
+    if l.is_null() {
+        return r.clone();
+    }
+    if r.is_null() {
+        return l.clone();
+    }
+    let (l_n, l_l, l_r) = split(l);
+    let (r_n, r_l, r_r) = split(r);
+    if Noun::mor(order.key(&l_n), order.key(&r_n)) {
+        node(l_n, l_l, merge(&l_r, r, order))
+    } else {
+        node(r_n, merge(l, &r_l, order), r_r)
+    }
+}
+
+/// Returns the union of `a` and `b`, taking the node from `b` when both have a node with the same
+/// key. This is `uni` from Hoon's `++by` and `++in`.
+///
+/// # Panics
+///
+/// Panics if `a` or `b` is malformed.
+pub(crate) fn uni(a: &Rc<Noun>, b: &Rc<Noun>, order: Order) -> Rc<Noun> {
+    if b.is_null() || Rc::ptr_eq(a, b) {
+        return a.clone();
+    }
+    if a.is_null() {
+        return b.clone();
+    }
+    let null = Rc::new(Noun::null());
+    let (a_n, a_l, a_r) = split(a);
+    let (b_n, b_l, b_r) = split(b);
+    if order.key(&a_n) == order.key(&b_n) {
+        node(b_n, uni(&a_l, &b_l, order), uni(&a_r, &b_r, order))
+    } else if Noun::mor(order.key(&a_n), order.key(&b_n)) {
+        if order.before(order.key(&b_n), order.key(&a_n)) {
+            let l = uni(&a_l, &node(b_n, b_l, null), order);
+            uni(&node(a_n, l, a_r), &b_r, order)
+        } else {
+            let r = uni(&a_r, &node(b_n, null, b_r), order);
+            uni(&node(a_n, a_l, r), &b_l, order)
+        }
+    } else if order.before(order.key(&a_n), order.key(&b_n)) {
+        let l = uni(&node(a_n, a_l, null), &b_l, order);
+        uni(&a_r, &node(b_n, l, b_r), order)
+    } else {
+        let r = uni(&node(a_n, null, a_r), &b_r, order);
+        uni(&a_l, &node(b_n, b_l, r), order)
+    }
+}
+
+/// Splits `tree` into the treaps of the nodes that come before and after `n`, leaving out the node
+/// with the same key as `n`. This is `bif` from Hoon's `++by` and `++in`.
+fn bif(tree: &Rc<Noun>, n: &Rc<Noun>, order: Order) -> (Rc<Noun>, Rc<Noun>) {
+    /// Returns `tree` rotated so that the node with the same key as `n` is at the root, inserting
+    /// `n` if there's no such node.
+    fn raise(tree: &Rc<Noun>, n: &Rc<Noun>, order: Order) -> Rc<Noun> {
+        if tree.is_null() {
+            let null = Rc::new(Noun::null());
+            return node(n.clone(), null.clone(), null);
+        }
+        let (tree_n, l, r) = split(tree);
+        if order.key(n) == order.key(&tree_n) {
+            tree.clone()
+        } else if order.before(order.key(n), order.key(&tree_n)) {
+            let (d_n, d_l, d_r) = split(&raise(&l, n, order));
+            node(d_n, d_l, node(tree_n, d_r, r))
+        } else {
+            let (d_n, d_l, d_r) = split(&raise(&r, n, order));
+            node(d_n, node(tree_n, l, d_l), d_r)
+        }
+    }
+
+    let (_, l, r) = split(&raise(tree, n, order));
+    (l, r)
+}
+
+/// Returns `a` without the nodes whose keys are in `b`. This is `dif` from Hoon's `++by` and
+/// `++in`.
+///
+/// # Panics
+///
+/// Panics if `a` or `b` is malformed.
+pub(crate) fn dif(a: &Rc<Noun>, b: &Rc<Noun>, order: Order) -> Rc<Noun> {
+    if a.is_null() || b.is_null() {
+        return a.clone();
+    }
+    let (b_n, b_l, b_r) = split(b);
+    let (l, r) = bif(a, &b_n, order);
+    merge(&dif(&l, &b_l, order), &dif(&r, &b_r, order), order)
+}
+
+/// Returns whether `tree` is a well-formed treap: every node is ordered relative to its ancestors,
+/// comes before its subtrees' nodes in `mor` order, and has a key unlike every other node's. This
+/// is `apt` from Hoon's `++by`, `++in`, and `++on`.
+pub(crate) fn apt(tree: &Noun, order: Order) -> bool {
+    // Subtrees to check, with the keys that their nodes' keys must come after and before.
+    let mut pending = vec![(tree, None, None)];
+    while let Some((tree, after, before)) = pending.pop() {
+        if tree.is_null() {
+            continue;
+        }
+        let Some((n, l, r)) = parts(tree) else {
+            return false;
+        };
+        let k = order.key(n);
+        if after.is_some_and(|after: &Noun| k == after || !order.before(after, k))
+            || before.is_some_and(|before: &Noun| k == before || !order.before(k, before))
+        {
+            return false;
+        }
+        for subtree in [l, r] {
+            if let Some((sub_n, _, _)) = parts(subtree) {
+                if k == order.key(sub_n) || !Noun::mor(k, order.key(sub_n)) {
+                    return false;
+                }
+            }
+        }
+        pending.push((l, after, Some(k)));
+        pending.push((r, Some(k), before));
+    }
+    true
+}
+
+/// Removes the leftmost node from `tree`, returning the node and the rest of `tree`, or `None` if
+/// `tree` is empty. This is `pop` from Hoon's `++on`.
+///
+/// # Panics
+///
+/// Panics if `tree` is malformed.
+pub(crate) fn pop(tree: &Rc<Noun>) -> Option<(Rc<Noun>, Rc<Noun>)> {
+    if tree.is_null() {
+        return None;
+    }
+    let (n, l, r) = split(tree);
+    match pop(&l) {
+        Some((first, l)) => Some((first, node(n, l, r))),
+        None => Some((n, r)),
+    }
+}
+
+/// Returns the treap of the nodes of `tree` whose keys come strictly after `start` and strictly
+/// before `end`, either of which may be unbounded. This is `lot` from Hoon's `++on`.
+///
+/// # Panics
+///
+/// Panics if `tree` is malformed.
+pub(crate) fn lot(
+    tree: &Rc<Noun>,
+    start: Option<&Noun>,
+    end: Option<&Noun>,
+    order: Order,
+) -> Rc<Noun> {
+    /// Removes the nodes whose keys come before or are `start`.
+    fn after(tree: &Rc<Noun>, start: &Noun, order: Order) -> Rc<Noun> {
+        if tree.is_null() {
+            return tree.clone();
+        }
+        let (n, l, r) = split(tree);
+        if order.key(&n) == start {
+            r
+        } else if order.before(order.key(&n), start) {
+            after(&r, start, order)
+        } else {
+            node(n, after(&l, start, order), r)
+        }
+    }
+
+    /// Removes the nodes whose keys come after or are `end`.
+    fn before(tree: &Rc<Noun>, end: &Noun, order: Order) -> Rc<Noun> {
+        if tree.is_null() {
+            return tree.clone();
+        }
+        let (n, l, r) = split(tree);
+        if order.key(&n) == end {
+            l
+        } else if order.before(order.key(&n), end) {
+            node(n, l, before(&r, end, order))
+        } else {
+            before(&l, end, order)
+        }
+    }
+
+    let tree = match start {
+        Some(start) => after(tree, start, order),
+        None => tree.clone(),
+    };
+    match end {
+        Some(end) => before(&tree, end, order),
+        None => tree,
+    }
+}
+
+/// Returns whether `tree` is a well-formed queue: every node comes before its subtrees' nodes in
+/// `mor` order. This is `apt` from Hoon's `++to`.
+pub(crate) fn is_heap(tree: &Noun) -> bool {
+    let mut pending = vec![tree];
+    while let Some(tree) = pending.pop() {
+        if tree.is_null() {
+            continue;
+        }
+        let Some((n, l, r)) = parts(tree) else {
+            return false;
+        };
+        for subtree in [l, r] {
+            if let Some((sub_n, _, _)) = parts(subtree) {
+                if !Noun::mor(n, sub_n) {
+                    return false;
+                }
+            }
+        }
+        pending.extend([l, r]);
+    }
+    true
+}
+
+/// An iterator over the nodes of a treap, either in the order Hoon's `tap` lists the nodes of a
+/// map, set, or queue (the right subtree, then the node, then the left subtree) or in the order it
+/// lists the nodes of a mop (the left subtree, then the node, then the right subtree).
+///
+/// The iteration stops early if the treap is malformed.
+#[derive(Clone)]
+pub(crate) struct Nodes<'a> {
+    /// The subtree whose nodes are listed next.
+    tree: &'a Noun,
+    /// Nodes whose first subtrees have been listed, with their second subtrees.
+    pending: Vec<(&'a Noun, &'a Noun)>,
+    /// Whether the left subtree is listed first.
+    left_first: bool,
+    /// Whether a malformed subtree was found.
+    malformed: bool,
+}
+
+impl<'a> Nodes<'a> {
+    /// Creates an iterator over the nodes of `tree` that lists the right subtree first.
+    pub(crate) fn new(tree: &'a Noun) -> Self {
+        Self {
+            tree,
+            pending: Vec::new(),
+            left_first: false,
+            malformed: false,
+        }
+    }
+
+    /// Creates an iterator over the nodes of `tree` that lists the left subtree first.
+    pub(crate) fn in_order(tree: &'a Noun) -> Self {
+        Self {
+            left_first: true,
+            ..Self::new(tree)
+        }
+    }
+}
+
+impl<'a> Iterator for Nodes<'a> {
+    type Item = &'a Noun;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.malformed {
+            return None;
+        }
+        while !self.tree.is_null() {
+            let Some((n, l, r)) = parts(self.tree) else {
+                self.malformed = true;
+                return None;
+            };
+            let (first, second) = if self.left_first { (l, r) } else { (r, l) };
+            self.pending.push((n, second));
+            self.tree = first;
+        }
+        let (n, second) = self.pending.pop()?;
+        self.tree = second;
+        Some(n)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{
+        atom::Atom,
+        convert::{self, FromNoun, ToNoun},
+        serdes::Cue,
+    };
+    use std::fmt::Debug;
+
+    /// Cues `jammed`, a jammed noun written as an atom in Hoon's syntax.
+    pub(crate) fn cue(jammed: &str) -> Noun {
+        match jammed.parse().expect("jammed noun") {
+            Noun::Atom(jammed) => Noun::cue(jammed).expect("cue"),
+            Noun::Cell(_) => panic!("expected an atom"),
+        }
+    }
+
+    /// Returns `(n, n * n)` for every `n` in `keys`, collected into a map or mop.
+    pub(crate) fn squares<K, T>(keys: impl IntoIterator<Item = u32>) -> T
+    where
+        K: From<u32>,
+        T: FromIterator<(K, K)>,
+    {
+        keys.into_iter()
+            .map(|n| (K::from(n), K::from(n * n)))
+            .collect()
+    }
+
+    /// Asserts that `~` converts to an empty `T`, that every noun in `malformed` fails to convert
+    /// with [`convert::Error::InvalidTreap`], and that `value` converts to a noun and back.
+    pub(crate) fn check_try_from<T>(value: T, malformed: impl IntoIterator<Item = Noun>)
+    where
+        T: Debug + Default + FromNoun + PartialEq + ToNoun,
+        T: for<'a> TryFrom<&'a Noun, Error = convert::Error>,
+    {
+        assert_eq!(T::try_from(&Noun::null()).expect("empty"), T::default());
+        for noun in malformed {
+            assert!(
+                matches!(T::try_from(&noun), Err(convert::Error::InvalidTreap)),
+                "{}",
+                noun
+            );
+        }
+        assert_eq!(T::from_noun(&value.to_noun()).expect("from noun"), value);
+    }
+
+    /// Asserts that `tree` is ordered by `gor` and balanced by `mor`, returning its size.
+    fn check(tree: &Noun) -> usize {
+        if tree.is_null() {
+            return 0;
+        }
+        let (n, l, r) = parts(tree).expect("treap");
+        for (subtree, left) in [(l, true), (r, false)] {
+            if let Some((sub_n, _, _)) = parts(subtree) {
+                assert!(Noun::mor(n, sub_n));
+                for m in Nodes::new(subtree) {
+                    assert_eq!(Noun::gor(m, n), left);
+                }
+            }
+        }
+        check(l) + 1 + check(r)
+    }
+
+    #[test]
+    fn put() {
+        let mut tree = Rc::new(Noun::null());
+        for i in 0..200u32 {
+            tree = super::put(&tree, Rc::<Noun>::from(Atom::from(i % 150)), Order::SET);
+        }
+        assert_eq!(check(&tree), 150);
+        let mut elems: Vec<_> = Nodes::new(&tree)
+            .map(|n| match n {
+                Noun::Atom(n) => n.as_u32().expect("u32"),
+                Noun::Cell(_) => panic!("expected an atom"),
+            })
+            .collect();
+        elems.sort_unstable();
+        assert_eq!(elems, (0..150).collect::<Vec<_>>());
+
+        let again = super::put(&tree, Rc::<Noun>::from(Atom::from(7u8)), Order::SET);
+        assert!(Rc::ptr_eq(&tree, &again));
+
+        let sets = [[1u8, 2, 3], [3, 1, 2], [2, 3, 1]].map(|elems| {
+            elems.iter().fold(Rc::new(Noun::null()), |tree, &i| {
+                super::put(&tree, Rc::<Noun>::from(Atom::from(i)), Order::SET)
+            })
+        });
+        assert_eq!(sets[0], sets[1]);
+        assert_eq!(sets[1], sets[2]);
+    }
+
+    #[test]
+    fn malformed() {
+        let atom = Noun::from(Atom::from(1u8));
+        let pair = Noun::from(Cell::from([1u8, 2]));
+        assert!(parts(&atom).is_none());
+        assert!(parts(&pair).is_none());
+        let subtree = Noun::from(Cell::from([
+            Rc::new(atom.clone()),
+            Rc::new(Noun::null()),
+            Rc::new(pair.clone()),
+        ]));
+        for noun in [atom, pair, subtree] {
+            assert_eq!(Nodes::new(&noun).next(), None);
+            assert!(!apt(&noun, Order::SET));
+        }
+    }
+}